@@ -1,48 +1,275 @@
 use crate::error::InterpreterError;
 use crate::symbol_table::SymbolTable;
+use crate::value::Value;
 
-use super::{ASTNode, ASTSemanticAnalysis, ASTSemanticExpression, ASTStatement, VarNode};
+use super::{
+    ASTNode, ASTSemanticAnalysis, ASTSemanticExpression, ASTStatement, BinaryOpType, NodeKind,
+    VarNode,
+};
 
 pub struct AssignNode {
-    var: Box<VarNode>,
-    expression: Box<dyn ASTSemanticExpression>,
+    vars: Vec<VarNode>,
+    expressions: Vec<Box<dyn ASTSemanticExpression>>,
+    op_type: Option<BinaryOpType>,
+    constant: bool,
 }
 
 impl AssignNode {
-    pub fn new(var: Box<VarNode>, expression: Box<dyn ASTSemanticExpression>) -> Self {
-        Self { var, expression }
+    pub fn new(var: VarNode, expression: Box<dyn ASTSemanticExpression>) -> Self {
+        Self {
+            vars: vec![var],
+            expressions: vec![expression],
+            op_type: None,
+            constant: false,
+        }
+    }
+
+    /// Assignment in the form of `x op= expr`, e.g. `x += 1`.
+    pub fn compound(
+        var: VarNode,
+        expression: Box<dyn ASTSemanticExpression>,
+        op_type: BinaryOpType,
+    ) -> Self {
+        Self {
+            vars: vec![var],
+            expressions: vec![expression],
+            op_type: Some(op_type),
+            constant: false,
+        }
+    }
+
+    /// Assignment in the form of `a, b = expr1, expr2`. All expressions are
+    /// evaluated before any variable is assigned.
+    pub fn multiple(vars: Vec<VarNode>, expressions: Vec<Box<dyn ASTSemanticExpression>>) -> Self {
+        Self {
+            vars,
+            expressions,
+            op_type: None,
+            constant: false,
+        }
+    }
+
+    /// Declares the assigned variables as constants, e.g. `const g = 9.81`.
+    pub fn constant(mut self) -> Self {
+        self.constant = true;
+        self
+    }
+
+    pub fn is_compound(&self) -> bool {
+        self.op_type.is_some()
     }
 }
 
 impl ASTStatement for AssignNode {
-    fn execute(&self, symtab: &mut SymbolTable) -> Result<f64, InterpreterError> {
-        let value = self.expression.eval(symtab)?;
+    fn execute(&self, symtab: &mut SymbolTable) -> Result<Value, InterpreterError> {
+        let mut values: Vec<Value> = vec![];
+
+        for (var, expression) in self.vars.iter().zip(self.expressions.iter()) {
+            let value = expression.eval(symtab)?;
+
+            match &self.op_type {
+                Some(op_type) => {
+                    let current = symtab
+                        .get(var.name())
+                        .cloned()
+                        .ok_or(InterpreterError::UndefinedSymbol(var.name().clone()))?;
+
+                    values.push(op_type.apply(&current, &value)?);
+                }
+                None => values.push(value),
+            }
+        }
 
-        symtab.insert(self.var.name().to_string(), value);
+        for (var, value) in self.vars.iter().zip(values.iter()) {
+            if self.constant {
+                symtab.insert_constant(var.name().to_string(), value.clone());
+            } else {
+                symtab.insert(var.name().to_string(), value.clone());
+            }
+        }
 
-        Ok(value)
+        Ok(values.pop().unwrap_or(Value::Number(0.)))
     }
 }
 
-impl ASTNode for AssignNode {}
+impl ASTNode for AssignNode {
+    fn kind(&self) -> NodeKind<'_> {
+        NodeKind::Assign(&self.vars, &self.expressions, self.op_type, self.constant)
+    }
+}
 
 impl ASTSemanticAnalysis for AssignNode {
     fn check_semantic(&self, symtab: &mut SymbolTable) -> Result<(), InterpreterError> {
-        if !self.expression.pure() {
-            let res = self.expression.check_semantic(symtab);
+        for expression in self.expressions.iter() {
+            if !expression.pure() {
+                expression.check_semantic(symtab)?;
+            }
+        }
+
+        for var in self.vars.iter() {
+            if symtab.is_global(var.name()) {
+                return Err(InterpreterError::RedefineBuiltinSymbol(var.name().clone()));
+            }
 
-            if res.is_err() {
-                return res;
+            if symtab.is_constant(var.name()) {
+                return Err(InterpreterError::ReassignConstant(var.name().clone()));
+            }
+
+            if self.op_type.is_some() {
+                var.check_semantic(symtab)?;
             }
         }
 
-        if symtab.is_global(self.var.name()) {
-            Err(InterpreterError::RedefineBuiltinSymbol(
-                self.var.name().clone(),
-            ))
-        } else {
-            symtab.insert(self.var.name().clone(), 0.);
-            Ok(())
+        for var in self.vars.iter() {
+            if self.constant {
+                symtab.insert_constant(var.name().clone(), 0.);
+            } else {
+                symtab.insert(var.name().clone(), 0.);
+            }
         }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::tests::MockNode;
+    use crate::ast::{ASTSemanticAnalysis, ASTStatement, BinaryOpType, VarNode};
+    use crate::error::InterpreterError;
+    use crate::symbol_table::SymbolTable;
+    use crate::value::Value;
+
+    use super::AssignNode;
+
+    #[test]
+    fn test_execute() {
+        let mut symtab = SymbolTable::new();
+        let node = AssignNode::new(
+            VarNode::new("x".to_string()),
+            Box::new(MockNode::new().expect_eval(3.)),
+        );
+
+        let result = node.execute(&mut symtab);
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 3.);
+        assert_eq!(symtab.get(&"x".to_string()), Some(&Value::Number(3.)));
+    }
+
+    #[test]
+    fn test_execute_compound() {
+        let mut symtab = SymbolTable::new();
+        let node = AssignNode::compound(
+            VarNode::new("x".to_string()),
+            Box::new(MockNode::new().expect_eval(3.)),
+            BinaryOpType::MUL,
+        );
+
+        symtab.insert("x".to_string(), 5.);
+
+        let result = node.execute(&mut symtab);
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 15.);
+        assert_eq!(symtab.get(&"x".to_string()), Some(&Value::Number(15.)));
+    }
+
+    #[test]
+    fn test_execute_multiple() {
+        let mut symtab = SymbolTable::new();
+        let node = AssignNode::multiple(
+            vec![VarNode::new("a".to_string()), VarNode::new("b".to_string())],
+            vec![
+                Box::new(VarNode::new("b".to_string())),
+                Box::new(VarNode::new("a".to_string())),
+            ],
+        );
+
+        symtab.insert("a".to_string(), 1.);
+        symtab.insert("b".to_string(), 2.);
+
+        let result = node.execute(&mut symtab);
+
+        assert!(result.is_ok());
+        assert_eq!(symtab.get(&"a".to_string()), Some(&Value::Number(2.)));
+        assert_eq!(symtab.get(&"b".to_string()), Some(&Value::Number(1.)));
+    }
+
+    #[test]
+    fn test_check_semantic_builtin() {
+        let mut symtab = SymbolTable::new();
+        let node = AssignNode::new(VarNode::new("pi".to_string()), Box::new(MockNode::new()));
+
+        let result = node.check_semantic(&mut symtab);
+
+        assert!(matches!(
+            result,
+            Err(InterpreterError::RedefineBuiltinSymbol(_))
+        ));
+    }
+
+    #[test]
+    fn test_check_semantic_compound_undefined() {
+        let mut symtab = SymbolTable::new();
+        let node = AssignNode::compound(
+            VarNode::new("x".to_string()),
+            Box::new(MockNode::new()),
+            BinaryOpType::ADD,
+        );
+
+        let result = node.check_semantic(&mut symtab);
+
+        assert!(matches!(result, Err(InterpreterError::UndefinedSymbol(_))));
+    }
+
+    #[test]
+    fn test_check_semantic_compound() {
+        let mut symtab = SymbolTable::new();
+        let node = AssignNode::compound(
+            VarNode::new("x".to_string()),
+            Box::new(MockNode::new()),
+            BinaryOpType::ADD,
+        );
+
+        symtab.insert("x".to_string(), 0.);
+
+        assert!(node.check_semantic(&mut symtab).is_ok());
+    }
+
+    #[test]
+    fn test_execute_constant() {
+        let mut symtab = SymbolTable::new();
+        let node = AssignNode::new(
+            VarNode::new("g".to_string()),
+            Box::new(MockNode::new().expect_eval(9.81)),
+        )
+        .constant();
+
+        let result = node.execute(&mut symtab);
+
+        assert!(result.is_ok());
+        assert!(symtab.is_constant(&"g".to_string()));
+        assert_eq!(symtab.get(&"g".to_string()), Some(&Value::Number(9.81)));
+    }
+
+    #[test]
+    fn test_check_semantic_reassign_constant() {
+        let mut symtab = SymbolTable::new();
+        let declare =
+            AssignNode::new(VarNode::new("g".to_string()), Box::new(MockNode::new())).constant();
+        let reassign = AssignNode::new(VarNode::new("g".to_string()), Box::new(MockNode::new()));
+        let redeclare =
+            AssignNode::new(VarNode::new("g".to_string()), Box::new(MockNode::new())).constant();
+
+        assert!(declare.check_semantic(&mut symtab).is_ok());
+        assert!(matches!(
+            reassign.check_semantic(&mut symtab),
+            Err(InterpreterError::ReassignConstant(_))
+        ));
+        assert!(matches!(
+            redeclare.check_semantic(&mut symtab),
+            Err(InterpreterError::ReassignConstant(_))
+        ));
     }
 }
@@ -1,16 +1,88 @@
 use crate::error::InterpreterError;
 use crate::symbol_table::SymbolTable;
+use crate::value::Value;
 
-pub trait ASTNode {}
+use super::{BinaryOpType, CompareOpType, UnaryOpType, UnitExpr, VarNode};
+
+/// Structure of a node, for code that takes a tree apart, such as symbolic
+/// differentiation and the formatter.
+pub enum NodeKind<'a> {
+    Number(&'a Value),
+    Var(&'a String),
+    Unary(&'a dyn ASTSemanticExpression, UnaryOpType),
+    Binary(
+        &'a dyn ASTSemanticExpression,
+        &'a dyn ASTSemanticExpression,
+        BinaryOpType,
+    ),
+    PercentChange(
+        &'a dyn ASTSemanticExpression,
+        &'a dyn ASTSemanticExpression,
+        BinaryOpType,
+    ),
+    Compare(
+        &'a dyn ASTSemanticExpression,
+        &'a dyn ASTSemanticExpression,
+        CompareOpType,
+    ),
+    Conditional(
+        &'a dyn ASTSemanticExpression,
+        &'a dyn ASTSemanticExpression,
+        &'a dyn ASTSemanticExpression,
+    ),
+    Call(&'a String, &'a [Box<dyn ASTSemanticExpression>]),
+    List(&'a [Box<dyn ASTSemanticExpression>]),
+    Index(&'a dyn ASTSemanticExpression, &'a dyn ASTSemanticExpression),
+    Interval(&'a dyn ASTSemanticExpression, &'a dyn ASTSemanticExpression),
+    Quantity(&'a dyn ASTSemanticExpression, &'a UnitExpr),
+    Convert(&'a dyn ASTSemanticExpression, &'a UnitExpr),
+    Diff(&'a dyn ASTSemanticExpression, &'a String),
+    Simplify(&'a dyn ASTSemanticExpression),
+    Poly(&'a dyn ASTSemanticExpression, Option<&'a String>),
+    Series(
+        BinaryOpType,
+        &'a String,
+        &'a dyn ASTSemanticExpression,
+        &'a dyn ASTSemanticExpression,
+        &'a dyn ASTSemanticExpression,
+    ),
+    Solve(
+        &'a dyn ASTSemanticExpression,
+        &'a dyn ASTSemanticExpression,
+        &'a String,
+        &'a [Box<dyn ASTSemanticExpression>],
+    ),
+    Lambda(&'a [String], &'a dyn ASTSemanticExpression),
+    Assign(
+        &'a [VarNode],
+        &'a [Box<dyn ASTSemanticExpression>],
+        Option<BinaryOpType>,
+        bool,
+    ),
+    Function(
+        &'a String,
+        &'a [String],
+        &'a dyn ASTSemanticExpression,
+        bool,
+    ),
+    StatementList(&'a [Box<dyn ASTSemanticStatement>]),
+    Other,
+}
+
+pub trait ASTNode {
+    fn kind(&self) -> NodeKind<'_> {
+        NodeKind::Other
+    }
+}
 
 pub trait ASTStatement {
-    fn execute(&self, symtab: &mut SymbolTable) -> Result<f64, InterpreterError>;
+    fn execute(&self, symtab: &mut SymbolTable) -> Result<Value, InterpreterError>;
 }
 
 pub trait ASTExpression {
     fn pure(&self) -> bool;
 
-    fn eval(&self, symtab: &mut SymbolTable) -> Result<f64, InterpreterError>;
+    fn eval(&self, symtab: &mut SymbolTable) -> Result<Value, InterpreterError>;
 }
 
 pub trait ASTSemanticAnalysis {
@@ -1,13 +1,595 @@
 use crate::error::InterpreterError;
+use crate::math;
+use crate::matrix;
 use crate::symbol_table::SymbolTable;
+use crate::value::{
+    BigInt, Complex, Decimal, Dual, Interval, Polynomial, Quantity, Rational, RoundingMode,
+    Uncertain, Value,
+};
 
-use super::{ASTExpression, ASTNode, ASTSemanticAnalysis, ASTSemanticExpression};
+use super::{ASTExpression, ASTNode, ASTSemanticAnalysis, ASTSemanticExpression, NodeKind};
 
+/// Powers with more digits than this are computed in floating point.
+const MAX_POW_DIGITS: u64 = 100_000;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub enum BinaryOpType {
     ADD,
     SUB,
     MUL,
+    MATMUL,
+    PLUSMINUS,
     DIV,
+    POW,
+    FLOORDIV,
+    MOD,
+    BITAND,
+    BITOR,
+    XOR,
+    SHL,
+    SHR,
+}
+
+impl BinaryOpType {
+    pub fn apply(&self, left: &Value, right: &Value) -> Result<Value, InterpreterError> {
+        match (left, right) {
+            (Value::Function(_), _) | (_, Value::Function(_)) => Err(InterpreterError::TypeError(
+                format!("{:?} is not defined for functions", self),
+            )),
+            _ if *self == BinaryOpType::MATMUL => matrix::matmul(left, right),
+            (Value::List(_), _) | (_, Value::List(_)) => self.apply_list(left, right),
+            (Value::Polynomial(_), _) | (_, Value::Polynomial(_)) => {
+                self.apply_polynomial(left, right)
+            }
+            _ if *self == BinaryOpType::PLUSMINUS => self.measurement(left, right),
+            (Value::Interval(_), _) | (_, Value::Interval(_)) => self.apply_interval(left, right),
+            (Value::Uncertain(_), _) | (_, Value::Uncertain(_)) => {
+                self.apply_uncertain(left, right)
+            }
+            (Value::Dual(_), _) | (_, Value::Dual(_)) => self.apply_dual(left, right),
+            (Value::Quantity(_), _) | (_, Value::Quantity(_)) => self.apply_quantity(left, right),
+            (Value::Complex(_), _) | (_, Value::Complex(_)) => {
+                self.apply_complex(&left.to_complex(), &right.to_complex())
+            }
+            _ if *self == BinaryOpType::POW
+                && left.to_f64() < 0.
+                && right.to_f64().is_finite()
+                && right.to_f64().fract() != 0. =>
+            {
+                self.apply_complex(&left.to_complex(), &right.to_complex())
+            }
+            (Value::Decimal(left), Value::Decimal(right)) => self.apply_decimal(left, right),
+            (Value::Rational(left), Value::Rational(right)) => self.apply_rational(left, right),
+            _ => match (left.to_bigint(), right.to_bigint()) {
+                (Some(left), Some(right)) => self.apply_bigint(&left, &right),
+                _ => self
+                    .apply_number(left.to_f64(), right.to_f64())
+                    .map(Value::Number),
+            },
+        }
+    }
+
+    fn apply_decimal(&self, left: &Decimal, right: &Decimal) -> Result<Value, InterpreterError> {
+        let value = match self {
+            BinaryOpType::ADD => Some(left.add(right)),
+            BinaryOpType::SUB => Some(left.sub(right)),
+            BinaryOpType::MUL => Some(left.mul(right)),
+            BinaryOpType::DIV => Some(
+                left.div(right)
+                    .ok_or_else(|| InterpreterError::DomainError("division by zero".to_string()))?,
+            ),
+            BinaryOpType::POW => match right.to_bigint().and_then(|exponent| exponent.to_i64()) {
+                Some(exponent) => Some(left.powi(exponent).ok_or_else(|| {
+                    InterpreterError::DomainError("division by zero".to_string())
+                })?),
+                None => None,
+            },
+            BinaryOpType::FLOORDIV | BinaryOpType::MOD => {
+                let quotient = left
+                    .div(right)
+                    .ok_or_else(|| InterpreterError::DomainError("division by zero".to_string()))?
+                    .round_to(0, RoundingMode::FLOOR);
+
+                if *self == BinaryOpType::FLOORDIV {
+                    Some(quotient)
+                } else {
+                    Some(left.sub(&right.mul(&quotient)))
+                }
+            }
+            _ => {
+                let value = self.apply_number(left.to_f64(), right.to_f64())?;
+
+                Decimal::from_f64(value, left.context())
+            }
+        };
+
+        match value {
+            Some(value) => Ok(Value::Decimal(value)),
+            None => self
+                .apply_number(left.to_f64(), right.to_f64())
+                .map(Value::Number),
+        }
+    }
+
+    fn apply_rational(&self, left: &Rational, right: &Rational) -> Result<Value, InterpreterError> {
+        let value = match self {
+            BinaryOpType::ADD => Some(left.add(right)),
+            BinaryOpType::SUB => Some(left.sub(right)),
+            BinaryOpType::MUL => Some(left.mul(right)),
+            BinaryOpType::DIV => Some(
+                left.div(right)
+                    .ok_or_else(|| InterpreterError::DomainError("division by zero".to_string()))?,
+            ),
+            BinaryOpType::POW if right.is_integer() => match right.numerator().to_i64() {
+                Some(exponent) => Some(left.powi(exponent).ok_or_else(|| {
+                    InterpreterError::DomainError("division by zero".to_string())
+                })?),
+                None => None,
+            },
+            BinaryOpType::POW => None,
+            BinaryOpType::FLOORDIV | BinaryOpType::MOD => {
+                let quotient = Rational::from_bigint(
+                    left.div(right)
+                        .ok_or_else(|| {
+                            InterpreterError::DomainError("division by zero".to_string())
+                        })?
+                        .floor(),
+                );
+
+                if *self == BinaryOpType::FLOORDIV {
+                    Some(quotient)
+                } else {
+                    Some(left.sub(&right.mul(&quotient)))
+                }
+            }
+            _ => {
+                let value = self.apply_number(left.to_f64(), right.to_f64())?;
+
+                Rational::from_f64(value)
+            }
+        };
+
+        match value {
+            Some(value) => Ok(Value::Rational(value)),
+            None => self
+                .apply_number(left.to_f64(), right.to_f64())
+                .map(Value::Number),
+        }
+    }
+
+    /// Applies the operator element-wise, a scalar operand is broadcast to
+    /// every element of the other.
+    fn apply_list(&self, left: &Value, right: &Value) -> Result<Value, InterpreterError> {
+        let values = match (left, right) {
+            (Value::List(lvalues), Value::List(rvalues)) if lvalues.len() != rvalues.len() => {
+                return Err(InterpreterError::ShapeMismatch(
+                    format!("{:?}", left.shape()),
+                    format!("{:?}", right.shape()),
+                ))
+            }
+            (Value::List(lvalues), Value::List(rvalues)) => lvalues
+                .iter()
+                .zip(rvalues)
+                .map(|(lvalue, rvalue)| self.apply(lvalue, rvalue))
+                .collect::<Result<Vec<Value>, InterpreterError>>()?,
+            (Value::List(lvalues), _) => lvalues
+                .iter()
+                .map(|lvalue| self.apply(lvalue, right))
+                .collect::<Result<Vec<Value>, InterpreterError>>()?,
+            (_, Value::List(rvalues)) => rvalues
+                .iter()
+                .map(|rvalue| self.apply(left, rvalue))
+                .collect::<Result<Vec<Value>, InterpreterError>>()?,
+            _ => unreachable!(),
+        };
+
+        Ok(Value::List(values))
+    }
+
+    /// Polynomial arithmetic, where division is long division with `//` for
+    /// the quotient and `%` for the remainder.
+    fn apply_polynomial(&self, left: &Value, right: &Value) -> Result<Value, InterpreterError> {
+        if let (BinaryOpType::POW, Value::Polynomial(lvalue)) = (self, left) {
+            return match right
+                .to_bigint()
+                .and_then(|exponent| exponent.to_i64())
+                .and_then(|exponent| u32::try_from(exponent).ok())
+            {
+                Some(exponent) => Ok(Value::Polynomial(lvalue.powi(exponent))),
+                None => Err(InterpreterError::TypeError(format!(
+                    "polynomial exponent must be a non-negative integer, found {}",
+                    right
+                ))),
+            };
+        }
+
+        let lvalue = self.polynomial(left, right)?;
+        let rvalue = self.polynomial(right, left)?;
+
+        let value = match self {
+            BinaryOpType::ADD => lvalue.add(&rvalue)?,
+            BinaryOpType::SUB => lvalue.sub(&rvalue)?,
+            BinaryOpType::MUL => lvalue.mul(&rvalue)?,
+            BinaryOpType::DIV if rvalue.degree() == 0 && !rvalue.is_zero() => {
+                lvalue.scale(1. / rvalue.coefficients()[0])
+            }
+            BinaryOpType::DIV if !rvalue.is_zero() => {
+                return Err(InterpreterError::TypeError(format!(
+                    "{} is not divisible by {}, use // and % for the quotient and remainder",
+                    left, right
+                )))
+            }
+            BinaryOpType::DIV | BinaryOpType::FLOORDIV | BinaryOpType::MOD => {
+                let (quotient, remainder) = lvalue
+                    .div_rem(&rvalue)?
+                    .ok_or_else(|| InterpreterError::DomainError("division by zero".to_string()))?;
+
+                if *self == BinaryOpType::MOD {
+                    remainder
+                } else {
+                    quotient
+                }
+            }
+            _ => {
+                return Err(InterpreterError::TypeError(format!(
+                    "{:?} is not defined for polynomials",
+                    self
+                )))
+            }
+        };
+
+        Ok(Value::Polynomial(value))
+    }
+
+    /// The operand as a polynomial, a real constant takes the variable of
+    /// the other operand.
+    fn polynomial(&self, value: &Value, other: &Value) -> Result<Polynomial, InterpreterError> {
+        let var = match other {
+            Value::Polynomial(other) => other.var().clone(),
+            _ => String::new(),
+        };
+
+        match value {
+            Value::Polynomial(value) => Ok(value.clone()),
+            Value::Number(_) | Value::Decimal(_) | Value::Rational(_) | Value::Integer(_) => {
+                Ok(Polynomial::constant(value.to_f64(), var))
+            }
+            _ => Err(InterpreterError::TypeError(format!(
+                "{:?} expects real or polynomial operands, found {}",
+                self, value
+            ))),
+        }
+    }
+
+    fn apply_interval(&self, left: &Value, right: &Value) -> Result<Value, InterpreterError> {
+        let lvalue = self.interval(left)?;
+        let rvalue = self.interval(right)?;
+
+        let value = match self {
+            BinaryOpType::ADD => lvalue.add(&rvalue),
+            BinaryOpType::SUB => lvalue.sub(&rvalue),
+            BinaryOpType::MUL => lvalue.mul(&rvalue),
+            BinaryOpType::DIV => lvalue
+                .div(&rvalue)
+                .ok_or_else(|| InterpreterError::DomainError("division by zero".to_string()))?,
+            BinaryOpType::POW => match right
+                .to_bigint()
+                .and_then(|exponent| exponent.to_i64())
+                .and_then(|exponent| i32::try_from(exponent).ok())
+            {
+                Some(exponent) => lvalue
+                    .powi(exponent)
+                    .ok_or_else(|| InterpreterError::DomainError("division by zero".to_string()))?,
+                None => lvalue.powf(&rvalue).ok_or_else(|| {
+                    InterpreterError::DomainError(format!("{}^{}", lvalue, rvalue))
+                })?,
+            },
+            _ => {
+                return Err(InterpreterError::TypeError(format!(
+                    "{:?} is not defined for intervals",
+                    self
+                )))
+            }
+        };
+
+        Ok(Value::Interval(value))
+    }
+
+    fn interval(&self, value: &Value) -> Result<Interval, InterpreterError> {
+        value.to_interval().ok_or_else(|| {
+            InterpreterError::TypeError(format!(
+                "{:?} expects real operands, found {}",
+                self, value
+            ))
+        })
+    }
+
+    /// `value ± uncertainty`, a measured value with a new error source.
+    fn measurement(&self, value: &Value, uncertainty: &Value) -> Result<Value, InterpreterError> {
+        let value = self.uncertain(value)?;
+        let uncertainty = match uncertainty {
+            Value::Uncertain(_) => return Err(self.uncertain_error(uncertainty)),
+            _ => self.uncertain(uncertainty)?.value(),
+        };
+
+        if uncertainty.is_nan() || uncertainty < 0. {
+            return Err(InterpreterError::DomainError(format!(
+                "negative uncertainty {}",
+                uncertainty
+            )));
+        }
+
+        Ok(Value::Uncertain(
+            value.add(&Uncertain::new(0., uncertainty)),
+        ))
+    }
+
+    fn apply_uncertain(&self, left: &Value, right: &Value) -> Result<Value, InterpreterError> {
+        let lvalue = self.uncertain(left)?;
+        let rvalue = self.uncertain(right)?;
+
+        let value = match self {
+            BinaryOpType::ADD => lvalue.add(&rvalue),
+            BinaryOpType::SUB => lvalue.sub(&rvalue),
+            BinaryOpType::MUL => lvalue.mul(&rvalue),
+            BinaryOpType::DIV => lvalue
+                .div(&rvalue)
+                .ok_or_else(|| InterpreterError::DomainError("division by zero".to_string()))?,
+            BinaryOpType::POW => lvalue
+                .powf(&rvalue)
+                .ok_or_else(|| InterpreterError::DomainError(format!("({})^{}", left, right)))?,
+            _ => {
+                return Err(InterpreterError::TypeError(format!(
+                    "{:?} is not defined for measurements",
+                    self
+                )))
+            }
+        };
+
+        Ok(Value::Uncertain(value))
+    }
+
+    fn uncertain(&self, value: &Value) -> Result<Uncertain, InterpreterError> {
+        value
+            .to_uncertain()
+            .ok_or_else(|| self.uncertain_error(value))
+    }
+
+    fn uncertain_error(&self, value: &Value) -> InterpreterError {
+        InterpreterError::TypeError(format!("{:?} expects real operands, found {}", self, value))
+    }
+
+    fn apply_dual(&self, left: &Value, right: &Value) -> Result<Value, InterpreterError> {
+        let lvalue = self.dual(left)?;
+        let rvalue = self.dual(right)?;
+
+        let value = match self {
+            BinaryOpType::ADD => lvalue.add(&rvalue),
+            BinaryOpType::SUB => lvalue.sub(&rvalue),
+            BinaryOpType::MUL => lvalue.mul(&rvalue),
+            BinaryOpType::DIV => lvalue
+                .div(&rvalue)
+                .ok_or_else(|| InterpreterError::DomainError("division by zero".to_string()))?,
+            BinaryOpType::POW => lvalue.powf(&rvalue).ok_or_else(|| {
+                InterpreterError::DomainError(format!("({})^{}", lvalue.value(), rvalue.value()))
+            })?,
+            BinaryOpType::FLOORDIV => {
+                Dual::constant(self.apply_number(lvalue.value(), rvalue.value())?)
+            }
+            // `a % b` is `a - b * (a // b)`, where the quotient is piecewise
+            // constant.
+            BinaryOpType::MOD => {
+                let remainder = self.apply_number(lvalue.value(), rvalue.value())?;
+                let quotient = (lvalue.value() - remainder) / rvalue.value();
+
+                lvalue.sub(&rvalue.scale(quotient)).chain(remainder, 1.)
+            }
+            _ => {
+                return Err(InterpreterError::TypeError(format!(
+                    "{:?} is not defined for dual numbers",
+                    self
+                )))
+            }
+        };
+
+        Ok(Value::Dual(value))
+    }
+
+    fn dual(&self, value: &Value) -> Result<Dual, InterpreterError> {
+        value.to_dual().ok_or_else(|| {
+            InterpreterError::TypeError(format!(
+                "{:?} expects real operands, found {}",
+                self, value
+            ))
+        })
+    }
+
+    fn apply_quantity(&self, left: &Value, right: &Value) -> Result<Value, InterpreterError> {
+        let lvalue = self.quantity(left)?;
+        let rvalue = self.quantity(right)?;
+
+        match self {
+            BinaryOpType::ADD | BinaryOpType::SUB => {
+                let rvalue = if *self == BinaryOpType::SUB {
+                    rvalue.neg()
+                } else {
+                    rvalue
+                };
+
+                lvalue
+                    .add(&rvalue)
+                    .map(Value::from_quantity)
+                    .ok_or_else(|| {
+                        InterpreterError::IncompatibleUnits(
+                            lvalue.dimension().to_string(),
+                            rvalue.dimension().to_string(),
+                        )
+                    })
+            }
+            BinaryOpType::MUL => Ok(Value::from_quantity(lvalue.mul(&rvalue))),
+            BinaryOpType::DIV => Ok(Value::from_quantity(lvalue.div(&rvalue))),
+            BinaryOpType::POW if rvalue.dimension().is_dimensionless() => lvalue
+                .powf(rvalue.value())
+                .map(Value::from_quantity)
+                .ok_or_else(|| {
+                    InterpreterError::DomainError(format!("({})^{}", left, rvalue.value()))
+                }),
+            BinaryOpType::POW => Err(InterpreterError::TypeError(format!(
+                "exponent must be dimensionless, found {}",
+                right
+            ))),
+            _ => Err(InterpreterError::TypeError(format!(
+                "{:?} expects dimensionless operands",
+                self
+            ))),
+        }
+    }
+
+    fn quantity(&self, value: &Value) -> Result<Quantity, InterpreterError> {
+        match value {
+            Value::Quantity(value) => Ok(value.clone()),
+            Value::Complex(_) => Err(InterpreterError::TypeError(format!(
+                "{:?} expects real operands",
+                self
+            ))),
+            _ => Ok(Quantity::dimensionless(value.to_f64())),
+        }
+    }
+
+    fn apply_complex(&self, left: &Complex, right: &Complex) -> Result<Value, InterpreterError> {
+        let value = match self {
+            BinaryOpType::ADD => left.add(right),
+            BinaryOpType::SUB => left.sub(right),
+            BinaryOpType::MUL => left.mul(right),
+            BinaryOpType::DIV => left
+                .div(right)
+                .ok_or_else(|| InterpreterError::DomainError("division by zero".to_string()))?,
+            BinaryOpType::POW => left.pow(right),
+            _ => {
+                return Err(InterpreterError::TypeError(format!(
+                    "{:?} expects real operands",
+                    self
+                )))
+            }
+        };
+
+        Ok(Value::from_complex(value))
+    }
+
+    /// Integer arithmetic that stays exact beyond the range of `f64`, falling
+    /// back to floating point when the result is not an integer.
+    fn apply_bigint(&self, left: &BigInt, right: &BigInt) -> Result<Value, InterpreterError> {
+        let value = match self {
+            BinaryOpType::ADD => Some(left.add(right)),
+            BinaryOpType::SUB => Some(left.sub(right)),
+            BinaryOpType::MUL => Some(left.mul(right)),
+            BinaryOpType::DIV => match left.div_rem(right) {
+                Some((quotient, remainder)) if remainder.is_zero() => Some(quotient),
+                _ => None,
+            },
+            BinaryOpType::FLOORDIV | BinaryOpType::MOD => {
+                let (quotient, remainder) = left
+                    .div_floor(right)
+                    .ok_or_else(|| InterpreterError::DomainError("division by zero".to_string()))?;
+
+                if *self == BinaryOpType::FLOORDIV {
+                    Some(quotient)
+                } else {
+                    Some(remainder)
+                }
+            }
+            BinaryOpType::POW => right
+                .to_i64()
+                .and_then(|exponent| u32::try_from(exponent).ok())
+                .filter(|exponent| left.digit_count() as u64 * *exponent as u64 <= MAX_POW_DIGITS)
+                .map(|exponent| left.pow(exponent)),
+            BinaryOpType::BITAND => Some(left.bitwise(right, |left, right| left & right)),
+            BinaryOpType::BITOR => Some(left.bitwise(right, |left, right| left | right)),
+            BinaryOpType::XOR => Some(left.bitwise(right, |left, right| left ^ right)),
+            BinaryOpType::SHL | BinaryOpType::SHR => {
+                // 2^shift has fewer than shift / 3 digits.
+                let shift = right
+                    .to_i64()
+                    .and_then(|shift| u32::try_from(shift).ok())
+                    .filter(|shift| {
+                        *self == BinaryOpType::SHR
+                            || left.digit_count() as u64 + *shift as u64 / 3 <= MAX_POW_DIGITS
+                    })
+                    .ok_or_else(|| InterpreterError::DomainError(format!("shift by {}", right)))?;
+
+                if *self == BinaryOpType::SHL {
+                    Some(left.shl(shift))
+                } else {
+                    Some(left.shr(shift))
+                }
+            }
+            _ => None,
+        };
+
+        match value {
+            Some(value) => Ok(Value::from_bigint(value)),
+            None => self
+                .apply_number(left.to_f64(), right.to_f64())
+                .map(Value::Number),
+        }
+    }
+
+    fn apply_number(&self, left: f64, right: f64) -> Result<f64, InterpreterError> {
+        match self {
+            BinaryOpType::ADD => Ok(left + right),
+            BinaryOpType::SUB => Ok(left - right),
+            BinaryOpType::MUL => Ok(left * right),
+            BinaryOpType::DIV => Ok(left / right),
+            BinaryOpType::POW => Ok(left.powf(right)),
+            BinaryOpType::FLOORDIV | BinaryOpType::MOD if right == 0. => Err(
+                InterpreterError::DomainError("division by zero".to_string()),
+            ),
+            BinaryOpType::FLOORDIV => Ok((left / right).floor()),
+            BinaryOpType::MOD => {
+                let remainder = left % right;
+
+                if remainder != 0. && (remainder < 0.) != (right < 0.) {
+                    Ok(remainder + right)
+                } else {
+                    Ok(remainder)
+                }
+            }
+            _ => self.apply_integer(left, right),
+        }
+    }
+
+    fn apply_integer(&self, left: f64, right: f64) -> Result<f64, InterpreterError> {
+        let lvalue = math::to_integer(left).ok_or_else(|| self.type_error(left))?;
+        let rvalue = math::to_integer(right).ok_or_else(|| self.type_error(right))?;
+
+        let value = match self {
+            BinaryOpType::BITAND => lvalue & rvalue,
+            BinaryOpType::BITOR => lvalue | rvalue,
+            BinaryOpType::XOR => lvalue ^ rvalue,
+            BinaryOpType::SHL | BinaryOpType::SHR => {
+                let shift = u32::try_from(rvalue)
+                    .ok()
+                    .filter(|shift| *shift < i64::BITS)
+                    .ok_or_else(|| InterpreterError::DomainError(format!("shift by {}", rvalue)))?;
+
+                if *self == BinaryOpType::SHL {
+                    lvalue << shift
+                } else {
+                    lvalue >> shift
+                }
+            }
+            _ => unreachable!(),
+        };
+
+        Ok(value as f64)
+    }
+
+    fn type_error(&self, value: f64) -> InterpreterError {
+        InterpreterError::TypeError(format!(
+            "{:?} expects integer operands, found {}",
+            self, value
+        ))
+    }
 }
 
 pub struct BinaryOpNode {
@@ -30,23 +612,22 @@ impl BinaryOpNode {
     }
 }
 
-impl ASTNode for BinaryOpNode {}
+impl ASTNode for BinaryOpNode {
+    fn kind(&self) -> NodeKind<'_> {
+        NodeKind::Binary(self.left.as_ref(), self.right.as_ref(), self.op_type)
+    }
+}
 
 impl ASTExpression for BinaryOpNode {
     fn pure(&self) -> bool {
         self.left.pure() && self.right.pure()
     }
 
-    fn eval(&self, symtab: &mut SymbolTable) -> Result<f64, InterpreterError> {
+    fn eval(&self, symtab: &mut SymbolTable) -> Result<Value, InterpreterError> {
         let left = self.left.eval(symtab)?;
         let right = self.right.eval(symtab)?;
 
-        match self.op_type {
-            BinaryOpType::ADD => Ok(left + right),
-            BinaryOpType::SUB => Ok(left - right),
-            BinaryOpType::MUL => Ok(left * right),
-            BinaryOpType::DIV => Ok(left / right),
-        }
+        self.op_type.apply(&left, &right)
     }
 }
 
@@ -70,6 +651,10 @@ mod tests {
     use crate::ast::tests::MockNode;
     use crate::error::InterpreterError;
     use crate::symbol_table::SymbolTable;
+    use crate::value::{
+        BigInt, Complex, DecimalContext, Dimension, Dual, Interval, NumberMode, Polynomial,
+        Quantity, Unit, Value,
+    };
 
     use super::{ASTExpression, ASTSemanticAnalysis, BinaryOpNode, BinaryOpType};
 
@@ -129,6 +714,531 @@ mod tests {
         assert_eq!(result.unwrap(), lvalue / rvalue);
     }
 
+    #[test]
+    fn test_eval_pow() {
+        let lvalue: f64 = 2.;
+        let rvalue: f64 = 10.;
+        let mut symtab = SymbolTable::new();
+        let left = MockNode::new().expect_eval(lvalue);
+        let right = MockNode::new().expect_eval(rvalue);
+        let node = BinaryOpNode::new(Box::new(left), Box::new(right), BinaryOpType::POW);
+        let result = node.eval(&mut symtab);
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 1024.);
+    }
+
+    #[test]
+    fn test_eval_decimal() {
+        let mode = NumberMode::DECIMAL(DecimalContext::default());
+        let mut symtab = SymbolTable::new();
+        let left = MockNode::new().expect_eval(mode.literal(0.1));
+        let right = MockNode::new().expect_eval(mode.literal(0.2));
+        let node = BinaryOpNode::new(Box::new(left), Box::new(right), BinaryOpType::ADD);
+        let result = node.eval(&mut symtab);
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), mode.literal(0.3));
+    }
+
+    #[test]
+    fn test_eval_decimal_division_by_zero() {
+        let mode = NumberMode::DECIMAL(DecimalContext::default());
+        let mut symtab = SymbolTable::new();
+        let left = MockNode::new().expect_eval(mode.literal(1.));
+        let right = MockNode::new().expect_eval(mode.literal(0.));
+        let node = BinaryOpNode::new(Box::new(left), Box::new(right), BinaryOpType::DIV);
+        let result = node.eval(&mut symtab);
+
+        assert!(matches!(result, Err(InterpreterError::DomainError(_))));
+    }
+
+    #[test]
+    fn test_eval_decimal_with_number() {
+        let mode = NumberMode::DECIMAL(DecimalContext::default());
+        let mut symtab = SymbolTable::new();
+        let left = MockNode::new().expect_eval(mode.literal(0.5));
+        let right = MockNode::new().expect_eval(2.);
+        let node = BinaryOpNode::new(Box::new(left), Box::new(right), BinaryOpType::MUL);
+        let result = node.eval(&mut symtab);
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 1.);
+    }
+
+    #[test]
+    fn test_eval_rational() {
+        let mode = NumberMode::RATIONAL;
+        let mut symtab = SymbolTable::new();
+        let left = BinaryOpNode::new(
+            Box::new(MockNode::new().expect_eval(mode.literal(1.))),
+            Box::new(MockNode::new().expect_eval(mode.literal(3.))),
+            BinaryOpType::DIV,
+        );
+        let right = BinaryOpNode::new(
+            Box::new(MockNode::new().expect_eval(mode.literal(1.))),
+            Box::new(MockNode::new().expect_eval(mode.literal(6.))),
+            BinaryOpType::DIV,
+        );
+        let node = BinaryOpNode::new(Box::new(left), Box::new(right), BinaryOpType::ADD);
+        let result = node.eval(&mut symtab);
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), mode.literal(0.5));
+    }
+
+    #[test]
+    fn test_eval_rational_irrational_power() {
+        let mode = NumberMode::RATIONAL;
+        let mut symtab = SymbolTable::new();
+        let left = MockNode::new().expect_eval(mode.literal(2.));
+        let right = MockNode::new().expect_eval(mode.literal(0.5));
+        let node = BinaryOpNode::new(Box::new(left), Box::new(right), BinaryOpType::POW);
+        let result = node.eval(&mut symtab);
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 2f64.sqrt());
+    }
+
+    #[test]
+    fn test_eval_bigint() {
+        let mut symtab = SymbolTable::new();
+        let cases = [
+            (
+                BinaryOpType::POW,
+                2.,
+                100.,
+                "1267650600228229401496703205376",
+            ),
+            (
+                BinaryOpType::MUL,
+                9007199254740992.,
+                3.,
+                "27021597764222976",
+            ),
+            (BinaryOpType::ADD, 9007199254740992., 1., "9007199254740993"),
+            (BinaryOpType::FLOORDIV, -7., 2., "-4"),
+            (BinaryOpType::MOD, -7., 2., "1"),
+            (BinaryOpType::MOD, 7., -2., "-1"),
+        ];
+
+        for (op_type, lvalue, rvalue, expected) in cases {
+            let left = MockNode::new().expect_eval(lvalue);
+            let right = MockNode::new().expect_eval(rvalue);
+            let node = BinaryOpNode::new(Box::new(left), Box::new(right), op_type);
+            let result = node.eval(&mut symtab);
+
+            assert!(result.is_ok());
+            assert_eq!(result.unwrap().to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn test_eval_bigint_promotion() {
+        let mut symtab = SymbolTable::new();
+        let left = BinaryOpNode::new(
+            Box::new(MockNode::new().expect_eval(2.)),
+            Box::new(MockNode::new().expect_eval(100.)),
+            BinaryOpType::POW,
+        );
+        let right = MockNode::new().expect_eval(2.);
+        let node = BinaryOpNode::new(Box::new(left), Box::new(right), BinaryOpType::DIV);
+        let result = node.eval(&mut symtab);
+
+        assert!(matches!(result, Ok(Value::Integer(_))));
+        assert_eq!(
+            result.unwrap().to_string(),
+            "633825300114114700748351602688"
+        );
+    }
+
+    #[test]
+    fn test_eval_modulo() {
+        let mut symtab = SymbolTable::new();
+        let left = MockNode::new().expect_eval(7.5);
+        let right = MockNode::new().expect_eval(-2.);
+        let node = BinaryOpNode::new(Box::new(left), Box::new(right), BinaryOpType::MOD);
+        let result = node.eval(&mut symtab);
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), -0.5);
+    }
+
+    #[test]
+    fn test_eval_modulo_by_zero() {
+        let mut symtab = SymbolTable::new();
+
+        for op_type in [BinaryOpType::FLOORDIV, BinaryOpType::MOD] {
+            let left = MockNode::new().expect_eval(7.);
+            let right = MockNode::new().expect_eval(0.);
+            let node = BinaryOpNode::new(Box::new(left), Box::new(right), op_type);
+            let result = node.eval(&mut symtab);
+
+            assert!(matches!(result, Err(InterpreterError::DomainError(_))));
+        }
+    }
+
+    #[test]
+    fn test_eval_complex() {
+        let mut symtab = SymbolTable::new();
+        let cases = [
+            (
+                BinaryOpType::ADD,
+                Complex::new(3., 4.),
+                Complex::new(0., -4.),
+                Value::Number(3.),
+            ),
+            (
+                BinaryOpType::MUL,
+                Complex::new(3., 4.),
+                Complex::new(3., -4.),
+                Value::Number(25.),
+            ),
+            (
+                BinaryOpType::DIV,
+                Complex::new(3., 4.),
+                Complex::new(1., -2.),
+                Value::Complex(Complex::new(-1., 2.)),
+            ),
+        ];
+
+        for (op_type, lvalue, rvalue, expected) in cases {
+            let left = MockNode::new().expect_eval(Value::Complex(lvalue));
+            let right = MockNode::new().expect_eval(Value::Complex(rvalue));
+            let node = BinaryOpNode::new(Box::new(left), Box::new(right), op_type);
+            let result = node.eval(&mut symtab);
+
+            assert!(result.is_ok());
+            assert_eq!(result.unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_eval_negative_base_fractional_power() {
+        let mut symtab = SymbolTable::new();
+        let left = MockNode::new().expect_eval(-4.);
+        let right = MockNode::new().expect_eval(0.5);
+        let node = BinaryOpNode::new(Box::new(left), Box::new(right), BinaryOpType::POW);
+        let result = node.eval(&mut symtab);
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), Value::Complex(Complex::new(0., 2.)));
+    }
+
+    #[test]
+    fn test_eval_complex_bitwise() {
+        let mut symtab = SymbolTable::new();
+        let left = MockNode::new().expect_eval(Value::Complex(Complex::new(0., 1.)));
+        let right = MockNode::new().expect_eval(1.);
+        let node = BinaryOpNode::new(Box::new(left), Box::new(right), BinaryOpType::BITAND);
+        let result = node.eval(&mut symtab);
+
+        assert!(matches!(result, Err(InterpreterError::TypeError(_))));
+    }
+
+    #[test]
+    fn test_eval_quantity() {
+        let mut symtab = SymbolTable::new();
+        let metre = Unit::new("m", 1., Dimension([1, 0, 0, 0, 0, 0, 0]));
+        let second = Unit::new("s", 1., Dimension([0, 0, 1, 0, 0, 0, 0]));
+        let distance = Value::Quantity(Quantity::new(6., metre));
+        let time = Value::Quantity(Quantity::new(2., second));
+
+        let node = BinaryOpNode::new(
+            Box::new(MockNode::new().expect_eval(distance.clone())),
+            Box::new(MockNode::new().expect_eval(time.clone())),
+            BinaryOpType::DIV,
+        );
+
+        assert_eq!(node.eval(&mut symtab).unwrap().to_string(), "3 m/s");
+
+        let node = BinaryOpNode::new(
+            Box::new(MockNode::new().expect_eval(distance)),
+            Box::new(MockNode::new().expect_eval(time)),
+            BinaryOpType::ADD,
+        );
+
+        assert!(matches!(
+            node.eval(&mut symtab),
+            Err(InterpreterError::IncompatibleUnits(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_eval_list() {
+        let mut symtab = SymbolTable::new();
+        let list = |values: &[f64]| Value::List(values.iter().map(|x| Value::Number(*x)).collect());
+
+        let node = BinaryOpNode::new(
+            Box::new(MockNode::new().expect_eval(list(&[1., 2., 3.]))),
+            Box::new(MockNode::new().expect_eval(2.)),
+            BinaryOpType::MUL,
+        );
+
+        assert_eq!(node.eval(&mut symtab).unwrap(), list(&[2., 4., 6.]));
+
+        let node = BinaryOpNode::new(
+            Box::new(MockNode::new().expect_eval(list(&[1., 2., 3.]))),
+            Box::new(MockNode::new().expect_eval(list(&[10., 20., 30.]))),
+            BinaryOpType::ADD,
+        );
+
+        assert_eq!(node.eval(&mut symtab).unwrap(), list(&[11., 22., 33.]));
+
+        let node = BinaryOpNode::new(
+            Box::new(MockNode::new().expect_eval(list(&[1., 2.]))),
+            Box::new(MockNode::new().expect_eval(list(&[1., 2., 3.]))),
+            BinaryOpType::ADD,
+        );
+
+        assert!(matches!(
+            node.eval(&mut symtab),
+            Err(InterpreterError::ShapeMismatch(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_eval_interval() {
+        let mut symtab = SymbolTable::new();
+        let interval = |lo: f64, hi: f64| Value::Interval(Interval::new(lo, hi).unwrap());
+
+        let node = BinaryOpNode::new(
+            Box::new(MockNode::new().expect_eval(interval(1., 2.))),
+            Box::new(MockNode::new().expect_eval(interval(-1., 3.))),
+            BinaryOpType::MUL,
+        );
+
+        assert_eq!(node.eval(&mut symtab).unwrap(), interval(-2., 6.));
+
+        let node = BinaryOpNode::new(
+            Box::new(MockNode::new().expect_eval(1.)),
+            Box::new(MockNode::new().expect_eval(interval(-1., 1.))),
+            BinaryOpType::DIV,
+        );
+
+        assert_eq!(
+            node.eval(&mut symtab).unwrap(),
+            Value::Interval(Interval::entire())
+        );
+
+        let node = BinaryOpNode::new(
+            Box::new(MockNode::new().expect_eval(interval(-2., 3.))),
+            Box::new(MockNode::new().expect_eval(2.)),
+            BinaryOpType::POW,
+        );
+
+        assert_eq!(node.eval(&mut symtab).unwrap(), interval(0., 9.));
+
+        let node = BinaryOpNode::new(
+            Box::new(MockNode::new().expect_eval(interval(1., 2.))),
+            Box::new(MockNode::new().expect_eval(1.)),
+            BinaryOpType::BITAND,
+        );
+
+        assert!(matches!(
+            node.eval(&mut symtab),
+            Err(InterpreterError::TypeError(_))
+        ));
+    }
+
+    #[test]
+    fn test_eval_uncertain() {
+        let mut symtab = SymbolTable::new();
+        let node = BinaryOpNode::new(
+            Box::new(MockNode::new().expect_eval(12.3)),
+            Box::new(MockNode::new().expect_eval(0.2)),
+            BinaryOpType::PLUSMINUS,
+        );
+        let measurement = node.eval(&mut symtab).unwrap();
+
+        assert_eq!(measurement.to_string(), "12.3 ± 0.2");
+
+        let node = BinaryOpNode::new(
+            Box::new(MockNode::new().expect_eval(measurement.clone())),
+            Box::new(MockNode::new().expect_eval(2.)),
+            BinaryOpType::MUL,
+        );
+
+        assert_eq!(node.eval(&mut symtab).unwrap().to_string(), "24.6 ± 0.4");
+
+        let node = BinaryOpNode::new(
+            Box::new(MockNode::new().expect_eval(measurement.clone())),
+            Box::new(MockNode::new().expect_eval(measurement)),
+            BinaryOpType::SUB,
+        );
+
+        assert_eq!(node.eval(&mut symtab).unwrap().to_string(), "0 ± 0");
+
+        let node = BinaryOpNode::new(
+            Box::new(MockNode::new().expect_eval(1.)),
+            Box::new(MockNode::new().expect_eval(-0.1)),
+            BinaryOpType::PLUSMINUS,
+        );
+
+        assert!(matches!(
+            node.eval(&mut symtab),
+            Err(InterpreterError::DomainError(_))
+        ));
+    }
+
+    #[test]
+    fn test_eval_dual() {
+        let x = Value::Dual(Dual::variable(3., 0, 1));
+        let derivative = |left: Value, right: Value, op_type: BinaryOpType| match op_type
+            .apply(&left, &right)
+            .unwrap()
+        {
+            Value::Dual(value) => value.derivative(0),
+            value => panic!("expected a dual number, found {}", value),
+        };
+
+        assert_eq!(derivative(x.clone(), x.clone(), BinaryOpType::MUL), 6.);
+        assert_eq!(
+            derivative(Value::Number(1.), x.clone(), BinaryOpType::DIV),
+            -1. / 9.
+        );
+        assert_eq!(
+            derivative(x.clone(), Value::Number(2.), BinaryOpType::POW),
+            6.
+        );
+        assert_eq!(
+            derivative(x.clone(), Value::Number(2.), BinaryOpType::MOD),
+            1.
+        );
+        assert_eq!(
+            derivative(Value::Number(7.), x.clone(), BinaryOpType::MOD),
+            -2.
+        );
+        assert_eq!(
+            derivative(x.clone(), Value::Number(2.), BinaryOpType::FLOORDIV),
+            0.
+        );
+        assert!(matches!(
+            BinaryOpType::BITAND.apply(&x, &Value::Number(1.)),
+            Err(InterpreterError::TypeError(_))
+        ));
+        assert!(matches!(
+            BinaryOpType::ADD.apply(&x, &Value::Complex(Complex::new(0., 1.))),
+            Err(InterpreterError::TypeError(_))
+        ));
+    }
+
+    #[test]
+    fn test_eval_polynomial() {
+        let poly = |coefficients: &[f64]| {
+            Value::Polynomial(Polynomial::new(coefficients.to_vec(), "x".to_string()))
+        };
+        // x^2 - 1 and x + 1
+        let p = poly(&[-1., 0., 1.]);
+        let q = poly(&[1., 1.]);
+
+        assert_eq!(
+            BinaryOpType::ADD.apply(&p, &Value::Number(1.)).unwrap(),
+            poly(&[0., 0., 1.])
+        );
+        assert_eq!(
+            BinaryOpType::SUB.apply(&Value::Number(1.), &q).unwrap(),
+            poly(&[0., -1.])
+        );
+        assert_eq!(
+            BinaryOpType::MUL.apply(&q, &q).unwrap(),
+            poly(&[1., 2., 1.])
+        );
+        assert_eq!(
+            BinaryOpType::POW.apply(&q, &Value::Number(2.)).unwrap(),
+            poly(&[1., 2., 1.])
+        );
+        assert_eq!(
+            BinaryOpType::DIV.apply(&p, &Value::Number(2.)).unwrap(),
+            poly(&[-0.5, 0., 0.5])
+        );
+        assert_eq!(
+            BinaryOpType::FLOORDIV.apply(&p, &q).unwrap(),
+            poly(&[-1., 1.])
+        );
+        assert_eq!(BinaryOpType::MOD.apply(&p, &q).unwrap(), poly(&[]));
+        assert!(matches!(
+            BinaryOpType::DIV.apply(&p, &q),
+            Err(InterpreterError::TypeError(_))
+        ));
+        assert!(matches!(
+            BinaryOpType::MOD.apply(&p, &Value::Number(0.)),
+            Err(InterpreterError::DomainError(_))
+        ));
+        assert!(matches!(
+            BinaryOpType::POW.apply(&p, &Value::Number(-1.)),
+            Err(InterpreterError::TypeError(_))
+        ));
+        assert!(matches!(
+            BinaryOpType::ADD.apply(&p, &Value::Complex(Complex::new(0., 1.))),
+            Err(InterpreterError::TypeError(_))
+        ));
+    }
+
+    #[test]
+    fn test_eval_bitwise() {
+        let mut symtab = SymbolTable::new();
+        let cases = [
+            (BinaryOpType::BITAND, 12., 10., 8.),
+            (BinaryOpType::BITOR, 12., 10., 14.),
+            (BinaryOpType::XOR, 12., 10., 6.),
+            (BinaryOpType::SHL, 3., 4., 48.),
+            (BinaryOpType::SHR, 48., 4., 3.),
+        ];
+
+        for (op_type, lvalue, rvalue, expected) in cases {
+            let left = MockNode::new().expect_eval(lvalue);
+            let right = MockNode::new().expect_eval(rvalue);
+            let node = BinaryOpNode::new(Box::new(left), Box::new(right), op_type);
+            let result = node.eval(&mut symtab);
+
+            assert!(result.is_ok());
+            assert_eq!(result.unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_eval_bitwise_non_integer() {
+        let mut symtab = SymbolTable::new();
+        let left = MockNode::new().expect_eval(1.5);
+        let right = MockNode::new().expect_eval(1.);
+        let node = BinaryOpNode::new(Box::new(left), Box::new(right), BinaryOpType::BITAND);
+        let result = node.eval(&mut symtab);
+
+        assert!(matches!(result, Err(InterpreterError::TypeError(_))));
+    }
+
+    #[test]
+    fn test_eval_shift_out_of_range() {
+        let mut symtab = SymbolTable::new();
+        let left = MockNode::new().expect_eval(1.);
+        let right = MockNode::new().expect_eval(-1.);
+        let node = BinaryOpNode::new(Box::new(left), Box::new(right), BinaryOpType::SHL);
+        let result = node.eval(&mut symtab);
+
+        assert!(matches!(result, Err(InterpreterError::DomainError(_))));
+    }
+
+    #[test]
+    fn test_eval_bitwise_exact() {
+        let big = |text| Value::Integer(BigInt::parse(text).unwrap());
+        let odd = big("9007199254740993");
+
+        assert_eq!(
+            BinaryOpType::SHL
+                .apply(&Value::Number(1.), &Value::Number(64.))
+                .unwrap(),
+            big("18446744073709551616")
+        );
+        assert_eq!(BinaryOpType::BITAND.apply(&odd, &odd).unwrap(), odd);
+        assert_eq!(
+            BinaryOpType::BITOR
+                .apply(&big("1152921504606846976"), &Value::Number(1.))
+                .unwrap(),
+            big("1152921504606846977")
+        );
+    }
+
     #[test]
     fn test_pure() {
         let left = MockNode::new().expect_pure(true);
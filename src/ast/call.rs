@@ -0,0 +1,215 @@
+use crate::builtins;
+use crate::error::InterpreterError;
+use crate::symbol_table::SymbolTable;
+use crate::value::Value;
+
+use super::{ASTExpression, ASTNode, ASTSemanticAnalysis, ASTSemanticExpression, NodeKind};
+
+pub struct CallNode {
+    name: String,
+    args: Vec<Box<dyn ASTSemanticExpression>>,
+}
+
+impl CallNode {
+    pub fn new(name: String, args: Vec<Box<dyn ASTSemanticExpression>>) -> Self {
+        Self { name, args }
+    }
+
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    fn eval_args(&self, symtab: &mut SymbolTable) -> Result<Vec<Value>, InterpreterError> {
+        let mut args: Vec<Value> = vec![];
+
+        for arg in self.args.iter() {
+            args.push(arg.eval(symtab)?);
+        }
+
+        Ok(args)
+    }
+}
+
+impl ASTNode for CallNode {
+    fn kind(&self) -> NodeKind<'_> {
+        NodeKind::Call(&self.name, &self.args)
+    }
+}
+
+impl ASTExpression for CallNode {
+    fn pure(&self) -> bool {
+        false
+    }
+
+    fn eval(&self, symtab: &mut SymbolTable) -> Result<Value, InterpreterError> {
+        if let Some(builtin) = builtins::lookup(self.name()) {
+            return builtin.call(&self.eval_args(symtab)?);
+        }
+
+        if let Some(builtin) = builtins::lookup_higher_order(self.name()) {
+            let args = self.eval_args(symtab)?;
+
+            return (builtin.func)(&args, symtab);
+        }
+
+        let function = match symtab.get(self.name()) {
+            Some(Value::Function(function)) => function.clone(),
+            Some(Value::Polynomial(polynomial)) if self.args.len() == 1 => {
+                let polynomial = polynomial.clone();
+                let args = self.eval_args(symtab)?;
+
+                return polynomial.eval(&args[0]);
+            }
+            _ => return Err(InterpreterError::UndefinedFunction(self.name().clone())),
+        };
+        let args = self.eval_args(symtab)?;
+
+        function.call(&args, symtab)
+    }
+}
+
+impl ASTSemanticAnalysis for CallNode {
+    fn check_semantic(&self, symtab: &mut SymbolTable) -> Result<(), InterpreterError> {
+        let (arity, optional) = match (
+            builtins::lookup(self.name()),
+            builtins::lookup_higher_order(self.name()),
+            symtab.get(self.name()),
+        ) {
+            (Some(builtin), _, _) => (Some(builtin.arity), 0),
+            (_, Some(builtin), _) => (Some(builtin.arity), builtin.optional),
+            (_, _, Some(Value::Function(function))) => (Some(function.params().len()), 0),
+            (_, _, Some(Value::Polynomial(_))) => (Some(1), 0),
+            // Variables are placeholders during the check, one may hold a
+            // function such as a derivative when the call is evaluated.
+            (_, _, Some(_)) => (None, 0),
+            _ => return Err(InterpreterError::UndefinedFunction(self.name().clone())),
+        };
+
+        if let Some(arity) = arity {
+            let len = self.args.len();
+
+            if len + optional < arity || len > arity {
+                return Err(InterpreterError::ArgumentCountMismatch(
+                    self.name().clone(),
+                    if len > arity { arity } else { arity - optional },
+                    len,
+                ));
+            }
+        }
+
+        for arg in self.args.iter() {
+            if !arg.pure() {
+                arg.check_semantic(symtab)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::tests::MockNode;
+    use crate::error::InterpreterError;
+    use crate::symbol_table::SymbolTable;
+    use crate::value::Value;
+
+    use super::{ASTExpression, ASTSemanticAnalysis, ASTSemanticExpression, CallNode};
+
+    #[test]
+    fn test_eval() {
+        let mut symtab = SymbolTable::new();
+        let node = CallNode::new(
+            "sqrt".to_string(),
+            vec![Box::new(MockNode::new().expect_eval(81.))],
+        );
+        let result = node.eval(&mut symtab);
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 9.);
+    }
+
+    #[test]
+    fn test_pure() {
+        let node = CallNode::new("sqrt".to_string(), vec![Box::new(MockNode::new())]);
+
+        assert!(!node.pure());
+    }
+
+    #[test]
+    fn test_check_semantic() {
+        let mut symtab = SymbolTable::new();
+        let node = CallNode::new(
+            "log".to_string(),
+            vec![Box::new(MockNode::new()), Box::new(MockNode::new())],
+        );
+
+        assert!(node.check_semantic(&mut symtab).is_ok());
+    }
+
+    #[test]
+    fn test_check_semantic_variable() {
+        let mut symtab = SymbolTable::new();
+        let node = CallNode::new("d".to_string(), vec![Box::new(MockNode::new())]);
+
+        symtab.insert("d".to_string(), Value::Number(0.));
+
+        assert!(node.check_semantic(&mut symtab).is_ok());
+    }
+
+    #[test]
+    fn test_check_semantic_undefined() {
+        let mut symtab = SymbolTable::new();
+        let node = CallNode::new("foo".to_string(), vec![]);
+
+        let result = node.check_semantic(&mut symtab);
+
+        assert!(matches!(
+            result,
+            Err(InterpreterError::UndefinedFunction(_))
+        ));
+    }
+
+    #[test]
+    fn test_check_semantic_argument_count() {
+        let mut symtab = SymbolTable::new();
+        let node = CallNode::new("sqrt".to_string(), vec![]);
+
+        let result = node.check_semantic(&mut symtab);
+
+        assert!(matches!(
+            result,
+            Err(InterpreterError::ArgumentCountMismatch(_, 1, 0))
+        ));
+    }
+
+    #[test]
+    fn test_check_semantic_optional_argument() {
+        let mut symtab = SymbolTable::new();
+        let args = |count: usize| {
+            (0..count)
+                .map(|_| Box::new(MockNode::new()) as Box<dyn ASTSemanticExpression>)
+                .collect()
+        };
+
+        for count in [3, 4] {
+            let node = CallNode::new("integrate".to_string(), args(count));
+
+            assert!(node.check_semantic(&mut symtab).is_ok());
+        }
+
+        let node = CallNode::new("integrate".to_string(), args(2));
+
+        assert!(matches!(
+            node.check_semantic(&mut symtab),
+            Err(InterpreterError::ArgumentCountMismatch(_, 3, 2))
+        ));
+
+        let node = CallNode::new("integrate".to_string(), args(5));
+
+        assert!(matches!(
+            node.check_semantic(&mut symtab),
+            Err(InterpreterError::ArgumentCountMismatch(_, 4, 5))
+        ));
+    }
+}
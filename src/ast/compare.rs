@@ -0,0 +1,230 @@
+use std::cmp::Ordering;
+
+use crate::error::InterpreterError;
+use crate::symbol_table::SymbolTable;
+use crate::value::{Quantity, Value};
+
+use super::{ASTExpression, ASTNode, ASTSemanticAnalysis, ASTSemanticExpression, NodeKind};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum CompareOpType {
+    LT,
+    LE,
+    GT,
+    GE,
+    EQ,
+    NE,
+}
+
+impl CompareOpType {
+    /// Compares two real values, or two quantities of the same dimension,
+    /// giving 1 when the comparison holds and 0 otherwise. Dual numbers
+    /// compare by their value, so that piecewise functions can be
+    /// differentiated.
+    pub fn apply(&self, left: &Value, right: &Value) -> Result<Value, InterpreterError> {
+        let holds = match (compare(left, right)?, self) {
+            (None, op_type) => *op_type == CompareOpType::NE,
+            (Some(ordering), CompareOpType::LT) => ordering.is_lt(),
+            (Some(ordering), CompareOpType::LE) => ordering.is_le(),
+            (Some(ordering), CompareOpType::GT) => ordering.is_gt(),
+            (Some(ordering), CompareOpType::GE) => ordering.is_ge(),
+            (Some(ordering), CompareOpType::EQ) => ordering.is_eq(),
+            (Some(ordering), CompareOpType::NE) => ordering.is_ne(),
+        };
+
+        Ok(Value::Number(if holds { 1. } else { 0. }))
+    }
+}
+
+/// Ordering of two values, `None` when one of them is `NaN`.
+fn compare(left: &Value, right: &Value) -> Result<Option<Ordering>, InterpreterError> {
+    match (left, right) {
+        (Value::Quantity(_), _) | (_, Value::Quantity(_)) => {
+            let (left, right) = (quantity(left)?, quantity(right)?);
+
+            if left.dimension() != right.dimension() {
+                return Err(InterpreterError::IncompatibleUnits(
+                    left.dimension().to_string(),
+                    right.dimension().to_string(),
+                ));
+            }
+
+            Ok(left.value().partial_cmp(&right.value()))
+        }
+        (Value::Decimal(left), Value::Decimal(right)) => Ok(Some(left.cmp_value(right))),
+        (Value::Rational(left), Value::Rational(right)) => Ok(left.partial_cmp(right)),
+        _ => match (left.to_bigint(), right.to_bigint()) {
+            (Some(left), Some(right)) => Ok(Some(left.cmp(&right))),
+            _ => Ok(real(left)?.partial_cmp(&real(right)?)),
+        },
+    }
+}
+
+fn real(value: &Value) -> Result<f64, InterpreterError> {
+    match value {
+        Value::Number(_) | Value::Decimal(_) | Value::Rational(_) | Value::Integer(_) => {
+            Ok(value.to_f64())
+        }
+        Value::Dual(value) => Ok(value.value()),
+        _ => Err(InterpreterError::TypeError(format!(
+            "comparison is not defined for {}",
+            value
+        ))),
+    }
+}
+
+fn quantity(value: &Value) -> Result<Quantity, InterpreterError> {
+    match value {
+        Value::Quantity(value) => Ok(value.clone()),
+        value => Ok(Quantity::dimensionless(real(value)?)),
+    }
+}
+
+/// Comparison, `a < b`. Comparisons do not chain, `a < b < c` is a syntax
+/// error rather than a comparison of `a < b` with `c`.
+pub struct CompareNode {
+    left: Box<dyn ASTSemanticExpression>,
+    right: Box<dyn ASTSemanticExpression>,
+    op_type: CompareOpType,
+}
+
+impl CompareNode {
+    pub fn new(
+        left: Box<dyn ASTSemanticExpression>,
+        right: Box<dyn ASTSemanticExpression>,
+        op_type: CompareOpType,
+    ) -> Self {
+        Self {
+            left,
+            right,
+            op_type,
+        }
+    }
+}
+
+impl ASTNode for CompareNode {
+    fn kind(&self) -> NodeKind<'_> {
+        NodeKind::Compare(self.left.as_ref(), self.right.as_ref(), self.op_type)
+    }
+}
+
+impl ASTExpression for CompareNode {
+    fn pure(&self) -> bool {
+        self.left.pure() && self.right.pure()
+    }
+
+    fn eval(&self, symtab: &mut SymbolTable) -> Result<Value, InterpreterError> {
+        let left = self.left.eval(symtab)?;
+        let right = self.right.eval(symtab)?;
+
+        self.op_type.apply(&left, &right)
+    }
+}
+
+impl ASTSemanticAnalysis for CompareNode {
+    fn check_semantic(&self, symtab: &mut SymbolTable) -> Result<(), InterpreterError> {
+        if !self.left.pure() {
+            self.left.check_semantic(symtab)?;
+        }
+
+        if !self.right.pure() {
+            self.right.check_semantic(symtab)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::tests::MockNode;
+    use crate::error::InterpreterError;
+    use crate::symbol_table::SymbolTable;
+    use crate::units;
+    use crate::value::{BigInt, Complex, Dual, Quantity, Rational, Value};
+
+    use super::{ASTExpression, CompareNode, CompareOpType};
+
+    fn compare<L: Into<Value>, R: Into<Value>>(
+        left: L,
+        right: R,
+        op_type: CompareOpType,
+    ) -> Result<Value, InterpreterError> {
+        let node = CompareNode::new(
+            Box::new(MockNode::new().expect_eval(left)),
+            Box::new(MockNode::new().expect_eval(right)),
+            op_type,
+        );
+
+        node.eval(&mut SymbolTable::new())
+    }
+
+    #[test]
+    fn test_eval() {
+        let cases = [
+            (CompareOpType::LT, [1., 0., 0.]),
+            (CompareOpType::LE, [1., 1., 0.]),
+            (CompareOpType::GT, [0., 0., 1.]),
+            (CompareOpType::GE, [0., 1., 1.]),
+            (CompareOpType::EQ, [0., 1., 0.]),
+            (CompareOpType::NE, [1., 0., 1.]),
+        ];
+
+        for (op_type, expected) in cases {
+            for (right, expected) in [3., 2., 1.].into_iter().zip(expected) {
+                assert_eq!(compare(2., right, op_type).unwrap(), expected);
+            }
+        }
+
+        // NaN is only unequal
+        assert_eq!(compare(f64::NAN, 1., CompareOpType::LE).unwrap(), 0.);
+        assert_eq!(compare(f64::NAN, f64::NAN, CompareOpType::NE).unwrap(), 1.);
+    }
+
+    #[test]
+    fn test_eval_exact() {
+        let big = BigInt::parse("100000000000000000001").unwrap();
+        let next = BigInt::parse("100000000000000000002").unwrap();
+
+        assert_eq!(
+            compare(Value::Integer(big), Value::Integer(next), CompareOpType::LT).unwrap(),
+            1.
+        );
+
+        let third =
+            Value::Rational(Rational::new(BigInt::from_i64(1), BigInt::from_i64(3)).unwrap());
+
+        assert_eq!(
+            compare(third.clone(), third, CompareOpType::EQ).unwrap(),
+            1.
+        );
+    }
+
+    #[test]
+    fn test_eval_quantity() {
+        let metre = Value::Quantity(Quantity::new(1., units::lookup("m").unwrap().unit()));
+        let foot = Value::Quantity(Quantity::new(1., units::lookup("ft").unwrap().unit()));
+
+        assert_eq!(compare(foot, metre.clone(), CompareOpType::LT).unwrap(), 1.);
+        assert!(matches!(
+            compare(metre, 1., CompareOpType::LT),
+            Err(InterpreterError::IncompatibleUnits(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_eval_err() {
+        assert_eq!(
+            compare(Value::Dual(Dual::variable(2., 0, 1)), 1., CompareOpType::GT).unwrap(),
+            1.
+        );
+        assert!(matches!(
+            compare(Value::Complex(Complex::new(1., 1.)), 1., CompareOpType::LT),
+            Err(InterpreterError::TypeError(_))
+        ));
+        assert!(matches!(
+            compare(Value::List(vec![]), 1., CompareOpType::EQ),
+            Err(InterpreterError::TypeError(_))
+        ));
+    }
+}
@@ -0,0 +1,139 @@
+use crate::error::InterpreterError;
+use crate::symbol_table::SymbolTable;
+use crate::value::Value;
+
+use super::{ASTExpression, ASTNode, ASTSemanticAnalysis, ASTSemanticExpression, NodeKind};
+
+/// Conditional, `if n < 2 then n else fib(n - 1) + fib(n - 2)`. Only the
+/// branch that is taken is evaluated, which is what ends a recursion.
+pub struct ConditionalNode {
+    condition: Box<dyn ASTSemanticExpression>,
+    then_branch: Box<dyn ASTSemanticExpression>,
+    else_branch: Box<dyn ASTSemanticExpression>,
+}
+
+impl ConditionalNode {
+    pub fn new(
+        condition: Box<dyn ASTSemanticExpression>,
+        then_branch: Box<dyn ASTSemanticExpression>,
+        else_branch: Box<dyn ASTSemanticExpression>,
+    ) -> Self {
+        Self {
+            condition,
+            then_branch,
+            else_branch,
+        }
+    }
+}
+
+/// Whether a condition holds, any real number other than 0 is true.
+fn truth(value: &Value) -> Result<bool, InterpreterError> {
+    match value {
+        Value::Number(_) | Value::Decimal(_) | Value::Rational(_) | Value::Integer(_) => {
+            Ok(value.to_f64() != 0.)
+        }
+        Value::Dual(value) => Ok(value.value() != 0.),
+        _ => Err(InterpreterError::TypeError(format!(
+            "if expects a real condition, found {}",
+            value
+        ))),
+    }
+}
+
+impl ASTNode for ConditionalNode {
+    fn kind(&self) -> NodeKind<'_> {
+        NodeKind::Conditional(
+            self.condition.as_ref(),
+            self.then_branch.as_ref(),
+            self.else_branch.as_ref(),
+        )
+    }
+}
+
+impl ASTExpression for ConditionalNode {
+    fn pure(&self) -> bool {
+        self.condition.pure() && self.then_branch.pure() && self.else_branch.pure()
+    }
+
+    fn eval(&self, symtab: &mut SymbolTable) -> Result<Value, InterpreterError> {
+        if truth(&self.condition.eval(symtab)?)? {
+            self.then_branch.eval(symtab)
+        } else {
+            self.else_branch.eval(symtab)
+        }
+    }
+}
+
+impl ASTSemanticAnalysis for ConditionalNode {
+    fn check_semantic(&self, symtab: &mut SymbolTable) -> Result<(), InterpreterError> {
+        for node in [&self.condition, &self.then_branch, &self.else_branch] {
+            if !node.pure() {
+                node.check_semantic(symtab)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::tests::MockNode;
+    use crate::error::InterpreterError;
+    use crate::symbol_table::SymbolTable;
+    use crate::value::{Complex, Value};
+
+    use super::{ASTExpression, ASTSemanticAnalysis, ConditionalNode};
+
+    fn conditional<T: Into<Value>>(condition: T) -> ConditionalNode {
+        ConditionalNode::new(
+            Box::new(MockNode::new().expect_eval(condition)),
+            Box::new(MockNode::new().expect_eval(1.)),
+            Box::new(MockNode::new().expect_eval(2.)),
+        )
+    }
+
+    #[test]
+    fn test_eval() {
+        let mut symtab = SymbolTable::new();
+
+        assert_eq!(conditional(1.).eval(&mut symtab).unwrap(), 1.);
+        assert_eq!(conditional(-0.5).eval(&mut symtab).unwrap(), 1.);
+        assert_eq!(conditional(0.).eval(&mut symtab).unwrap(), 2.);
+        assert!(matches!(
+            conditional(Value::Complex(Complex::new(0., 1.))).eval(&mut symtab),
+            Err(InterpreterError::TypeError(_))
+        ));
+    }
+
+    #[test]
+    fn test_eval_untaken_branch() {
+        let mut symtab = SymbolTable::new();
+        let node = ConditionalNode::new(
+            Box::new(MockNode::new().expect_eval(0.)),
+            Box::new(MockNode::new().expect_eval_err(InterpreterError::StackOverflow(1))),
+            Box::new(MockNode::new().expect_eval(2.)),
+        );
+
+        assert_eq!(node.eval(&mut symtab).unwrap(), 2.);
+    }
+
+    #[test]
+    fn test_check_semantic() {
+        let mut symtab = SymbolTable::new();
+        let node = ConditionalNode::new(
+            Box::new(MockNode::new().expect_pure(false)),
+            Box::new(MockNode::new()),
+            Box::new(
+                MockNode::new()
+                    .expect_pure(false)
+                    .expect_check_semantic_err(InterpreterError::UndefinedSymbol("y".to_string())),
+            ),
+        );
+
+        assert!(matches!(
+            node.check_semantic(&mut symtab),
+            Err(InterpreterError::UndefinedSymbol(_))
+        ));
+    }
+}
@@ -0,0 +1,132 @@
+use std::rc::Rc;
+
+use crate::error::InterpreterError;
+use crate::symbol_table::SymbolTable;
+use crate::symbolic;
+use crate::value::{Function, Value};
+
+use super::{ASTExpression, ASTNode, ASTSemanticAnalysis, ASTSemanticExpression, NodeKind};
+
+/// Symbolic derivative, `diff(x^2 * y, x)`. Evaluates to a function of the
+/// variable whose body is the simplified derivative of the expression.
+pub struct DiffNode {
+    expr: Box<dyn ASTSemanticExpression>,
+    var: String,
+}
+
+impl DiffNode {
+    pub fn new(expr: Box<dyn ASTSemanticExpression>, var: String) -> Self {
+        Self { expr, var }
+    }
+}
+
+impl ASTNode for DiffNode {
+    fn kind(&self) -> NodeKind<'_> {
+        NodeKind::Diff(self.expr.as_ref(), &self.var)
+    }
+}
+
+impl ASTExpression for DiffNode {
+    fn pure(&self) -> bool {
+        false
+    }
+
+    fn eval(&self, symtab: &mut SymbolTable) -> Result<Value, InterpreterError> {
+        let derivative = symbolic::diff(self.expr.as_ref(), &self.var, symtab)?;
+        let body = symbolic::simplify(derivative.as_ref())?;
+
+        Ok(Value::Function(Function::anonymous(
+            vec![self.var.clone()],
+            Rc::from(body),
+        )))
+    }
+}
+
+impl ASTSemanticAnalysis for DiffNode {
+    fn check_semantic(&self, symtab: &mut SymbolTable) -> Result<(), InterpreterError> {
+        if symtab.is_global(&self.var) {
+            return Err(InterpreterError::RedefineBuiltinSymbol(self.var.clone()));
+        }
+
+        symtab.push_scope([(self.var.clone(), Value::Number(0.))].into())?;
+
+        let result = if self.expr.pure() {
+            Ok(())
+        } else {
+            self.expr.check_semantic(symtab)
+        };
+
+        symtab.pop_scope();
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::tests::var;
+    use crate::ast::tests::MockNode;
+    use crate::ast::{BinaryOpNode, BinaryOpType, NumberNode};
+    use crate::error::InterpreterError;
+    use crate::symbol_table::SymbolTable;
+    use crate::value::Value;
+
+    use super::{ASTExpression, ASTSemanticAnalysis, DiffNode};
+
+    #[test]
+    fn test_eval() {
+        let mut symtab = SymbolTable::new();
+        // x^3 + y
+        let node = DiffNode::new(
+            Box::new(BinaryOpNode::new(
+                Box::new(BinaryOpNode::new(
+                    var("x"),
+                    Box::new(NumberNode::new(3.)),
+                    BinaryOpType::POW,
+                )),
+                var("y"),
+                BinaryOpType::ADD,
+            )),
+            "x".to_string(),
+        );
+        let function = match node.eval(&mut symtab) {
+            Ok(Value::Function(function)) => function,
+            result => panic!("{:?} is not a function", result),
+        };
+
+        assert_eq!(function.to_string(), "x -> 3 * x^2");
+        assert_eq!(
+            function.call(&[Value::Number(2.)], &mut symtab).unwrap(),
+            12.
+        );
+    }
+
+    #[test]
+    fn test_eval_err() {
+        let mut symtab = SymbolTable::new();
+        let node = DiffNode::new(Box::new(MockNode::new().expect_eval(1.)), "x".to_string());
+
+        assert!(matches!(
+            node.eval(&mut symtab),
+            Err(InterpreterError::TypeError(_))
+        ));
+    }
+
+    #[test]
+    fn test_check_semantic() {
+        let mut symtab = SymbolTable::new();
+        let node = DiffNode::new(var("x"), "x".to_string());
+        let undefined = DiffNode::new(var("y"), "x".to_string());
+        let global = DiffNode::new(var("pi"), "pi".to_string());
+
+        assert!(node.check_semantic(&mut symtab).is_ok());
+        assert_eq!(symtab.get(&"x".to_string()), None);
+        assert!(matches!(
+            undefined.check_semantic(&mut symtab),
+            Err(InterpreterError::UndefinedSymbol(_))
+        ));
+        assert!(matches!(
+            global.check_semantic(&mut symtab),
+            Err(InterpreterError::RedefineBuiltinSymbol(_))
+        ));
+    }
+}
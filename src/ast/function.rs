@@ -0,0 +1,446 @@
+use std::collections::BTreeSet;
+use std::rc::Rc;
+
+use crate::builtins;
+use crate::error::InterpreterError;
+use crate::symbol_table::SymbolTable;
+use crate::value::{Function, Value};
+
+use super::lambda::free_names;
+use super::{ASTNode, ASTSemanticAnalysis, ASTSemanticExpression, ASTStatement, NodeKind};
+
+/// Function definition, `f(x, y) = x * y`, or `memo f(x, y) = x * y` for a
+/// function that remembers its results. The body may call the function
+/// itself.
+pub struct FunctionNode {
+    name: String,
+    params: Vec<String>,
+    body: Rc<dyn ASTSemanticExpression>,
+    memo: bool,
+}
+
+impl FunctionNode {
+    pub fn new(name: String, params: Vec<String>, body: Box<dyn ASTSemanticExpression>) -> Self {
+        Self {
+            name,
+            params,
+            body: Rc::from(body),
+            memo: false,
+        }
+    }
+
+    pub fn memoized(self) -> Self {
+        Self { memo: true, ..self }
+    }
+
+    fn function(&self) -> Function {
+        let function = Function::new(self.name.clone(), self.params.clone(), self.body.clone());
+
+        if self.memo {
+            function.memoized()
+        } else {
+            function
+        }
+    }
+
+    fn check_body(&self, symtab: &mut SymbolTable) -> Result<(), InterpreterError> {
+        symtab.push_scope(
+            self.params
+                .iter()
+                .map(|param| (param.clone(), Value::Number(0.)))
+                .collect(),
+        )?;
+
+        let result = if self.body.pure() {
+            Ok(())
+        } else {
+            self.body.check_semantic(symtab)
+        };
+
+        symtab.pop_scope();
+        result?;
+
+        check_memos(symtab)
+    }
+}
+
+/// Remembered results stay valid only if a memo function depends on nothing
+/// but its arguments, constants and functions, whose redefinition clears
+/// them. Functions it calls are followed, so redefining one of them to read
+/// a variable is rejected as well.
+fn check_memos(symtab: &SymbolTable) -> Result<(), InterpreterError> {
+    for name in symtab.functions() {
+        let function = match symtab.get(&name) {
+            Some(Value::Function(function)) if function.is_memoized() => function,
+            _ => continue,
+        };
+
+        if let Some(variable) = variable_read(function, symtab, &mut BTreeSet::from([name.clone()]))
+        {
+            return Err(InterpreterError::TypeError(format!(
+                "memo function {} depends on variable {}",
+                name, variable
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// A variable `function` reads, directly or through the functions it calls.
+fn variable_read(
+    function: &Function,
+    symtab: &SymbolTable,
+    visited: &mut BTreeSet<String>,
+) -> Option<String> {
+    for name in free_names(function.body()) {
+        if function.params().contains(&name)
+            || function.captures().contains_key(&name)
+            || symtab.is_global(&name)
+            || symtab.is_constant(&name)
+            || builtins::is_builtin(&name)
+        {
+            continue;
+        }
+
+        match symtab.get(&name) {
+            Some(Value::Function(callee)) => {
+                if visited.insert(name.clone()) {
+                    if let Some(variable) = variable_read(callee, symtab, visited) {
+                        return Some(variable);
+                    }
+                }
+            }
+            _ => return Some(name),
+        }
+    }
+
+    None
+}
+
+impl ASTStatement for FunctionNode {
+    fn execute(&self, symtab: &mut SymbolTable) -> Result<Value, InterpreterError> {
+        let function = Value::Function(self.function());
+
+        symtab.insert(self.name.clone(), function.clone());
+
+        Ok(function)
+    }
+}
+
+impl ASTNode for FunctionNode {
+    fn kind(&self) -> NodeKind<'_> {
+        NodeKind::Function(&self.name, &self.params, self.body.as_ref(), self.memo)
+    }
+}
+
+impl ASTSemanticAnalysis for FunctionNode {
+    fn check_semantic(&self, symtab: &mut SymbolTable) -> Result<(), InterpreterError> {
+        if symtab.is_global(&self.name) || builtins::is_builtin(&self.name) {
+            return Err(InterpreterError::RedefineBuiltinSymbol(self.name.clone()));
+        }
+
+        if symtab.is_constant(&self.name) {
+            return Err(InterpreterError::ReassignConstant(self.name.clone()));
+        }
+
+        if let Some(param) = self.params.iter().find(|param| symtab.is_global(param)) {
+            return Err(InterpreterError::RedefineBuiltinSymbol(param.clone()));
+        }
+
+        // The function is defined while its body is checked, so that it can
+        // call itself, and the previous definition is restored if the body
+        // is invalid.
+        let previous = symtab.get(&self.name).cloned();
+
+        symtab.insert(self.name.clone(), Value::Function(self.function()));
+
+        let result = self.check_body(symtab);
+
+        if result.is_err() {
+            match previous {
+                Some(previous) => symtab.insert(self.name.clone(), previous),
+                None => {
+                    symtab.remove(&self.name);
+                }
+            }
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::tests::var;
+    use crate::ast::tests::MockNode;
+    use crate::ast::{
+        ASTSemanticAnalysis, ASTSemanticExpression, ASTStatement, BinaryOpNode, BinaryOpType,
+        CallNode, NumberNode, VarNode,
+    };
+    use crate::error::InterpreterError;
+    use crate::symbol_table::SymbolTable;
+    use crate::value::{Function, Value};
+
+    use super::FunctionNode;
+
+    fn call(name: &str, args: Vec<Box<dyn ASTSemanticExpression>>) -> Box<CallNode> {
+        Box::new(CallNode::new(name.to_string(), args))
+    }
+
+    fn function(symtab: &SymbolTable, name: &str) -> Function {
+        match symtab.get(&name.to_string()) {
+            Some(Value::Function(function)) => function.clone(),
+            _ => panic!("{} is defined as a function", name),
+        }
+    }
+
+    fn square() -> FunctionNode {
+        FunctionNode::new(
+            "f".to_string(),
+            vec!["x".to_string()],
+            Box::new(BinaryOpNode::new(
+                Box::new(VarNode::new("x".to_string())),
+                Box::new(VarNode::new("x".to_string())),
+                BinaryOpType::MUL,
+            )),
+        )
+    }
+
+    #[test]
+    fn test_execute() {
+        let mut symtab = SymbolTable::new();
+        let result = square().execute(&mut symtab);
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().to_string(), "f(x)");
+
+        let function = match symtab.get(&"f".to_string()) {
+            Some(Value::Function(function)) => function.clone(),
+            _ => panic!("f is defined as a function"),
+        };
+
+        assert_eq!(
+            function.call(&[Value::Number(3.)], &mut symtab).unwrap(),
+            9.
+        );
+        assert!(matches!(
+            function.call(&[], &mut symtab),
+            Err(InterpreterError::ArgumentCountMismatch(_, 1, 0))
+        ));
+        assert_eq!(symtab.get(&"x".to_string()), None);
+    }
+
+    #[test]
+    fn test_execute_recursion() {
+        let mut symtab = SymbolTable::new();
+        let node = FunctionNode::new(
+            "f".to_string(),
+            vec!["x".to_string()],
+            Box::new(CallNode::new(
+                "f".to_string(),
+                vec![Box::new(VarNode::new("x".to_string()))],
+            )),
+        );
+
+        node.execute(&mut symtab).unwrap();
+
+        let function = match symtab.get(&"f".to_string()) {
+            Some(Value::Function(function)) => function.clone(),
+            _ => panic!("f is defined as a function"),
+        };
+
+        assert!(matches!(
+            function.call(&[Value::Number(1.)], &mut symtab),
+            Err(InterpreterError::StackOverflow(_))
+        ));
+    }
+
+    #[test]
+    fn test_check_semantic() {
+        let mut symtab = SymbolTable::new();
+
+        assert!(square().check_semantic(&mut symtab).is_ok());
+        assert!(matches!(
+            symtab.get(&"f".to_string()),
+            Some(Value::Function(_))
+        ));
+        assert_eq!(symtab.get(&"x".to_string()), None);
+    }
+
+    #[test]
+    fn test_check_semantic_err() {
+        let mut symtab = SymbolTable::new();
+        let undefined = FunctionNode::new(
+            "f".to_string(),
+            vec!["x".to_string()],
+            Box::new(VarNode::new("y".to_string())),
+        );
+        let builtin = FunctionNode::new(
+            "sqrt".to_string(),
+            vec!["x".to_string()],
+            Box::new(MockNode::new()),
+        );
+        let param = FunctionNode::new(
+            "f".to_string(),
+            vec!["pi".to_string()],
+            Box::new(MockNode::new()),
+        );
+
+        assert!(matches!(
+            undefined.check_semantic(&mut symtab),
+            Err(InterpreterError::UndefinedSymbol(_))
+        ));
+        assert!(matches!(
+            builtin.check_semantic(&mut symtab),
+            Err(InterpreterError::RedefineBuiltinSymbol(_))
+        ));
+        assert!(matches!(
+            param.check_semantic(&mut symtab),
+            Err(InterpreterError::RedefineBuiltinSymbol(_))
+        ));
+    }
+
+    #[test]
+    fn test_execute_memo() {
+        let mut symtab = SymbolTable::new();
+        // memo f(x) = x + a, which the semantic check would reject
+        let node = FunctionNode::new(
+            "f".to_string(),
+            vec!["x".to_string()],
+            Box::new(BinaryOpNode::new(var("x"), var("a"), BinaryOpType::ADD)),
+        )
+        .memoized();
+
+        symtab.insert("a".to_string(), 1.);
+        node.execute(&mut symtab).unwrap();
+
+        let f = function(&symtab, "f");
+
+        assert!(f.is_memoized());
+        assert_eq!(f.call(&[Value::Number(1.)], &mut symtab).unwrap(), 2.);
+
+        // Results are remembered until a function changes
+        symtab.insert("a".to_string(), 10.);
+
+        assert_eq!(f.call(&[Value::Number(1.)], &mut symtab).unwrap(), 2.);
+        assert_eq!(f.call(&[Value::Number(2.)], &mut symtab).unwrap(), 12.);
+
+        square().execute(&mut symtab).unwrap();
+        node.execute(&mut symtab).unwrap();
+
+        let f = function(&symtab, "f");
+
+        assert_eq!(f.call(&[Value::Number(1.)], &mut symtab).unwrap(), 11.);
+    }
+
+    #[test]
+    fn test_check_semantic_recursion() {
+        let mut symtab = SymbolTable::new();
+        let recursive = FunctionNode::new(
+            "f".to_string(),
+            vec!["x".to_string()],
+            call("f", vec![var("x")]),
+        );
+        let arity = FunctionNode::new(
+            "g".to_string(),
+            vec!["x".to_string()],
+            call("g", vec![var("x"), var("x")]),
+        );
+
+        assert!(recursive.check_semantic(&mut symtab).is_ok());
+        assert!(matches!(
+            arity.check_semantic(&mut symtab),
+            Err(InterpreterError::ArgumentCountMismatch(_, 1, 2))
+        ));
+        assert_eq!(symtab.get(&"g".to_string()), None);
+
+        // An invalid definition keeps the previous one
+        let invalid = FunctionNode::new("f".to_string(), vec![], var("y"));
+
+        assert!(invalid.check_semantic(&mut symtab).is_err());
+        assert_eq!(function(&symtab, "f").params(), ["x".to_string()]);
+    }
+
+    #[test]
+    fn test_check_semantic_memo() {
+        let mut symtab = SymbolTable::new();
+        let memo = |body: Box<dyn ASTSemanticExpression>| {
+            FunctionNode::new("h".to_string(), vec!["x".to_string()], body).memoized()
+        };
+
+        symtab.insert("a".to_string(), 1.);
+        symtab.insert_constant("c".to_string(), 2.);
+        square().check_semantic(&mut symtab).unwrap();
+
+        assert!(matches!(
+            memo(Box::new(BinaryOpNode::new(
+                var("x"),
+                var("a"),
+                BinaryOpType::ADD
+            )))
+            .check_semantic(&mut symtab),
+            Err(InterpreterError::TypeError(_))
+        ));
+
+        // memo h(x) = sqrt(f(x) * c) + pi + h(x - 1)
+        let body = BinaryOpNode::new(
+            Box::new(BinaryOpNode::new(
+                call(
+                    "sqrt",
+                    vec![Box::new(BinaryOpNode::new(
+                        call("f", vec![var("x")]),
+                        var("c"),
+                        BinaryOpType::MUL,
+                    ))],
+                ),
+                var("pi"),
+                BinaryOpType::ADD,
+            )),
+            call(
+                "h",
+                vec![Box::new(BinaryOpNode::new(
+                    var("x"),
+                    Box::new(NumberNode::new(1.)),
+                    BinaryOpType::SUB,
+                ))],
+            ),
+            BinaryOpType::ADD,
+        );
+
+        assert!(memo(Box::new(body)).check_semantic(&mut symtab).is_ok());
+        assert!(function(&symtab, "h").is_memoized());
+
+        // g(x) = x + a; memo h(x) = g(x)
+        let g = |body| FunctionNode::new("g".to_string(), vec!["x".to_string()], body);
+
+        g(Box::new(BinaryOpNode::new(
+            var("x"),
+            var("a"),
+            BinaryOpType::ADD,
+        )))
+        .check_semantic(&mut symtab)
+        .unwrap();
+
+        assert!(matches!(
+            memo(call("g", vec![var("x")])).check_semantic(&mut symtab),
+            Err(InterpreterError::TypeError(_))
+        ));
+
+        // g(x) = x; memo h(x) = g(x); g(x) = x + a
+        g(var("x")).check_semantic(&mut symtab).unwrap();
+        memo(call("g", vec![var("x")]))
+            .check_semantic(&mut symtab)
+            .unwrap();
+
+        assert!(matches!(
+            g(Box::new(BinaryOpNode::new(
+                var("x"),
+                var("a"),
+                BinaryOpType::ADD
+            )))
+            .check_semantic(&mut symtab),
+            Err(InterpreterError::TypeError(_))
+        ));
+    }
+}
@@ -0,0 +1,114 @@
+use crate::error::InterpreterError;
+use crate::symbol_table::SymbolTable;
+use crate::value::{Interval, Value};
+
+use super::{ASTExpression, ASTNode, ASTSemanticAnalysis, ASTSemanticExpression, NodeKind};
+
+/// Interval literal, `[9.5 .. 10.5]`.
+pub struct IntervalNode {
+    lo: Box<dyn ASTSemanticExpression>,
+    hi: Box<dyn ASTSemanticExpression>,
+}
+
+impl IntervalNode {
+    pub fn new(lo: Box<dyn ASTSemanticExpression>, hi: Box<dyn ASTSemanticExpression>) -> Self {
+        Self { lo, hi }
+    }
+}
+
+fn to_interval(value: &Value) -> Result<Interval, InterpreterError> {
+    value.to_interval().ok_or_else(|| {
+        InterpreterError::TypeError(format!("interval bounds must be real, found {}", value))
+    })
+}
+
+impl ASTNode for IntervalNode {
+    fn kind(&self) -> NodeKind<'_> {
+        NodeKind::Interval(self.lo.as_ref(), self.hi.as_ref())
+    }
+}
+
+impl ASTExpression for IntervalNode {
+    fn pure(&self) -> bool {
+        self.lo.pure() && self.hi.pure()
+    }
+
+    fn eval(&self, symtab: &mut SymbolTable) -> Result<Value, InterpreterError> {
+        let lo = to_interval(&self.lo.eval(symtab)?)?;
+        let hi = to_interval(&self.hi.eval(symtab)?)?;
+
+        Interval::new(lo.lo(), hi.hi())
+            .map(Value::Interval)
+            .ok_or_else(|| {
+                InterpreterError::DomainError(format!(
+                    "empty interval [{} .. {}]",
+                    lo.lo(),
+                    hi.hi()
+                ))
+            })
+    }
+}
+
+impl ASTSemanticAnalysis for IntervalNode {
+    fn check_semantic(&self, symtab: &mut SymbolTable) -> Result<(), InterpreterError> {
+        if !self.lo.pure() {
+            self.lo.check_semantic(symtab)?;
+        }
+
+        if !self.hi.pure() {
+            self.hi.check_semantic(symtab)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::tests::MockNode;
+    use crate::error::InterpreterError;
+    use crate::symbol_table::SymbolTable;
+    use crate::value::{Complex, Interval, Value};
+
+    use super::{ASTExpression, IntervalNode};
+
+    #[test]
+    fn test_eval() {
+        let mut symtab = SymbolTable::new();
+        let node = IntervalNode::new(
+            Box::new(MockNode::new().expect_eval(9.5)),
+            Box::new(MockNode::new().expect_eval(10.5)),
+        );
+        let result = node.eval(&mut symtab);
+
+        assert!(result.is_ok());
+        assert_eq!(
+            result.unwrap(),
+            Value::Interval(Interval::new(9.5, 10.5).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_eval_err() {
+        let mut symtab = SymbolTable::new();
+        let node = IntervalNode::new(
+            Box::new(MockNode::new().expect_eval(2.)),
+            Box::new(MockNode::new().expect_eval(1.)),
+        );
+
+        assert!(matches!(
+            node.eval(&mut symtab),
+            Err(InterpreterError::DomainError(_))
+        ));
+
+        let node = IntervalNode::new(
+            Box::new(MockNode::new().expect_eval(Value::Complex(Complex::new(0., 1.)))),
+            Box::new(MockNode::new().expect_eval(1.)),
+        );
+
+        assert!(matches!(
+            node.eval(&mut symtab),
+            Err(InterpreterError::TypeError(_))
+        ));
+    }
+}
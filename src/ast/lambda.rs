@@ -0,0 +1,251 @@
+use std::collections::{BTreeSet, HashMap};
+use std::rc::Rc;
+use std::slice;
+
+use crate::error::InterpreterError;
+use crate::symbol_table::SymbolTable;
+use crate::value::{Function, Value};
+
+use super::{ASTExpression, ASTNode, ASTSemanticAnalysis, ASTSemanticExpression, NodeKind};
+
+/// Anonymous function, `x -> x^2 + 1` or `(a, b) -> a * b`. Evaluates to a
+/// function value that captures the variables its body refers to by value,
+/// so it keeps working outside the function it was defined in.
+pub struct LambdaNode {
+    params: Vec<String>,
+    body: Rc<dyn ASTSemanticExpression>,
+}
+
+impl LambdaNode {
+    pub fn new(params: Vec<String>, body: Box<dyn ASTSemanticExpression>) -> Self {
+        Self {
+            params,
+            body: Rc::from(body),
+        }
+    }
+
+    fn captures(&self, symtab: &SymbolTable) -> HashMap<String, Value> {
+        free_names(self.body.as_ref())
+            .into_iter()
+            .filter(|name| !self.params.contains(name) && !symtab.is_global(name))
+            .filter_map(|name| symtab.get(&name).cloned().map(|value| (name, value)))
+            .collect()
+    }
+}
+
+/// Names of the variables and functions a tree refers to, leaving out the
+/// ones it binds itself, such as the index of a sum or the parameters of an
+/// inner lambda.
+pub(super) fn free_names(node: &dyn ASTSemanticExpression) -> BTreeSet<String> {
+    let mut names = BTreeSet::new();
+    let mut collect = |nodes: &[&dyn ASTSemanticExpression]| {
+        for node in nodes {
+            names.extend(free_names(*node));
+        }
+    };
+
+    match node.kind() {
+        NodeKind::Var(name) => {
+            names.insert(name.clone());
+        }
+        NodeKind::Call(name, args) => {
+            names.insert(name.clone());
+
+            for arg in args {
+                names.extend(free_names(arg.as_ref()));
+            }
+        }
+        NodeKind::List(nodes) => {
+            for node in nodes {
+                names.extend(free_names(node.as_ref()));
+            }
+        }
+        NodeKind::Solve(lhs, rhs, var, bounds) => {
+            names.extend(bound_in(&[lhs, rhs], slice::from_ref(var)));
+
+            for node in bounds {
+                names.extend(free_names(node.as_ref()));
+            }
+        }
+        NodeKind::Diff(node, var) | NodeKind::Poly(node, Some(var)) => {
+            names.extend(bound_in(&[node], slice::from_ref(var)));
+        }
+        NodeKind::Lambda(params, node) => names.extend(bound_in(&[node], params)),
+        NodeKind::Series(_, index, lo, hi, body) => {
+            collect(&[lo, hi]);
+            names.extend(bound_in(&[body], slice::from_ref(index)));
+        }
+        NodeKind::Unary(node, _)
+        | NodeKind::Quantity(node, _)
+        | NodeKind::Convert(node, _)
+        | NodeKind::Simplify(node)
+        | NodeKind::Poly(node, None) => collect(&[node]),
+        NodeKind::Binary(left, right, _)
+        | NodeKind::PercentChange(left, right, _)
+        | NodeKind::Compare(left, right, _)
+        | NodeKind::Index(left, right)
+        | NodeKind::Interval(left, right) => collect(&[left, right]),
+        NodeKind::Conditional(condition, then_branch, else_branch) => {
+            collect(&[condition, then_branch, else_branch])
+        }
+        _ => {}
+    }
+
+    names
+}
+
+/// Free names of `nodes` other than the variables in `bound`.
+fn bound_in(nodes: &[&dyn ASTSemanticExpression], bound: &[String]) -> BTreeSet<String> {
+    nodes
+        .iter()
+        .flat_map(|node| free_names(*node))
+        .filter(|name| !bound.contains(name))
+        .collect()
+}
+
+impl ASTNode for LambdaNode {
+    fn kind(&self) -> NodeKind<'_> {
+        NodeKind::Lambda(&self.params, self.body.as_ref())
+    }
+}
+
+impl ASTExpression for LambdaNode {
+    fn pure(&self) -> bool {
+        false
+    }
+
+    fn eval(&self, symtab: &mut SymbolTable) -> Result<Value, InterpreterError> {
+        Ok(Value::Function(Function::closure(
+            self.params.clone(),
+            self.body.clone(),
+            self.captures(symtab),
+        )))
+    }
+}
+
+impl ASTSemanticAnalysis for LambdaNode {
+    fn check_semantic(&self, symtab: &mut SymbolTable) -> Result<(), InterpreterError> {
+        if let Some(param) = self.params.iter().find(|param| symtab.is_global(param)) {
+            return Err(InterpreterError::RedefineBuiltinSymbol(param.clone()));
+        }
+
+        if self.body.pure() {
+            return Ok(());
+        }
+
+        symtab.push_bindings(
+            self.params
+                .iter()
+                .map(|param| (param.clone(), Value::Number(0.)))
+                .collect(),
+        )?;
+
+        let result = self.body.check_semantic(symtab);
+
+        symtab.pop_scope();
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::tests::{number, var};
+    use crate::ast::{BinaryOpNode, BinaryOpType, CallNode};
+    use crate::error::InterpreterError;
+    use crate::symbol_table::SymbolTable;
+    use crate::value::Value;
+
+    use super::{ASTExpression, ASTSemanticAnalysis, LambdaNode};
+
+    fn call(function: &Value, args: &[Value], symtab: &mut SymbolTable) -> Value {
+        match function {
+            Value::Function(function) => function.call(args, symtab).unwrap(),
+            value => panic!("expected a function, found {}", value),
+        }
+    }
+
+    #[test]
+    fn test_eval() {
+        let mut symtab = SymbolTable::new();
+        // (a, b) -> a * b
+        let node = LambdaNode::new(
+            vec!["a".to_string(), "b".to_string()],
+            Box::new(BinaryOpNode::new(var("a"), var("b"), BinaryOpType::MUL)),
+        );
+        let function = node.eval(&mut symtab).unwrap();
+
+        assert_eq!(function.to_string(), "(a, b) -> a * b");
+        assert_eq!(
+            call(
+                &function,
+                &[Value::Number(2.), Value::Number(3.)],
+                &mut symtab
+            ),
+            Value::Number(6.)
+        );
+    }
+
+    #[test]
+    fn test_eval_captures_by_value() {
+        let mut symtab = SymbolTable::new();
+        // x -> x + a
+        let node = LambdaNode::new(
+            vec!["x".to_string()],
+            Box::new(BinaryOpNode::new(var("x"), var("a"), BinaryOpType::ADD)),
+        );
+
+        symtab.insert("a".to_string(), 1.);
+
+        let function = node.eval(&mut symtab).unwrap();
+
+        symtab.insert("a".to_string(), 10.);
+
+        assert_eq!(
+            call(&function, &[Value::Number(2.)], &mut symtab),
+            Value::Number(3.)
+        );
+
+        // Parameters of the enclosing call stay visible after it returns
+        symtab
+            .push_scope([("a".to_string(), Value::Number(100.))].into())
+            .unwrap();
+
+        let function = node.eval(&mut symtab).unwrap();
+
+        symtab.pop_scope();
+
+        assert_eq!(
+            call(&function, &[Value::Number(2.)], &mut symtab),
+            Value::Number(102.)
+        );
+    }
+
+    #[test]
+    fn test_check_semantic() {
+        let mut symtab = SymbolTable::new();
+        let node = LambdaNode::new(
+            vec!["x".to_string()],
+            Box::new(CallNode::new("sqrt".to_string(), vec![var("x")])),
+        );
+
+        assert!(node.check_semantic(&mut symtab).is_ok());
+        assert_eq!(symtab.get(&"x".to_string()), None);
+
+        let node = LambdaNode::new(
+            vec!["x".to_string()],
+            Box::new(BinaryOpNode::new(var("x"), var("y"), BinaryOpType::ADD)),
+        );
+
+        assert!(matches!(
+            node.check_semantic(&mut symtab),
+            Err(InterpreterError::UndefinedSymbol(_))
+        ));
+
+        let node = LambdaNode::new(vec!["pi".to_string()], number(1.));
+
+        assert!(matches!(
+            node.check_semantic(&mut symtab),
+            Err(InterpreterError::RedefineBuiltinSymbol(_))
+        ));
+    }
+}
@@ -0,0 +1,220 @@
+use crate::error::InterpreterError;
+use crate::symbol_table::SymbolTable;
+use crate::value::Value;
+
+use super::{ASTExpression, ASTNode, ASTSemanticAnalysis, ASTSemanticExpression, NodeKind};
+
+/// List literal, `[1, 2, 3]`.
+pub struct ListNode {
+    nodes: Vec<Box<dyn ASTSemanticExpression>>,
+}
+
+impl ListNode {
+    pub fn new(nodes: Vec<Box<dyn ASTSemanticExpression>>) -> Self {
+        Self { nodes }
+    }
+}
+
+impl ASTNode for ListNode {
+    fn kind(&self) -> NodeKind<'_> {
+        NodeKind::List(&self.nodes)
+    }
+}
+
+impl ASTExpression for ListNode {
+    fn pure(&self) -> bool {
+        self.nodes.iter().all(|node| node.pure())
+    }
+
+    fn eval(&self, symtab: &mut SymbolTable) -> Result<Value, InterpreterError> {
+        let mut values = vec![];
+
+        for node in self.nodes.iter() {
+            values.push(node.eval(symtab)?);
+        }
+
+        Ok(Value::List(values))
+    }
+}
+
+impl ASTSemanticAnalysis for ListNode {
+    fn check_semantic(&self, symtab: &mut SymbolTable) -> Result<(), InterpreterError> {
+        for node in self.nodes.iter() {
+            if !node.pure() {
+                node.check_semantic(symtab)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Indexing, `v[0]`. Negative indices count from the end.
+pub struct IndexNode {
+    node: Box<dyn ASTSemanticExpression>,
+    index: Box<dyn ASTSemanticExpression>,
+}
+
+impl IndexNode {
+    pub fn new(
+        node: Box<dyn ASTSemanticExpression>,
+        index: Box<dyn ASTSemanticExpression>,
+    ) -> Self {
+        Self { node, index }
+    }
+}
+
+impl ASTNode for IndexNode {
+    fn kind(&self) -> NodeKind<'_> {
+        NodeKind::Index(self.node.as_ref(), self.index.as_ref())
+    }
+}
+
+impl ASTExpression for IndexNode {
+    fn pure(&self) -> bool {
+        self.node.pure() && self.index.pure()
+    }
+
+    fn eval(&self, symtab: &mut SymbolTable) -> Result<Value, InterpreterError> {
+        let value = self.node.eval(symtab)?;
+        let index = self.index.eval(symtab)?;
+
+        let values = match value {
+            Value::List(values) => values,
+            _ => {
+                return Err(InterpreterError::TypeError(format!(
+                    "{} is not a list",
+                    value
+                )))
+            }
+        };
+
+        let index = index
+            .to_bigint()
+            .and_then(|index| index.to_i64())
+            .ok_or_else(|| {
+                InterpreterError::TypeError(format!("index must be an integer, found {}", index))
+            })?;
+
+        let position = if index < 0 {
+            values.len() as i64 + index
+        } else {
+            index
+        };
+
+        usize::try_from(position)
+            .ok()
+            .and_then(|position| values.get(position))
+            .cloned()
+            .ok_or(InterpreterError::IndexOutOfRange(index, values.len()))
+    }
+}
+
+impl ASTSemanticAnalysis for IndexNode {
+    fn check_semantic(&self, symtab: &mut SymbolTable) -> Result<(), InterpreterError> {
+        if !self.node.pure() {
+            self.node.check_semantic(symtab)?;
+        }
+
+        if !self.index.pure() {
+            self.index.check_semantic(symtab)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::tests::MockNode;
+    use crate::error::InterpreterError;
+    use crate::symbol_table::SymbolTable;
+    use crate::value::{DecimalContext, NumberMode, Value};
+
+    use super::{ASTExpression, ASTSemanticAnalysis, IndexNode, ListNode};
+
+    fn list() -> ListNode {
+        ListNode::new(vec![
+            Box::new(MockNode::new().expect_eval(1.)),
+            Box::new(MockNode::new().expect_eval(2.)),
+            Box::new(MockNode::new().expect_eval(3.)),
+        ])
+    }
+
+    #[test]
+    fn test_eval_list() {
+        let mut symtab = SymbolTable::new();
+        let result = list().eval(&mut symtab);
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().to_string(), "[1, 2, 3]");
+    }
+
+    #[test]
+    fn test_eval_index() {
+        let mut symtab = SymbolTable::new();
+
+        for (index, expected) in [(0., 1.), (2., 3.), (-1., 3.)] {
+            let node = IndexNode::new(
+                Box::new(list()),
+                Box::new(MockNode::new().expect_eval(index)),
+            );
+            let result = node.eval(&mut symtab);
+
+            assert!(result.is_ok());
+            assert_eq!(result.unwrap(), Value::Number(expected));
+        }
+
+        for mode in [
+            NumberMode::DECIMAL(DecimalContext::default()),
+            NumberMode::RATIONAL,
+        ] {
+            let node = IndexNode::new(
+                Box::new(list()),
+                Box::new(MockNode::new().expect_eval(mode.literal(1.))),
+            );
+
+            assert_eq!(node.eval(&mut symtab).unwrap(), Value::Number(2.));
+        }
+    }
+
+    #[test]
+    fn test_eval_index_err() {
+        let mut symtab = SymbolTable::new();
+        let node = IndexNode::new(Box::new(list()), Box::new(MockNode::new().expect_eval(3.)));
+
+        assert!(matches!(
+            node.eval(&mut symtab),
+            Err(InterpreterError::IndexOutOfRange(3, 3))
+        ));
+
+        let node = IndexNode::new(Box::new(list()), Box::new(MockNode::new().expect_eval(0.5)));
+
+        assert!(matches!(
+            node.eval(&mut symtab),
+            Err(InterpreterError::TypeError(_))
+        ));
+
+        let node = IndexNode::new(
+            Box::new(MockNode::new().expect_eval(1.)),
+            Box::new(MockNode::new().expect_eval(0.)),
+        );
+
+        assert!(matches!(
+            node.eval(&mut symtab),
+            Err(InterpreterError::TypeError(_))
+        ));
+    }
+
+    #[test]
+    fn test_check_semantic() {
+        let mut symtab = SymbolTable::new();
+        let node = ListNode::new(vec![Box::new(
+            MockNode::new()
+                .expect_pure(false)
+                .expect_check_semantic_err(InterpreterError::UndefinedSymbol("x".to_string())),
+        )]);
+
+        assert!(node.check_semantic(&mut symtab).is_err());
+    }
+}
@@ -1,7 +1,22 @@
 mod assign;
+#[allow(clippy::module_inception)]
 mod ast;
 mod binary;
+mod call;
+mod compare;
+mod conditional;
+mod diff;
+mod function;
+mod interval;
+mod lambda;
+mod list;
 mod number;
+mod percent;
+mod poly;
+mod quantity;
+mod series;
+mod simplify;
+mod solve;
 mod statement;
 mod unary;
 mod var;
@@ -11,10 +26,24 @@ pub mod tests;
 pub use assign::AssignNode;
 pub use ast::{
     ASTExpression, ASTNode, ASTSemanticAnalysis, ASTSemanticExpression, ASTSemanticStatement,
-    ASTStatement,
+    ASTStatement, NodeKind,
 };
 pub use binary::{BinaryOpNode, BinaryOpType};
+pub use call::CallNode;
+pub use compare::{CompareNode, CompareOpType};
+pub use conditional::ConditionalNode;
+pub use diff::DiffNode;
+pub use function::FunctionNode;
+pub use interval::IntervalNode;
+pub use lambda::LambdaNode;
+pub use list::{IndexNode, ListNode};
 pub use number::NumberNode;
+pub use percent::PercentChangeNode;
+pub use poly::PolyNode;
+pub use quantity::{ConvertNode, QuantityNode, UnitExpr};
+pub use series::SeriesNode;
+pub use simplify::SimplifyNode;
+pub use solve::SolveNode;
 pub use statement::StatementListNode;
 pub use unary::{UnaryOpNode, UnaryOpType};
 pub use var::VarNode;
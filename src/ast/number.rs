@@ -1,27 +1,34 @@
 use crate::error::InterpreterError;
 use crate::symbol_table::SymbolTable;
+use crate::value::Value;
 
-use super::{ASTExpression, ASTNode, ASTSemanticAnalysis};
+use super::{ASTExpression, ASTNode, ASTSemanticAnalysis, NodeKind};
 
 pub struct NumberNode {
-    value: f64,
+    value: Value,
 }
 
 impl NumberNode {
-    pub fn new(value: f64) -> Self {
-        Self { value }
+    pub fn new<T: Into<Value>>(value: T) -> Self {
+        Self {
+            value: value.into(),
+        }
     }
 }
 
-impl ASTNode for NumberNode {}
+impl ASTNode for NumberNode {
+    fn kind(&self) -> NodeKind<'_> {
+        NodeKind::Number(&self.value)
+    }
+}
 
 impl ASTExpression for NumberNode {
     fn pure(&self) -> bool {
         true
     }
 
-    fn eval(&self, _symtab: &mut SymbolTable) -> Result<f64, InterpreterError> {
-        Ok(self.value)
+    fn eval(&self, _symtab: &mut SymbolTable) -> Result<Value, InterpreterError> {
+        Ok(self.value.clone())
     }
 }
 
@@ -0,0 +1,128 @@
+use crate::error::InterpreterError;
+use crate::symbol_table::SymbolTable;
+use crate::value::Value;
+
+use super::{
+    ASTExpression, ASTNode, ASTSemanticAnalysis, ASTSemanticExpression, BinaryOpType, NodeKind,
+};
+
+/// Percentage change in calculator mode, `x + 10%` is `x * (1 + 10%)` and
+/// `x - 10%` is `x * (1 - 10%)`. `one` is 1 in the number mode of the
+/// literals, so that the ratio stays exact.
+pub struct PercentChangeNode {
+    base: Box<dyn ASTSemanticExpression>,
+    percent: Box<dyn ASTSemanticExpression>,
+    op_type: BinaryOpType,
+    one: Value,
+}
+
+impl PercentChangeNode {
+    pub fn new(
+        base: Box<dyn ASTSemanticExpression>,
+        percent: Box<dyn ASTSemanticExpression>,
+        op_type: BinaryOpType,
+        one: Value,
+    ) -> Self {
+        Self {
+            base,
+            percent,
+            op_type,
+            one,
+        }
+    }
+}
+
+impl ASTNode for PercentChangeNode {
+    fn kind(&self) -> NodeKind<'_> {
+        NodeKind::PercentChange(self.base.as_ref(), self.percent.as_ref(), self.op_type)
+    }
+}
+
+impl ASTExpression for PercentChangeNode {
+    fn pure(&self) -> bool {
+        self.base.pure() && self.percent.pure()
+    }
+
+    fn eval(&self, symtab: &mut SymbolTable) -> Result<Value, InterpreterError> {
+        let base = self.base.eval(symtab)?;
+        let percent = self.percent.eval(symtab)?;
+        let ratio = self.op_type.apply(&self.one, &percent)?;
+
+        BinaryOpType::MUL.apply(&base, &ratio)
+    }
+}
+
+impl ASTSemanticAnalysis for PercentChangeNode {
+    fn check_semantic(&self, symtab: &mut SymbolTable) -> Result<(), InterpreterError> {
+        if !self.base.pure() {
+            self.base.check_semantic(symtab)?;
+        }
+
+        if !self.percent.pure() {
+            self.percent.check_semantic(symtab)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::tests::MockNode;
+    use crate::ast::BinaryOpType;
+    use crate::error::InterpreterError;
+    use crate::symbol_table::SymbolTable;
+    use crate::value::{BigInt, NumberMode, Rational, Value};
+
+    use super::{ASTExpression, PercentChangeNode};
+
+    #[test]
+    fn test_eval() {
+        let mut symtab = SymbolTable::new();
+        let node = PercentChangeNode::new(
+            Box::new(MockNode::new().expect_eval(80.)),
+            Box::new(MockNode::new().expect_eval(0.25)),
+            BinaryOpType::ADD,
+            Value::Number(1.),
+        );
+
+        assert_eq!(node.eval(&mut symtab).unwrap(), 100f64);
+
+        let node = PercentChangeNode::new(
+            Box::new(MockNode::new().expect_eval(80.)),
+            Box::new(MockNode::new().expect_eval(0.25)),
+            BinaryOpType::SUB,
+            Value::Number(1.),
+        );
+
+        assert_eq!(node.eval(&mut symtab).unwrap(), 60f64);
+
+        // 3 - 10% stays exact with rationals
+        let tenth =
+            Value::Rational(Rational::new(BigInt::from_i64(1), BigInt::from_i64(10)).unwrap());
+        let node = PercentChangeNode::new(
+            Box::new(MockNode::new().expect_eval(NumberMode::RATIONAL.literal(3.))),
+            Box::new(MockNode::new().expect_eval(tenth)),
+            BinaryOpType::SUB,
+            NumberMode::RATIONAL.literal(1.),
+        );
+
+        assert_eq!(
+            node.eval(&mut symtab).unwrap(),
+            Value::Rational(Rational::new(BigInt::from_i64(27), BigInt::from_i64(10)).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_eval_err() {
+        let mut symtab = SymbolTable::new();
+        let node = PercentChangeNode::new(
+            Box::new(MockNode::new().expect_eval_err(InterpreterError::EOF)),
+            Box::new(MockNode::new().expect_eval(0.1)),
+            BinaryOpType::ADD,
+            Value::Number(1.),
+        );
+
+        assert!(node.eval(&mut symtab).is_err());
+    }
+}
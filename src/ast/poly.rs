@@ -0,0 +1,208 @@
+use crate::error::InterpreterError;
+use crate::symbol_table::SymbolTable;
+use crate::value::{Polynomial, Value};
+
+use super::{ASTExpression, ASTNode, ASTSemanticAnalysis, ASTSemanticExpression, NodeKind};
+
+/// Polynomial value, from its coefficients in decreasing powers of `x`,
+/// `poly([1, -3, 2])`, or from an expression in one variable,
+/// `poly((x - 1) * (x - 2), x)`, which is evaluated with the variable bound
+/// to the polynomial itself.
+pub struct PolyNode {
+    expr: Box<dyn ASTSemanticExpression>,
+    var: Option<String>,
+}
+
+impl PolyNode {
+    pub fn new(expr: Box<dyn ASTSemanticExpression>, var: Option<String>) -> Self {
+        Self { expr, var }
+    }
+
+    fn coefficients(&self, value: Value) -> Result<Polynomial, InterpreterError> {
+        let values = match value {
+            Value::List(values) => values,
+            value => {
+                return Err(InterpreterError::TypeError(format!(
+                    "poly expects a list of coefficients, found {}",
+                    value
+                )))
+            }
+        };
+        let coefficients = values
+            .iter()
+            .rev()
+            .map(|value| self.real(value))
+            .collect::<Result<Vec<f64>, InterpreterError>>()?;
+
+        Ok(Polynomial::new(coefficients, "x".to_string()))
+    }
+
+    fn substitute(
+        &self,
+        var: &str,
+        symtab: &mut SymbolTable,
+    ) -> Result<Polynomial, InterpreterError> {
+        symtab.push_binding(
+            var.to_string(),
+            Value::Polynomial(Polynomial::variable(var.to_string())),
+        )?;
+
+        let result = self.expr.eval(symtab);
+
+        symtab.pop_scope();
+
+        match result? {
+            Value::Polynomial(polynomial) => Ok(polynomial),
+            value => Ok(Polynomial::constant(self.real(&value)?, var.to_string())),
+        }
+    }
+
+    fn real(&self, value: &Value) -> Result<f64, InterpreterError> {
+        match value {
+            Value::Number(_) | Value::Decimal(_) | Value::Rational(_) | Value::Integer(_) => {
+                Ok(value.to_f64())
+            }
+            _ => Err(InterpreterError::TypeError(format!(
+                "poly expects real coefficients, found {}",
+                value
+            ))),
+        }
+    }
+}
+
+impl ASTNode for PolyNode {
+    fn kind(&self) -> NodeKind<'_> {
+        NodeKind::Poly(self.expr.as_ref(), self.var.as_ref())
+    }
+}
+
+impl ASTExpression for PolyNode {
+    fn pure(&self) -> bool {
+        false
+    }
+
+    fn eval(&self, symtab: &mut SymbolTable) -> Result<Value, InterpreterError> {
+        let polynomial = match &self.var {
+            Some(var) => self.substitute(var, symtab)?,
+            None => self.coefficients(self.expr.eval(symtab)?)?,
+        };
+
+        Ok(Value::Polynomial(polynomial))
+    }
+}
+
+impl ASTSemanticAnalysis for PolyNode {
+    fn check_semantic(&self, symtab: &mut SymbolTable) -> Result<(), InterpreterError> {
+        let var = match &self.var {
+            Some(var) => var,
+            None if self.expr.pure() => return Ok(()),
+            None => return self.expr.check_semantic(symtab),
+        };
+
+        if symtab.is_global(var) {
+            return Err(InterpreterError::RedefineBuiltinSymbol(var.clone()));
+        }
+
+        if self.expr.pure() {
+            return Ok(());
+        }
+
+        symtab.push_binding(var.clone(), Value::Number(0.))?;
+
+        let result = self.expr.check_semantic(symtab);
+
+        symtab.pop_scope();
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::tests::{number, var};
+    use crate::ast::{BinaryOpNode, BinaryOpType, ListNode};
+    use crate::error::InterpreterError;
+    use crate::symbol_table::SymbolTable;
+    use crate::value::{Polynomial, Value};
+
+    use super::{ASTExpression, ASTSemanticAnalysis, PolyNode};
+
+    #[test]
+    fn test_eval() {
+        let mut symtab = SymbolTable::new();
+        let expected = Value::Polynomial(Polynomial::new(vec![2., -3., 1.], "x".to_string()));
+
+        // poly([1, -3, 2])
+        let node = PolyNode::new(
+            Box::new(ListNode::new(vec![number(1.), number(-3.), number(2.)])),
+            None,
+        );
+
+        assert_eq!(node.eval(&mut symtab).unwrap(), expected);
+
+        // poly((x - 1) * (x - 2), x)
+        let node = PolyNode::new(
+            Box::new(BinaryOpNode::new(
+                Box::new(BinaryOpNode::new(var("x"), number(1.), BinaryOpType::SUB)),
+                Box::new(BinaryOpNode::new(var("x"), number(2.), BinaryOpType::SUB)),
+                BinaryOpType::MUL,
+            )),
+            Some("x".to_string()),
+        );
+
+        assert_eq!(node.eval(&mut symtab).unwrap(), expected);
+        assert_eq!(symtab.get(&"x".to_string()), None);
+
+        let node = PolyNode::new(number(3.), Some("t".to_string()));
+
+        assert_eq!(
+            node.eval(&mut symtab).unwrap(),
+            Value::Polynomial(Polynomial::constant(3., "t".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_eval_err() {
+        let mut symtab = SymbolTable::new();
+
+        assert!(matches!(
+            PolyNode::new(number(1.), None).eval(&mut symtab),
+            Err(InterpreterError::TypeError(_))
+        ));
+
+        // x^0.5 is not a polynomial
+        let node = PolyNode::new(
+            Box::new(BinaryOpNode::new(var("x"), number(0.5), BinaryOpType::POW)),
+            Some("x".to_string()),
+        );
+
+        assert!(matches!(
+            node.eval(&mut symtab),
+            Err(InterpreterError::TypeError(_))
+        ));
+    }
+
+    #[test]
+    fn test_check_semantic() {
+        let mut symtab = SymbolTable::new();
+        let node = PolyNode::new(
+            Box::new(BinaryOpNode::new(var("x"), number(1.), BinaryOpType::ADD)),
+            Some("x".to_string()),
+        );
+
+        assert!(node.check_semantic(&mut symtab).is_ok());
+
+        let node = PolyNode::new(var("y"), Some("x".to_string()));
+
+        assert!(matches!(
+            node.check_semantic(&mut symtab),
+            Err(InterpreterError::UndefinedSymbol(_))
+        ));
+
+        let node = PolyNode::new(var("e"), Some("e".to_string()));
+
+        assert!(matches!(
+            node.check_semantic(&mut symtab),
+            Err(InterpreterError::RedefineBuiltinSymbol(_))
+        ));
+    }
+}
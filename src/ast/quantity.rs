@@ -0,0 +1,191 @@
+use crate::error::InterpreterError;
+use crate::symbol_table::SymbolTable;
+use crate::value::{Quantity, Unit, Value};
+
+use super::{ASTExpression, ASTNode, ASTSemanticAnalysis, ASTSemanticExpression, NodeKind};
+
+/// Unit names with their exponents, `m/s^2` is `[("m", 1), ("s", -2)]`.
+pub type UnitExpr = Vec<(String, i32)>;
+
+fn resolve_unit(unit: &UnitExpr, symtab: &SymbolTable) -> Result<Unit, InterpreterError> {
+    let mut result = Unit::dimensionless();
+
+    for (name, exponent) in unit {
+        let base = symtab
+            .get_unit(name)
+            .ok_or_else(|| InterpreterError::UndefinedUnit(name.clone()))?;
+
+        result = if *exponent < 0 {
+            result.div(&base.powi(-exponent))
+        } else {
+            result.mul(&base.powi(*exponent))
+        };
+    }
+
+    Ok(result)
+}
+
+fn to_quantity(value: Value) -> Result<Quantity, InterpreterError> {
+    match value {
+        Value::Quantity(value) => Ok(value),
+        Value::Complex(_) => Err(InterpreterError::TypeError(format!(
+            "units expect a real value, found {}",
+            value
+        ))),
+        _ => Ok(Quantity::dimensionless(value.to_f64())),
+    }
+}
+
+/// A number with a unit, such as `9.81 m/s^2`.
+pub struct QuantityNode {
+    node: Box<dyn ASTSemanticExpression>,
+    unit: UnitExpr,
+}
+
+impl QuantityNode {
+    pub fn new(node: Box<dyn ASTSemanticExpression>, unit: UnitExpr) -> Self {
+        Self { node, unit }
+    }
+}
+
+impl ASTNode for QuantityNode {
+    fn kind(&self) -> NodeKind<'_> {
+        NodeKind::Quantity(self.node.as_ref(), &self.unit)
+    }
+}
+
+impl ASTExpression for QuantityNode {
+    fn pure(&self) -> bool {
+        false
+    }
+
+    fn eval(&self, symtab: &mut SymbolTable) -> Result<Value, InterpreterError> {
+        let unit = resolve_unit(&self.unit, symtab)?;
+        let value = to_quantity(self.node.eval(symtab)?)?;
+
+        Ok(Value::from_quantity(Quantity::new(value.value(), unit)))
+    }
+}
+
+impl ASTSemanticAnalysis for QuantityNode {
+    fn check_semantic(&self, symtab: &mut SymbolTable) -> Result<(), InterpreterError> {
+        resolve_unit(&self.unit, symtab)?;
+
+        if self.node.pure() {
+            Ok(())
+        } else {
+            self.node.check_semantic(symtab)
+        }
+    }
+}
+
+/// Unit conversion, `60 mph to km/h`.
+pub struct ConvertNode {
+    node: Box<dyn ASTSemanticExpression>,
+    unit: UnitExpr,
+}
+
+impl ConvertNode {
+    pub fn new(node: Box<dyn ASTSemanticExpression>, unit: UnitExpr) -> Self {
+        Self { node, unit }
+    }
+}
+
+impl ASTNode for ConvertNode {
+    fn kind(&self) -> NodeKind<'_> {
+        NodeKind::Convert(self.node.as_ref(), &self.unit)
+    }
+}
+
+impl ASTExpression for ConvertNode {
+    fn pure(&self) -> bool {
+        false
+    }
+
+    fn eval(&self, symtab: &mut SymbolTable) -> Result<Value, InterpreterError> {
+        let unit = resolve_unit(&self.unit, symtab)?;
+        let value = to_quantity(self.node.eval(symtab)?)?;
+
+        value.convert(&unit).map(Value::Quantity).ok_or_else(|| {
+            InterpreterError::IncompatibleUnits(
+                value.dimension().to_string(),
+                unit.dimension().to_string(),
+            )
+        })
+    }
+}
+
+impl ASTSemanticAnalysis for ConvertNode {
+    fn check_semantic(&self, symtab: &mut SymbolTable) -> Result<(), InterpreterError> {
+        resolve_unit(&self.unit, symtab)?;
+
+        if self.node.pure() {
+            Ok(())
+        } else {
+            self.node.check_semantic(symtab)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::tests::MockNode;
+    use crate::error::InterpreterError;
+    use crate::symbol_table::SymbolTable;
+
+    use super::{ASTExpression, ASTSemanticAnalysis, ConvertNode, QuantityNode, UnitExpr};
+
+    fn unit(parts: &[(&str, i32)]) -> UnitExpr {
+        parts
+            .iter()
+            .map(|(name, exponent)| (name.to_string(), *exponent))
+            .collect()
+    }
+
+    #[test]
+    fn test_eval_quantity() {
+        let mut symtab = SymbolTable::new();
+        let inner = MockNode::new().expect_eval(9.81);
+        let node = QuantityNode::new(Box::new(inner), unit(&[("m", 1), ("s", -2)]));
+        let result = node.eval(&mut symtab);
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().to_string(), "9.81 m/s^2");
+    }
+
+    #[test]
+    fn test_eval_convert() {
+        let mut symtab = SymbolTable::new();
+        let inner = QuantityNode::new(Box::new(MockNode::new().expect_eval(3.)), unit(&[("h", 1)]));
+        let node = ConvertNode::new(Box::new(inner), unit(&[("min", 1)]));
+        let result = node.eval(&mut symtab);
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().to_string(), "180 min");
+    }
+
+    #[test]
+    fn test_eval_convert_incompatible() {
+        let mut symtab = SymbolTable::new();
+        let inner = QuantityNode::new(Box::new(MockNode::new().expect_eval(3.)), unit(&[("h", 1)]));
+        let node = ConvertNode::new(Box::new(inner), unit(&[("km", 1)]));
+        let result = node.eval(&mut symtab);
+
+        assert!(matches!(
+            result,
+            Err(InterpreterError::IncompatibleUnits(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_check_semantic_undefined_unit() {
+        let mut symtab = SymbolTable::new();
+        let inner = MockNode::new().expect_pure(true);
+        let node = QuantityNode::new(Box::new(inner), unit(&[("parsec", 1)]));
+
+        assert!(matches!(
+            node.check_semantic(&mut symtab),
+            Err(InterpreterError::UndefinedUnit(_))
+        ));
+    }
+}
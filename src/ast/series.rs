@@ -0,0 +1,258 @@
+use crate::error::InterpreterError;
+use crate::symbol_table::SymbolTable;
+use crate::value::{BigInt, Value};
+
+use super::{
+    ASTExpression, ASTNode, ASTSemanticAnalysis, ASTSemanticExpression, BinaryOpType, NodeKind,
+};
+
+/// Series longer than this are an error rather than a calculation that
+/// seems to hang.
+const MAX_TERMS: i64 = 10_000_000;
+
+/// Sum or product over an integer index, `sum(k, 1, n, k^2)` or
+/// `prod(k, 1, n, 1 + r / k)`. The index is bound only in the body, an empty
+/// range is the sum 0 or the product 1.
+pub struct SeriesNode {
+    op_type: BinaryOpType,
+    index: String,
+    lo: Box<dyn ASTSemanticExpression>,
+    hi: Box<dyn ASTSemanticExpression>,
+    body: Box<dyn ASTSemanticExpression>,
+}
+
+impl SeriesNode {
+    /// Series that combines its terms with `op_type`, `ADD` for a sum and
+    /// `MUL` for a product.
+    pub fn new(
+        op_type: BinaryOpType,
+        index: String,
+        lo: Box<dyn ASTSemanticExpression>,
+        hi: Box<dyn ASTSemanticExpression>,
+        body: Box<dyn ASTSemanticExpression>,
+    ) -> Self {
+        Self {
+            op_type,
+            index,
+            lo,
+            hi,
+            body,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self.op_type {
+            BinaryOpType::MUL => "prod",
+            _ => "sum",
+        }
+    }
+
+    fn bound(
+        &self,
+        node: &dyn ASTSemanticExpression,
+        symtab: &mut SymbolTable,
+    ) -> Result<i64, InterpreterError> {
+        let value = node.eval(symtab)?;
+
+        value
+            .to_bigint()
+            .and_then(|value| value.to_i64())
+            .ok_or_else(|| {
+                InterpreterError::TypeError(format!(
+                    "{} expects integer bounds, found {}",
+                    self.name(),
+                    value
+                ))
+            })
+    }
+
+    fn terms(&self, lo: i64, hi: i64, symtab: &mut SymbolTable) -> Result<Value, InterpreterError> {
+        let mut result = Value::Number(if self.op_type == BinaryOpType::MUL {
+            1.
+        } else {
+            0.
+        });
+
+        for k in lo..=hi {
+            symtab.rebind(&self.index, Value::from_bigint(BigInt::from_i64(k)));
+            result = self.op_type.apply(&result, &self.body.eval(symtab)?)?;
+        }
+
+        Ok(result)
+    }
+}
+
+impl ASTNode for SeriesNode {
+    fn kind(&self) -> NodeKind<'_> {
+        NodeKind::Series(
+            self.op_type,
+            &self.index,
+            self.lo.as_ref(),
+            self.hi.as_ref(),
+            self.body.as_ref(),
+        )
+    }
+}
+
+impl ASTExpression for SeriesNode {
+    fn pure(&self) -> bool {
+        false
+    }
+
+    fn eval(&self, symtab: &mut SymbolTable) -> Result<Value, InterpreterError> {
+        let lo = self.bound(self.lo.as_ref(), symtab)?;
+        let hi = self.bound(self.hi.as_ref(), symtab)?;
+
+        if hi.saturating_sub(lo) >= MAX_TERMS {
+            return Err(InterpreterError::DomainError(format!(
+                "{} over more than {} terms",
+                self.name(),
+                MAX_TERMS
+            )));
+        }
+
+        symtab.push_binding(self.index.clone(), Value::Number(0.))?;
+
+        let result = self.terms(lo, hi, symtab);
+
+        symtab.pop_scope();
+        result
+    }
+}
+
+impl ASTSemanticAnalysis for SeriesNode {
+    fn check_semantic(&self, symtab: &mut SymbolTable) -> Result<(), InterpreterError> {
+        if symtab.is_global(&self.index) {
+            return Err(InterpreterError::RedefineBuiltinSymbol(self.index.clone()));
+        }
+
+        // The bounds are outside the scope of the index.
+        for node in [&self.lo, &self.hi] {
+            if !node.pure() {
+                node.check_semantic(symtab)?;
+            }
+        }
+
+        if self.body.pure() {
+            return Ok(());
+        }
+
+        symtab.push_binding(self.index.clone(), Value::Number(0.))?;
+
+        let result = self.body.check_semantic(symtab);
+
+        symtab.pop_scope();
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::tests::{number, var};
+    use crate::ast::{ASTSemanticExpression, BinaryOpNode, BinaryOpType, NumberNode};
+    use crate::error::InterpreterError;
+    use crate::symbol_table::SymbolTable;
+    use crate::value::{DecimalContext, NumberMode, Value};
+
+    use super::{ASTExpression, ASTSemanticAnalysis, SeriesNode};
+
+    // op(k, lo, hi, body)
+    fn series(
+        op_type: BinaryOpType,
+        lo: Box<dyn ASTSemanticExpression>,
+        hi: Box<dyn ASTSemanticExpression>,
+        body: Box<dyn ASTSemanticExpression>,
+    ) -> SeriesNode {
+        SeriesNode::new(op_type, "k".to_string(), lo, hi, body)
+    }
+
+    #[test]
+    fn test_eval() {
+        let mut symtab = SymbolTable::new();
+        let square = || Box::new(BinaryOpNode::new(var("k"), number(2.), BinaryOpType::POW));
+
+        let node = series(BinaryOpType::ADD, number(1.), number(10.), square());
+        assert_eq!(node.eval(&mut symtab).unwrap(), Value::Number(385.));
+
+        let node = series(BinaryOpType::MUL, number(1.), number(5.), var("k"));
+        assert_eq!(node.eval(&mut symtab).unwrap(), Value::Number(120.));
+
+        // Empty ranges
+        let node = series(BinaryOpType::ADD, number(1.), number(0.), square());
+        assert_eq!(node.eval(&mut symtab).unwrap(), Value::Number(0.));
+
+        let node = series(BinaryOpType::MUL, number(1.), number(0.), square());
+        assert_eq!(node.eval(&mut symtab).unwrap(), Value::Number(1.));
+
+        // Exact bounds
+        for mode in [
+            NumberMode::DECIMAL(DecimalContext::default()),
+            NumberMode::RATIONAL,
+        ] {
+            let bound = |value| Box::new(NumberNode::new(mode.literal(value)));
+            let node = series(BinaryOpType::ADD, bound(1.), bound(3.), var("k"));
+
+            assert_eq!(node.eval(&mut symtab).unwrap().to_f64(), 6.);
+        }
+
+        // The index is only bound in the body
+        assert_eq!(symtab.get(&"k".to_string()), None);
+
+        // Nested sums, the inner bound refers to the outer index
+        let inner = SeriesNode::new(
+            BinaryOpType::ADD,
+            "j".to_string(),
+            number(1.),
+            var("k"),
+            var("j"),
+        );
+        let node = series(BinaryOpType::ADD, number(1.), number(3.), Box::new(inner));
+        assert_eq!(node.eval(&mut symtab).unwrap(), Value::Number(10.));
+    }
+
+    #[test]
+    fn test_eval_err() {
+        let mut symtab = SymbolTable::new();
+
+        let node = series(BinaryOpType::ADD, number(1.), number(2.5), var("k"));
+        assert!(matches!(
+            node.eval(&mut symtab),
+            Err(InterpreterError::TypeError(_))
+        ));
+
+        let node = series(BinaryOpType::ADD, number(0.), number(1e12), var("k"));
+        assert!(matches!(
+            node.eval(&mut symtab),
+            Err(InterpreterError::DomainError(_))
+        ));
+        assert_eq!(symtab.get(&"k".to_string()), None);
+    }
+
+    #[test]
+    fn test_check_semantic() {
+        let mut symtab = SymbolTable::new();
+
+        let node = series(BinaryOpType::ADD, number(1.), number(3.), var("k"));
+        assert!(node.check_semantic(&mut symtab).is_ok());
+        assert_eq!(symtab.get(&"k".to_string()), None);
+
+        // The index is not in scope in the bounds
+        let node = series(BinaryOpType::ADD, number(1.), var("k"), var("k"));
+        assert!(matches!(
+            node.check_semantic(&mut symtab),
+            Err(InterpreterError::UndefinedSymbol(_))
+        ));
+
+        let node = SeriesNode::new(
+            BinaryOpType::MUL,
+            "pi".to_string(),
+            number(1.),
+            number(3.),
+            var("pi"),
+        );
+        assert!(matches!(
+            node.check_semantic(&mut symtab),
+            Err(InterpreterError::RedefineBuiltinSymbol(_))
+        ));
+    }
+}
@@ -0,0 +1,124 @@
+use std::rc::Rc;
+
+use crate::error::InterpreterError;
+use crate::symbol_table::SymbolTable;
+use crate::symbolic;
+use crate::value::{Function, Value};
+
+use super::{ASTExpression, ASTNode, ASTSemanticAnalysis, ASTSemanticExpression, NodeKind};
+
+/// Canonical form, `simplify(2x + 3x)`. Evaluates to a function whose
+/// parameters are the variables of the expression that are not defined.
+pub struct SimplifyNode {
+    expr: Box<dyn ASTSemanticExpression>,
+}
+
+impl SimplifyNode {
+    pub fn new(expr: Box<dyn ASTSemanticExpression>) -> Self {
+        Self { expr }
+    }
+
+    fn params(&self, symtab: &SymbolTable) -> Vec<String> {
+        symbolic::variables(self.expr.as_ref())
+            .into_iter()
+            .filter(|name| symtab.get(name).is_none())
+            .collect()
+    }
+}
+
+impl ASTNode for SimplifyNode {
+    fn kind(&self) -> NodeKind<'_> {
+        NodeKind::Simplify(self.expr.as_ref())
+    }
+}
+
+impl ASTExpression for SimplifyNode {
+    fn pure(&self) -> bool {
+        false
+    }
+
+    fn eval(&self, symtab: &mut SymbolTable) -> Result<Value, InterpreterError> {
+        let body = symbolic::simplify(self.expr.as_ref())?;
+
+        Ok(Value::Function(Function::anonymous(
+            self.params(symtab),
+            Rc::from(body),
+        )))
+    }
+}
+
+impl ASTSemanticAnalysis for SimplifyNode {
+    fn check_semantic(&self, symtab: &mut SymbolTable) -> Result<(), InterpreterError> {
+        symtab.push_scope(
+            self.params(symtab)
+                .into_iter()
+                .map(|param| (param, Value::Number(0.)))
+                .collect(),
+        )?;
+
+        let result = if self.expr.pure() {
+            Ok(())
+        } else {
+            self.expr.check_semantic(symtab)
+        };
+
+        symtab.pop_scope();
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::tests::var;
+    use crate::ast::tests::MockNode;
+    use crate::ast::{BinaryOpNode, BinaryOpType};
+    use crate::error::InterpreterError;
+    use crate::symbol_table::SymbolTable;
+    use crate::value::Value;
+
+    use super::{ASTExpression, ASTSemanticAnalysis, SimplifyNode};
+
+    #[test]
+    fn test_eval() {
+        let mut symtab = SymbolTable::new();
+        // y * x + x * y
+        let node = SimplifyNode::new(Box::new(BinaryOpNode::new(
+            Box::new(BinaryOpNode::new(var("y"), var("x"), BinaryOpType::MUL)),
+            Box::new(BinaryOpNode::new(var("x"), var("y"), BinaryOpType::MUL)),
+            BinaryOpType::ADD,
+        )));
+
+        symtab.insert("y".to_string(), Value::Number(3.));
+
+        let function = match node.eval(&mut symtab) {
+            Ok(Value::Function(function)) => function,
+            result => panic!("{:?} is not a function", result),
+        };
+
+        assert_eq!(function.to_string(), "x -> 2 * x * y");
+        assert_eq!(
+            function.call(&[Value::Number(2.)], &mut symtab).unwrap(),
+            12.
+        );
+    }
+
+    #[test]
+    fn test_eval_err() {
+        let mut symtab = SymbolTable::new();
+        let node = SimplifyNode::new(Box::new(MockNode::new().expect_eval(1.)));
+
+        assert!(matches!(
+            node.eval(&mut symtab),
+            Err(InterpreterError::TypeError(_))
+        ));
+    }
+
+    #[test]
+    fn test_check_semantic() {
+        let mut symtab = SymbolTable::new();
+        let node = SimplifyNode::new(var("x"));
+
+        assert!(node.check_semantic(&mut symtab).is_ok());
+        assert_eq!(symtab.get(&"x".to_string()), None);
+    }
+}
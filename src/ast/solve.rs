@@ -0,0 +1,293 @@
+use crate::builtins;
+use crate::error::InterpreterError;
+use crate::format;
+use crate::math;
+use crate::symbol_table::SymbolTable;
+use crate::value::{Dual, Value};
+
+use super::{
+    ASTExpression, ASTNode, ASTSemanticAnalysis, ASTSemanticExpression, BinaryOpType, NodeKind,
+};
+
+/// Numeric solution of an equation in one variable, `solve(x^2 = 2, x)`.
+/// Evaluates to the root Newton's method reaches from an initial guess,
+/// `solve(x^2 = 2, x, -1)`, which is 1 by default. Given an interval,
+/// `solve(x^2 = 2, x, -5, 5)` is the list of all roots in it.
+pub struct SolveNode {
+    lhs: Box<dyn ASTSemanticExpression>,
+    rhs: Box<dyn ASTSemanticExpression>,
+    var: String,
+    bounds: Vec<Box<dyn ASTSemanticExpression>>,
+}
+
+impl SolveNode {
+    pub fn new(
+        lhs: Box<dyn ASTSemanticExpression>,
+        rhs: Box<dyn ASTSemanticExpression>,
+        var: String,
+        bounds: Vec<Box<dyn ASTSemanticExpression>>,
+    ) -> Self {
+        Self {
+            lhs,
+            rhs,
+            var,
+            bounds,
+        }
+    }
+
+    /// Difference of the two sides with the variable bound to `x`. The
+    /// methods cannot carry the derivatives of a dual number the equation
+    /// refers to, as under `deriv`, so a real `x` must give a real result.
+    fn residual(&self, x: Value, symtab: &mut SymbolTable) -> Result<Dual, InterpreterError> {
+        let real = !matches!(x, Value::Dual(_));
+
+        symtab.push_binding(self.var.clone(), x)?;
+
+        let result = self.difference(symtab);
+
+        symtab.pop_scope();
+
+        let result = result?;
+
+        if real && matches!(result, Value::Dual(_)) {
+            return Err(InterpreterError::TypeError(
+                "solve is not differentiable".to_string(),
+            ));
+        }
+
+        result.to_dual().ok_or_else(|| {
+            InterpreterError::TypeError(format!(
+                "solve expects a real-valued equation, {} = {} is {}",
+                format::expression(self.lhs.as_ref()),
+                format::expression(self.rhs.as_ref()),
+                result
+            ))
+        })
+    }
+
+    fn difference(&self, symtab: &mut SymbolTable) -> Result<Value, InterpreterError> {
+        let lhs = self.lhs.eval(symtab)?;
+        let rhs = self.rhs.eval(symtab)?;
+
+        BinaryOpType::SUB.apply(&lhs, &rhs)
+    }
+
+    fn bound(
+        &self,
+        node: &dyn ASTSemanticExpression,
+        symtab: &mut SymbolTable,
+    ) -> Result<f64, InterpreterError> {
+        builtins::expect_real_number("solve", &node.eval(symtab)?)
+    }
+}
+
+impl ASTNode for SolveNode {
+    fn kind(&self) -> NodeKind<'_> {
+        NodeKind::Solve(
+            self.lhs.as_ref(),
+            self.rhs.as_ref(),
+            &self.var,
+            &self.bounds,
+        )
+    }
+}
+
+impl ASTExpression for SolveNode {
+    fn pure(&self) -> bool {
+        false
+    }
+
+    fn eval(&self, symtab: &mut SymbolTable) -> Result<Value, InterpreterError> {
+        match self.bounds.as_slice() {
+            [lo, hi] => {
+                let (lo, hi) = (
+                    self.bound(lo.as_ref(), symtab)?,
+                    self.bound(hi.as_ref(), symtab)?,
+                );
+                let roots = math::roots(
+                    |x| Ok(self.residual(Value::Number(x), symtab)?.value()),
+                    lo,
+                    hi,
+                    math::DEFAULT_TOLERANCE,
+                )?;
+
+                Ok(Value::List(roots.into_iter().map(Value::Number).collect()))
+            }
+            bounds => {
+                let guess = match bounds.first() {
+                    Some(guess) => self.bound(guess.as_ref(), symtab)?,
+                    None => 1.,
+                };
+                let root = math::newton(
+                    |x| {
+                        let y = self.residual(Value::Number(x), symtab)?;
+                        let dy = self.residual(Value::Dual(Dual::variable(x, 0, 1)), symtab)?;
+
+                        Ok((y.value(), dy.derivative(0)))
+                    },
+                    guess,
+                    math::DEFAULT_TOLERANCE,
+                )?;
+
+                root.map(Value::Number).ok_or_else(|| {
+                    InterpreterError::ConvergenceFailure(format!(
+                        "{} from {}",
+                        format::expression(self),
+                        guess
+                    ))
+                })
+            }
+        }
+    }
+}
+
+impl ASTSemanticAnalysis for SolveNode {
+    fn check_semantic(&self, symtab: &mut SymbolTable) -> Result<(), InterpreterError> {
+        if symtab.is_global(&self.var) {
+            return Err(InterpreterError::RedefineBuiltinSymbol(self.var.clone()));
+        }
+
+        for node in self.bounds.iter() {
+            if !node.pure() {
+                node.check_semantic(symtab)?;
+            }
+        }
+
+        symtab.push_binding(self.var.clone(), Value::Number(0.))?;
+
+        let result = [&self.lhs, &self.rhs]
+            .into_iter()
+            .filter(|node| !node.pure())
+            .try_for_each(|node| node.check_semantic(symtab));
+
+        symtab.pop_scope();
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::tests::MockNode;
+    use crate::ast::tests::{number, var};
+    use crate::ast::{ASTSemanticExpression, BinaryOpNode, BinaryOpType, CallNode};
+    use crate::error::InterpreterError;
+    use crate::symbol_table::SymbolTable;
+    use crate::value::{Dual, Value};
+
+    use super::{ASTExpression, ASTSemanticAnalysis, SolveNode};
+
+    // x^2 = 2
+    fn square(bounds: Vec<Box<dyn ASTSemanticExpression>>) -> SolveNode {
+        SolveNode::new(
+            Box::new(BinaryOpNode::new(var("x"), number(2.), BinaryOpType::POW)),
+            number(2.),
+            "x".to_string(),
+            bounds,
+        )
+    }
+
+    #[test]
+    fn test_eval() {
+        let mut symtab = SymbolTable::new();
+
+        let result = square(vec![]).eval(&mut symtab).unwrap();
+        assert!((result.to_f64() - 2f64.sqrt()).abs() < 1e-12);
+
+        let result = square(vec![number(-1.)]).eval(&mut symtab).unwrap();
+        assert!((result.to_f64() + 2f64.sqrt()).abs() < 1e-12);
+
+        let result = square(vec![number(-5.), number(5.)])
+            .eval(&mut symtab)
+            .unwrap();
+
+        match result {
+            Value::List(roots) => {
+                assert_eq!(roots.len(), 2);
+                assert!((roots[0].to_f64() + 2f64.sqrt()).abs() < 1e-10);
+                assert!((roots[1].to_f64() - 2f64.sqrt()).abs() < 1e-10);
+            }
+            _ => panic!("expected a list of roots, found {}", result),
+        }
+
+        // The variable is only bound while solving
+        assert_eq!(symtab.get(&"x".to_string()), None);
+    }
+
+    #[test]
+    fn test_eval_err() {
+        let mut symtab = SymbolTable::new();
+        // x^2 = -1
+        let node = SolveNode::new(
+            Box::new(BinaryOpNode::new(var("x"), number(2.), BinaryOpType::POW)),
+            number(-1.),
+            "x".to_string(),
+            vec![],
+        );
+
+        assert!(matches!(
+            node.eval(&mut symtab),
+            Err(InterpreterError::ConvergenceFailure(_))
+        ));
+
+        // sqrt(x) = -1 is complex left of 0
+        let node = SolveNode::new(
+            Box::new(CallNode::new("sqrt".to_string(), vec![var("x")])),
+            number(-1.),
+            "x".to_string(),
+            vec![number(-4.), number(4.)],
+        );
+
+        assert!(matches!(
+            node.eval(&mut symtab),
+            Err(InterpreterError::TypeError(_))
+        ));
+
+        // t^2 = x under deriv, whose derivative solve cannot carry
+        symtab.insert("x".to_string(), Value::Dual(Dual::variable(4., 0, 1)));
+
+        let bounds: [Vec<Box<dyn ASTSemanticExpression>>; 2] =
+            [vec![], vec![number(0.), number(5.)]];
+
+        for bounds in bounds {
+            let node = SolveNode::new(
+                Box::new(BinaryOpNode::new(var("t"), number(2.), BinaryOpType::POW)),
+                var("x"),
+                "t".to_string(),
+                bounds,
+            );
+
+            assert!(matches!(
+                node.eval(&mut symtab),
+                Err(InterpreterError::TypeError(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_check_semantic() {
+        let mut symtab = SymbolTable::new();
+
+        assert!(square(vec![number(0.), number(1.)])
+            .check_semantic(&mut symtab)
+            .is_ok());
+
+        let node = SolveNode::new(
+            var("x"),
+            var("y"),
+            "x".to_string(),
+            vec![Box::new(MockNode::new())],
+        );
+
+        assert!(matches!(
+            node.check_semantic(&mut symtab),
+            Err(InterpreterError::UndefinedSymbol(_))
+        ));
+
+        let node = SolveNode::new(var("pi"), number(3.), "pi".to_string(), vec![]);
+
+        assert!(matches!(
+            node.check_semantic(&mut symtab),
+            Err(InterpreterError::RedefineBuiltinSymbol(_))
+        ));
+    }
+}
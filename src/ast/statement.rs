@@ -1,21 +1,22 @@
 use crate::error::InterpreterError;
 use crate::symbol_table::SymbolTable;
+use crate::value::Value;
 
-use super::{ASTNode, ASTSemanticAnalysis, ASTStatement, AssignNode};
+use super::{ASTNode, ASTSemanticAnalysis, ASTSemanticStatement, ASTStatement, NodeKind};
 
 pub struct StatementListNode {
-    nodes: Vec<Box<AssignNode>>,
+    nodes: Vec<Box<dyn ASTSemanticStatement>>,
 }
 
 impl StatementListNode {
-    pub fn new(nodes: Vec<Box<AssignNode>>) -> Self {
+    pub fn new(nodes: Vec<Box<dyn ASTSemanticStatement>>) -> Self {
         Self { nodes }
     }
 }
 
 impl ASTStatement for StatementListNode {
-    fn execute(&self, symtab: &mut SymbolTable) -> Result<f64, InterpreterError> {
-        let mut value: f64 = 0.;
+    fn execute(&self, symtab: &mut SymbolTable) -> Result<Value, InterpreterError> {
+        let mut value = Value::Number(0.);
 
         for node in self.nodes.iter() {
             value = node.execute(symtab)?;
@@ -25,16 +26,16 @@ impl ASTStatement for StatementListNode {
     }
 }
 
-impl ASTNode for StatementListNode {}
+impl ASTNode for StatementListNode {
+    fn kind(&self) -> NodeKind<'_> {
+        NodeKind::StatementList(&self.nodes)
+    }
+}
 
 impl ASTSemanticAnalysis for StatementListNode {
     fn check_semantic(&self, symtab: &mut SymbolTable) -> Result<(), InterpreterError> {
         for node in self.nodes.iter() {
-            let res = node.check_semantic(symtab);
-
-            if res.is_err() {
-                return res;
-            }
+            node.check_semantic(symtab)?;
         }
 
         Ok(())
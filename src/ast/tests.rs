@@ -1,10 +1,11 @@
 use crate::error::InterpreterError;
 use crate::symbol_table::SymbolTable;
+use crate::value::Value;
 
-use super::{ASTExpression, ASTNode, ASTSemanticAnalysis};
+use super::{ASTExpression, ASTNode, ASTSemanticAnalysis, NumberNode, VarNode};
 
 pub struct MockNode {
-    eval_value: f64,
+    eval_value: Value,
     eval_err: InterpreterError,
     eval_is_ok: bool,
     pure: bool,
@@ -15,7 +16,7 @@ pub struct MockNode {
 impl MockNode {
     pub fn new() -> Self {
         Self {
-            eval_value: 0.,
+            eval_value: Value::Number(0.),
             eval_err: InterpreterError::EOF,
             eval_is_ok: true,
             pure: true,
@@ -24,9 +25,9 @@ impl MockNode {
         }
     }
 
-    pub fn expect_eval(&self, expect_value: f64) -> Self {
+    pub fn expect_eval<T: Into<Value>>(&self, expect_value: T) -> Self {
         Self {
-            eval_value: expect_value,
+            eval_value: expect_value.into(),
             eval_err: InterpreterError::EOF,
             eval_is_ok: true,
             pure: self.pure,
@@ -37,7 +38,7 @@ impl MockNode {
 
     pub fn expect_eval_err(&self, err: InterpreterError) -> Self {
         Self {
-            eval_value: self.eval_value,
+            eval_value: self.eval_value.clone(),
             eval_err: err,
             eval_is_ok: false,
             pure: self.pure,
@@ -48,7 +49,7 @@ impl MockNode {
 
     pub fn expect_pure(&self, expect_value: bool) -> Self {
         Self {
-            eval_value: self.eval_value,
+            eval_value: self.eval_value.clone(),
             eval_err: self.eval_err.clone(),
             eval_is_ok: self.eval_is_ok,
             pure: expect_value,
@@ -59,7 +60,7 @@ impl MockNode {
 
     pub fn expect_check_semantic(&self) -> Self {
         Self {
-            eval_value: self.eval_value,
+            eval_value: self.eval_value.clone(),
             eval_err: self.eval_err.clone(),
             eval_is_ok: self.eval_is_ok,
             pure: self.pure,
@@ -70,7 +71,7 @@ impl MockNode {
 
     pub fn expect_check_semantic_err(&self, err: InterpreterError) -> Self {
         Self {
-            eval_value: self.eval_value,
+            eval_value: self.eval_value.clone(),
             eval_err: self.eval_err.clone(),
             eval_is_ok: self.eval_is_ok,
             pure: self.pure,
@@ -80,6 +81,12 @@ impl MockNode {
     }
 }
 
+impl Default for MockNode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ASTNode for MockNode {}
 
 impl ASTExpression for MockNode {
@@ -87,9 +94,9 @@ impl ASTExpression for MockNode {
         self.pure
     }
 
-    fn eval(&self, _symtab: &mut SymbolTable) -> Result<f64, InterpreterError> {
+    fn eval(&self, _symtab: &mut SymbolTable) -> Result<Value, InterpreterError> {
         if self.eval_is_ok {
-            Ok(self.eval_value)
+            Ok(self.eval_value.clone())
         } else {
             Err(self.eval_err.clone())
         }
@@ -106,7 +113,18 @@ impl ASTSemanticAnalysis for MockNode {
     }
 }
 
+/// Variable leaf of a tree built by hand in a test.
+pub fn var(name: &str) -> Box<VarNode> {
+    Box::new(VarNode::new(name.to_string()))
+}
+
+/// Number leaf of a tree built by hand in a test.
+pub fn number(value: f64) -> Box<NumberNode> {
+    Box::new(NumberNode::new(value))
+}
+
 #[cfg(test)]
+#[allow(clippy::module_inception)]
 mod tests {
     use crate::error::InterpreterError;
     use crate::symbol_table::SymbolTable;
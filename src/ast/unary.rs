@@ -1,11 +1,160 @@
 use crate::error::InterpreterError;
+use crate::math;
 use crate::symbol_table::SymbolTable;
+use crate::value::{BigInt, Decimal, Interval, Rational, Value};
 
-use super::{ASTExpression, ASTNode, ASTSemanticAnalysis, ASTSemanticExpression};
+use super::{ASTExpression, ASTNode, ASTSemanticAnalysis, ASTSemanticExpression, NodeKind};
 
+/// Factorials of larger integers are computed in floating point.
+const MAX_FACTORIAL: u32 = 10_000;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub enum UnaryOpType {
     PLUS,
     MINUS,
+    FACTORIAL,
+    PERCENT,
+    BITNOT,
+}
+
+impl UnaryOpType {
+    pub fn apply(&self, value: &Value) -> Result<Value, InterpreterError> {
+        if let Value::List(values) = value {
+            return values
+                .iter()
+                .map(|value| self.apply(value))
+                .collect::<Result<Vec<Value>, InterpreterError>>()
+                .map(Value::List);
+        }
+
+        if let Some(result) = self.exact_factorial(value) {
+            return Ok(match value {
+                Value::Decimal(value) => {
+                    Value::Decimal(Decimal::from_bigint(result, value.context()))
+                }
+                Value::Rational(_) => Value::Rational(Rational::from_bigint(result)),
+                _ => Value::from_bigint(result),
+            });
+        }
+
+        match (self, value) {
+            (UnaryOpType::PLUS, _) => Ok(value.clone()),
+            (_, Value::Function(_)) => Err(InterpreterError::TypeError(format!(
+                "{:?} is not defined for functions",
+                self
+            ))),
+            (UnaryOpType::MINUS, Value::Polynomial(value)) => {
+                Ok(Value::Polynomial(value.scale(-1.)))
+            }
+            (_, Value::Polynomial(_)) => Err(InterpreterError::TypeError(format!(
+                "{:?} is not defined for polynomials",
+                self
+            ))),
+            (UnaryOpType::MINUS, Value::Interval(value)) => Ok(Value::Interval(value.neg())),
+            (UnaryOpType::PERCENT, Value::Interval(value)) => {
+                Ok(Value::Interval(value.div(&Interval::point(100.)).unwrap()))
+            }
+            (UnaryOpType::FACTORIAL, Value::Interval(value)) => value
+                .factorial(math::factorial)
+                .map(Value::Interval)
+                .ok_or_else(|| InterpreterError::DomainError(format!("factorial of {}", value))),
+            (_, Value::Interval(_)) => Err(InterpreterError::TypeError(format!(
+                "{:?} is not defined for intervals",
+                self
+            ))),
+            (UnaryOpType::MINUS, Value::Uncertain(value)) => Ok(Value::Uncertain(value.scale(-1.))),
+            (UnaryOpType::PERCENT, Value::Uncertain(value)) => {
+                Ok(Value::Uncertain(value.scale(0.01)))
+            }
+            (UnaryOpType::FACTORIAL, Value::Uncertain(value)) => math::factorial(value.value())
+                .and_then(|_| value.map(|x| math::factorial(x).unwrap_or(f64::NAN)))
+                .map(Value::Uncertain)
+                .ok_or_else(|| {
+                    InterpreterError::DomainError(format!("factorial of {}", value.value()))
+                }),
+            (_, Value::Uncertain(_)) => Err(InterpreterError::TypeError(format!(
+                "{:?} is not defined for measurements",
+                self
+            ))),
+            (UnaryOpType::MINUS, Value::Dual(value)) => Ok(Value::Dual(value.scale(-1.))),
+            (UnaryOpType::PERCENT, Value::Dual(value)) => Ok(Value::Dual(value.scale(0.01))),
+            (UnaryOpType::FACTORIAL, Value::Dual(value)) => match math::factorial(value.value()) {
+                Some(result) => Ok(Value::Dual(
+                    value.chain(result, result * math::digamma(value.value() + 1.)),
+                )),
+                None => Err(InterpreterError::DomainError(format!(
+                    "factorial of {}",
+                    value.value()
+                ))),
+            },
+            (_, Value::Dual(_)) => Err(InterpreterError::TypeError(format!(
+                "{:?} is not defined for dual numbers",
+                self
+            ))),
+            (UnaryOpType::MINUS, Value::Decimal(value)) => Ok(Value::Decimal(value.neg())),
+            (UnaryOpType::MINUS, Value::Rational(value)) => Ok(Value::Rational(value.neg())),
+            (UnaryOpType::MINUS, Value::Integer(value)) => Ok(Value::Integer(value.neg())),
+            (UnaryOpType::BITNOT, Value::Integer(value)) => Ok(Value::from_bigint(value.not())),
+            (UnaryOpType::MINUS, Value::Complex(value)) => Ok(Value::Complex(value.neg())),
+            (UnaryOpType::PERCENT, Value::Complex(value)) => Ok(Value::Complex(value.scale(0.01))),
+            (UnaryOpType::MINUS, Value::Quantity(value)) => Ok(Value::Quantity(value.neg())),
+            (UnaryOpType::PERCENT, Value::Quantity(value)) => {
+                Ok(Value::Quantity(value.scale(0.01)))
+            }
+            (_, Value::Quantity(_)) => Err(InterpreterError::TypeError(format!(
+                "{:?} expects a dimensionless operand",
+                self
+            ))),
+            (_, Value::Complex(_)) => Err(InterpreterError::TypeError(format!(
+                "{:?} expects a real operand",
+                self
+            ))),
+            (UnaryOpType::PERCENT, Value::Decimal(value)) => {
+                let hundred = Decimal::from_bigint(BigInt::from_i64(100), value.context());
+
+                Ok(Value::Decimal(value.div(&hundred).unwrap()))
+            }
+            (UnaryOpType::PERCENT, Value::Rational(value)) => {
+                let hundred = Rational::from_bigint(BigInt::from_i64(100));
+
+                Ok(Value::Rational(value.div(&hundred).unwrap()))
+            }
+            _ => self.apply_number(value.to_f64()).map(Value::Number),
+        }
+    }
+
+    /// Factorial of a non-negative integer operand, `None` for other operators
+    /// and operands or when it is too large to compute exactly.
+    fn exact_factorial(&self, value: &Value) -> Option<BigInt> {
+        if *self != UnaryOpType::FACTORIAL {
+            return None;
+        }
+
+        value
+            .to_bigint()?
+            .to_i64()
+            .and_then(|n| u32::try_from(n).ok())
+            .filter(|n| *n <= MAX_FACTORIAL)
+            .map(math::big_factorial)
+    }
+
+    fn apply_number(&self, value: f64) -> Result<f64, InterpreterError> {
+        match self {
+            UnaryOpType::PLUS => Ok(value),
+            UnaryOpType::MINUS => Ok(-value),
+            UnaryOpType::FACTORIAL => math::factorial(value)
+                .ok_or_else(|| InterpreterError::DomainError(format!("factorial of {}", value))),
+            UnaryOpType::PERCENT => Ok(value / 100.),
+            UnaryOpType::BITNOT => math::to_integer(value)
+                .map(|value| !value as f64)
+                .ok_or_else(|| {
+                    InterpreterError::TypeError(format!(
+                        "{:?} expects an integer operand, found {}",
+                        self, value
+                    ))
+                }),
+        }
+    }
 }
 
 pub struct UnaryOpNode {
@@ -19,20 +168,21 @@ impl UnaryOpNode {
     }
 }
 
-impl ASTNode for UnaryOpNode {}
+impl ASTNode for UnaryOpNode {
+    fn kind(&self) -> NodeKind<'_> {
+        NodeKind::Unary(self.node.as_ref(), self.op_type)
+    }
+}
 
 impl ASTExpression for UnaryOpNode {
     fn pure(&self) -> bool {
         self.node.pure()
     }
 
-    fn eval(&self, symtab: &mut SymbolTable) -> Result<f64, InterpreterError> {
+    fn eval(&self, symtab: &mut SymbolTable) -> Result<Value, InterpreterError> {
         let value = self.node.eval(symtab)?;
 
-        match self.op_type {
-            UnaryOpType::PLUS => Ok(value),
-            UnaryOpType::MINUS => Ok(-value),
-        }
+        self.op_type.apply(&value)
     }
 }
 
@@ -50,7 +200,9 @@ impl ASTSemanticAnalysis for UnaryOpNode {
 mod tests {
     use crate::ast::tests::MockNode;
     use crate::error::InterpreterError;
+    use crate::math;
     use crate::symbol_table::SymbolTable;
+    use crate::value::{Complex, Dual, Value};
 
     use super::{ASTExpression, ASTSemanticAnalysis, UnaryOpNode, UnaryOpType};
 
@@ -78,6 +230,116 @@ mod tests {
         assert_eq!(result.unwrap(), -value);
     }
 
+    #[test]
+    fn test_eval_factorial() {
+        let mut symtab = SymbolTable::new();
+        let inner = MockNode::new().expect_eval(5.).expect_pure(true);
+        let node = UnaryOpNode::new(Box::new(inner), UnaryOpType::FACTORIAL);
+        let result = node.eval(&mut symtab);
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 120.);
+    }
+
+    #[test]
+    fn test_eval_factorial_exact() {
+        let mut symtab = SymbolTable::new();
+        let inner = MockNode::new().expect_eval(30.).expect_pure(true);
+        let node = UnaryOpNode::new(Box::new(inner), UnaryOpType::FACTORIAL);
+        let result = node.eval(&mut symtab);
+
+        assert!(result.is_ok());
+        assert_eq!(
+            result.unwrap().to_string(),
+            "265252859812191058636308480000000"
+        );
+    }
+
+    #[test]
+    fn test_eval_factorial_negative_integer() {
+        let mut symtab = SymbolTable::new();
+        let inner = MockNode::new().expect_eval(-2.).expect_pure(true);
+        let node = UnaryOpNode::new(Box::new(inner), UnaryOpType::FACTORIAL);
+        let result = node.eval(&mut symtab);
+
+        assert!(matches!(result, Err(InterpreterError::DomainError(_))));
+    }
+
+    #[test]
+    fn test_eval_complex() {
+        let mut symtab = SymbolTable::new();
+        let value = Value::Complex(Complex::new(3., 4.));
+        let inner = MockNode::new().expect_eval(value.clone()).expect_pure(true);
+        let node = UnaryOpNode::new(Box::new(inner), UnaryOpType::MINUS);
+        let result = node.eval(&mut symtab);
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), Value::Complex(Complex::new(-3., -4.)));
+
+        let inner = MockNode::new().expect_eval(value).expect_pure(true);
+        let node = UnaryOpNode::new(Box::new(inner), UnaryOpType::FACTORIAL);
+
+        assert!(matches!(
+            node.eval(&mut symtab),
+            Err(InterpreterError::TypeError(_))
+        ));
+    }
+
+    #[test]
+    fn test_eval_dual() {
+        let x = Value::Dual(Dual::variable(2., 0, 1));
+
+        assert_eq!(
+            UnaryOpType::MINUS.apply(&x).unwrap(),
+            Value::Dual(Dual::variable(2., 0, 1).scale(-1.))
+        );
+
+        match UnaryOpType::FACTORIAL.apply(&x).unwrap() {
+            Value::Dual(value) => {
+                assert_eq!(value.value(), 2.);
+                assert!((value.derivative(0) - 2. * math::digamma(3.)).abs() < 1e-12);
+            }
+            value => panic!("expected a dual number, found {}", value),
+        }
+
+        assert!(matches!(
+            UnaryOpType::BITNOT.apply(&x),
+            Err(InterpreterError::TypeError(_))
+        ));
+    }
+
+    #[test]
+    fn test_eval_percent() {
+        let mut symtab = SymbolTable::new();
+        let inner = MockNode::new().expect_eval(15.).expect_pure(true);
+        let node = UnaryOpNode::new(Box::new(inner), UnaryOpType::PERCENT);
+        let result = node.eval(&mut symtab);
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 0.15);
+    }
+
+    #[test]
+    fn test_eval_bitnot() {
+        let mut symtab = SymbolTable::new();
+        let inner = MockNode::new().expect_eval(5.).expect_pure(true);
+        let node = UnaryOpNode::new(Box::new(inner), UnaryOpType::BITNOT);
+        let result = node.eval(&mut symtab);
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), -6.);
+    }
+
+    #[test]
+    fn test_eval_bitnot_non_integer() {
+        let mut symtab = SymbolTable::new();
+        let inner = MockNode::new().expect_eval(0.5).expect_pure(true);
+        let node = UnaryOpNode::new(Box::new(inner), UnaryOpType::BITNOT);
+        let result = node.eval(&mut symtab);
+
+        assert!(matches!(result, Err(InterpreterError::TypeError(_))));
+    }
+
     #[test]
     fn test_pure() {
         let inner = MockNode::new().expect_pure(true);
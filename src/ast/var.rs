@@ -1,7 +1,8 @@
 use crate::error::InterpreterError;
 use crate::symbol_table::SymbolTable;
+use crate::value::Value;
 
-use super::{ASTExpression, ASTNode, ASTSemanticAnalysis};
+use super::{ASTExpression, ASTNode, ASTSemanticAnalysis, NodeKind};
 
 pub struct VarNode {
     name: String,
@@ -17,17 +18,21 @@ impl VarNode {
     }
 }
 
-impl ASTNode for VarNode {}
+impl ASTNode for VarNode {
+    fn kind(&self) -> NodeKind<'_> {
+        NodeKind::Var(&self.name)
+    }
+}
 
 impl ASTExpression for VarNode {
     fn pure(&self) -> bool {
         false
     }
 
-    fn eval(&self, symtab: &mut SymbolTable) -> Result<f64, InterpreterError> {
+    fn eval(&self, symtab: &mut SymbolTable) -> Result<Value, InterpreterError> {
         symtab
             .get(self.name())
-            .map(|value| *value)
+            .cloned()
             .ok_or(InterpreterError::UndefinedSymbol(self.name().clone()))
     }
 }
@@ -53,7 +58,7 @@ mod tests {
     fn test_eval() {
         let mut symtab = SymbolTable::new();
         let node = VarNode::new("x".to_string());
-    
+
         symtab.insert("x".to_string(), 25.);
 
         let result = node.eval(&mut symtab);
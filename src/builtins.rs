@@ -0,0 +1,1583 @@
+use std::f64::consts;
+
+use crate::ast::BinaryOpType;
+use crate::error::InterpreterError;
+use crate::math;
+use crate::matrix;
+use crate::symbol_table::SymbolTable;
+use crate::value::{
+    BigInt, Complex, Decimal, Dual, Function, Interval, Polynomial, Rational, RoundingMode, Value,
+};
+
+pub struct Builtin {
+    pub name: &'static str,
+    pub arity: usize,
+    /// Scalar builtins are mapped over list arguments.
+    pub elementwise: bool,
+    pub func: fn(&[Value]) -> Result<Value, InterpreterError>,
+}
+
+impl Builtin {
+    pub fn call(&self, args: &[Value]) -> Result<Value, InterpreterError> {
+        if let Some(function) = args.iter().find(|arg| matches!(arg, Value::Function(_))) {
+            return Err(InterpreterError::TypeError(format!(
+                "{} expects numeric arguments, found function {}",
+                self.name, function
+            )));
+        }
+
+        if let Some(polynomial) = args
+            .iter()
+            .find(|arg| self.elementwise && matches!(arg, Value::Polynomial(_)))
+        {
+            return Err(InterpreterError::TypeError(format!(
+                "{} expects numeric arguments, found polynomial {}",
+                self.name, polynomial
+            )));
+        }
+
+        let mut len = None;
+
+        for arg in args.iter() {
+            if let Value::List(values) = arg {
+                match len {
+                    Some(len) if len != values.len() => {
+                        return Err(InterpreterError::ShapeMismatch(
+                            format!("[{}]", len),
+                            format!("[{}]", values.len()),
+                        ))
+                    }
+                    _ => len = Some(values.len()),
+                }
+            }
+        }
+
+        match len {
+            Some(len) if self.elementwise => {
+                let mut values = vec![];
+
+                for i in 0..len {
+                    let row: Vec<Value> = args
+                        .iter()
+                        .map(|arg| match arg {
+                            Value::List(values) => values[i].clone(),
+                            value => value.clone(),
+                        })
+                        .collect();
+
+                    values.push(self.call(&row)?);
+                }
+
+                Ok(Value::List(values))
+            }
+            _ => (self.func)(args),
+        }
+    }
+}
+
+/// Built-in function that calls back into user functions passed as
+/// arguments, e.g. `deriv(f, 2)`, so it is given the symbol table.
+pub struct HigherOrderBuiltin {
+    pub name: &'static str,
+    pub arity: usize,
+    /// Number of trailing arguments that may be left out.
+    pub optional: usize,
+    pub func: fn(&[Value], &mut SymbolTable) -> Result<Value, InterpreterError>,
+}
+
+/// Matrices with more elements than this are an error rather than a
+/// calculation that seems to hang or runs out of memory.
+const MAX_ELEMENTS: usize = 10_000_000;
+
+static BUILTINS: [Builtin; 37] = [
+    Builtin {
+        name: "log",
+        arity: 2,
+        elementwise: true,
+        func: |args| {
+            expect_dimensionless("log", &args[0])?;
+            expect_dimensionless("log", &args[1])?;
+
+            match (&args[0], &args[1]) {
+                (Value::Complex(_), _) | (_, Value::Complex(_)) => complex_log(&args[0], &args[1]),
+                (Value::Interval(_) | Value::Uncertain(_) | Value::Dual(_), _)
+                | (_, Value::Interval(_) | Value::Uncertain(_) | Value::Dual(_)) => {
+                    let ln = lookup("ln").unwrap().func;
+
+                    BinaryOpType::DIV.apply(&ln(&args[0..1])?, &ln(&args[1..2])?)
+                }
+                _ if args[0].to_f64() < 0. || args[1].to_f64() < 0. => {
+                    complex_log(&args[0], &args[1])
+                }
+                _ => Ok(Value::Number(args[0].to_f64().log(args[1].to_f64()))),
+            }
+        },
+    },
+    Builtin {
+        name: "log2",
+        arity: 1,
+        elementwise: true,
+        func: |args| {
+            real_or_complex(
+                "log2",
+                &args[0],
+                |x| x >= 0.,
+                f64::log2,
+                |x| 1. / (x * consts::LN_2),
+                |z| z.ln().scale(1. / consts::LN_2),
+            )
+        },
+    },
+    Builtin {
+        name: "log10",
+        arity: 1,
+        elementwise: true,
+        func: |args| {
+            real_or_complex(
+                "log10",
+                &args[0],
+                |x| x >= 0.,
+                f64::log10,
+                |x| 1. / (x * consts::LN_10),
+                |z| z.ln().scale(1. / consts::LN_10),
+            )
+        },
+    },
+    Builtin {
+        name: "ln",
+        arity: 1,
+        elementwise: true,
+        func: |args| real_or_complex("ln", &args[0], |x| x >= 0., f64::ln, |x| 1. / x, |z| z.ln()),
+    },
+    Builtin {
+        name: "pow",
+        arity: 2,
+        elementwise: true,
+        func: |args| {
+            expect_dimensionless("pow", &args[0])?;
+            expect_dimensionless("pow", &args[1])?;
+
+            match (&args[0], &args[1]) {
+                (Value::Interval(_) | Value::Uncertain(_) | Value::Dual(_), _)
+                | (_, Value::Interval(_) | Value::Uncertain(_) | Value::Dual(_)) => {
+                    BinaryOpType::POW.apply(&args[0], &args[1])
+                }
+                (Value::Complex(_), _) | (_, Value::Complex(_)) => Ok(Value::from_complex(
+                    args[0].to_complex().pow(&args[1].to_complex()),
+                )),
+                _ if args[0].to_f64() < 0. && args[1].to_f64().fract() != 0. => Ok(
+                    Value::from_complex(args[0].to_complex().pow(&args[1].to_complex())),
+                ),
+                _ => Ok(Value::Number(args[0].to_f64().powf(args[1].to_f64()))),
+            }
+        },
+    },
+    Builtin {
+        name: "pow2",
+        arity: 1,
+        elementwise: true,
+        func: |args| {
+            real_or_complex(
+                "pow2",
+                &args[0],
+                |_| true,
+                f64::exp2,
+                |x| x.exp2() * consts::LN_2,
+                |z| z.scale(consts::LN_2).exp(),
+            )
+        },
+    },
+    Builtin {
+        name: "pow10",
+        arity: 1,
+        elementwise: true,
+        func: |args| {
+            real_or_complex(
+                "pow10",
+                &args[0],
+                |_| true,
+                |x| 10f64.powf(x),
+                |x| 10f64.powf(x) * consts::LN_10,
+                |z| z.scale(consts::LN_10).exp(),
+            )
+        },
+    },
+    Builtin {
+        name: "exp",
+        arity: 1,
+        elementwise: true,
+        func: |args| real_or_complex("exp", &args[0], |_| true, f64::exp, f64::exp, |z| z.exp()),
+    },
+    Builtin {
+        name: "sqrt",
+        arity: 1,
+        elementwise: true,
+        func: |args| match &args[0] {
+            Value::Interval(value) if value.lo() >= 0. => Ok(Value::Interval(value.sqrt())),
+            Value::Quantity(value) => value
+                .powf(0.5)
+                .map(Value::from_quantity)
+                .ok_or_else(|| InterpreterError::DomainError(format!("sqrt({})", args[0]))),
+            _ => real_or_complex(
+                "sqrt",
+                &args[0],
+                |x| x >= 0.,
+                f64::sqrt,
+                |x| 0.5 / x.sqrt(),
+                |z| z.sqrt(),
+            ),
+        },
+    },
+    Builtin {
+        name: "sin",
+        arity: 1,
+        elementwise: true,
+        func: |args| match &args[0] {
+            Value::Interval(value) => Ok(Value::Interval(value.sin())),
+            _ => real_or_complex("sin", &args[0], |_| true, f64::sin, f64::cos, |z| z.sin()),
+        },
+    },
+    Builtin {
+        name: "cos",
+        arity: 1,
+        elementwise: true,
+        func: |args| match &args[0] {
+            Value::Interval(value) => Ok(Value::Interval(value.cos())),
+            _ => real_or_complex(
+                "cos",
+                &args[0],
+                |_| true,
+                f64::cos,
+                |x| -x.sin(),
+                |z| z.cos(),
+            ),
+        },
+    },
+    Builtin {
+        name: "tan",
+        arity: 1,
+        elementwise: true,
+        func: |args| match &args[0] {
+            Value::Interval(value) => value
+                .tan()
+                .map(Value::Interval)
+                .ok_or_else(|| InterpreterError::DomainError(format!("tan({})", args[0]))),
+            _ => real_or_complex(
+                "tan",
+                &args[0],
+                |_| true,
+                f64::tan,
+                |x| 1. / x.cos().powi(2),
+                |z| z.tan(),
+            ),
+        },
+    },
+    Builtin {
+        name: "ceil",
+        arity: 1,
+        elementwise: true,
+        func: |args| {
+            match &args[0] {
+                Value::Interval(value) => return Ok(Value::Interval(value.map_exact(f64::ceil))),
+                Value::Dual(value) => return Ok(Value::Dual(Dual::constant(value.value().ceil()))),
+                _ => {}
+            }
+
+            expect_real("ceil", &args[0])?;
+
+            Ok(
+                round_exact(&args[0], Rational::ceil, Some(RoundingMode::CEILING))
+                    .unwrap_or_else(|| Value::Number(args[0].to_f64().ceil())),
+            )
+        },
+    },
+    Builtin {
+        name: "floor",
+        arity: 1,
+        elementwise: true,
+        func: |args| {
+            match &args[0] {
+                Value::Interval(value) => return Ok(Value::Interval(value.map_exact(f64::floor))),
+                Value::Dual(value) => {
+                    return Ok(Value::Dual(Dual::constant(value.value().floor())))
+                }
+                _ => {}
+            }
+
+            expect_real("floor", &args[0])?;
+
+            Ok(
+                round_exact(&args[0], Rational::floor, Some(RoundingMode::FLOOR))
+                    .unwrap_or_else(|| Value::Number(args[0].to_f64().floor())),
+            )
+        },
+    },
+    Builtin {
+        name: "round",
+        arity: 1,
+        elementwise: true,
+        func: |args| {
+            match &args[0] {
+                Value::Interval(value) => return Ok(Value::Interval(value.map_exact(f64::round))),
+                Value::Dual(value) => {
+                    return Ok(Value::Dual(Dual::constant(value.value().round())))
+                }
+                _ => {}
+            }
+
+            expect_real("round", &args[0])?;
+
+            Ok(round_exact(&args[0], Rational::round, None)
+                .unwrap_or_else(|| Value::Number(args[0].to_f64().round())))
+        },
+    },
+    Builtin {
+        name: "abs",
+        arity: 1,
+        elementwise: true,
+        func: |args| {
+            Ok(match &args[0] {
+                Value::Complex(z) => Value::Number(z.norm()),
+                Value::Interval(value) => Value::Interval(value.abs()),
+                Value::Uncertain(value) if value.value() < 0. => Value::Uncertain(value.scale(-1.)),
+                Value::Dual(value) if value.value() < 0. => Value::Dual(value.scale(-1.)),
+                Value::Quantity(value) if value.value() < 0. => Value::Quantity(value.neg()),
+                Value::Integer(value) => Value::Integer(value.abs()),
+                Value::Rational(value) if value.numerator().is_negative() => {
+                    Value::Rational(value.neg())
+                }
+                Value::Decimal(value) if value.to_f64() < 0. => Value::Decimal(value.neg()),
+                Value::Number(value) => Value::Number(value.abs()),
+                value => value.clone(),
+            })
+        },
+    },
+    Builtin {
+        name: "arg",
+        arity: 1,
+        elementwise: true,
+        func: |args| {
+            expect_dimensionless("arg", &args[0])?;
+            expect_point("arg", &args[0])?;
+
+            Ok(Value::Number(args[0].to_complex().arg()))
+        },
+    },
+    Builtin {
+        name: "conj",
+        arity: 1,
+        elementwise: true,
+        func: |args| {
+            Ok(match &args[0] {
+                Value::Complex(z) => Value::Complex(z.conj()),
+                value => value.clone(),
+            })
+        },
+    },
+    Builtin {
+        name: "polar",
+        arity: 2,
+        elementwise: true,
+        func: |args| {
+            expect_real("polar", &args[0])?;
+            expect_real("polar", &args[1])?;
+
+            Ok(Value::from_complex(Complex::from_polar(
+                args[0].to_f64(),
+                args[1].to_f64(),
+            )))
+        },
+    },
+    Builtin {
+        name: "len",
+        arity: 1,
+        elementwise: false,
+        func: |args| Ok(Value::Number(expect_list("len", &args[0])?.len() as f64)),
+    },
+    Builtin {
+        name: "sum",
+        arity: 1,
+        elementwise: false,
+        func: |args| sum(expect_list("sum", &args[0])?),
+    },
+    Builtin {
+        name: "prod",
+        arity: 1,
+        elementwise: false,
+        func: |args| {
+            fold(
+                BinaryOpType::MUL,
+                expect_list("prod", &args[0])?,
+                Value::Number(1.),
+            )
+        },
+    },
+    Builtin {
+        name: "mean",
+        arity: 1,
+        elementwise: false,
+        func: |args| {
+            let values = expect_list("mean", &args[0])?;
+
+            if values.is_empty() {
+                return Err(InterpreterError::DomainError("mean([])".to_string()));
+            }
+
+            let total = sum(values)?;
+            let count = BigInt::from_i64(values.len() as i64);
+            let count = match &total {
+                Value::Decimal(total) => {
+                    Value::Decimal(Decimal::from_bigint(count, total.context()))
+                }
+                Value::Rational(_) => Value::Rational(Rational::from_bigint(count)),
+                _ => Value::from_bigint(count),
+            };
+
+            BinaryOpType::DIV.apply(&total, &count)
+        },
+    },
+    Builtin {
+        name: "min",
+        arity: 1,
+        elementwise: false,
+        func: |args| extremum("min", &args[0], |ordering| ordering < 0., Interval::min),
+    },
+    Builtin {
+        name: "max",
+        arity: 1,
+        elementwise: false,
+        func: |args| extremum("max", &args[0], |ordering| ordering > 0., Interval::max),
+    },
+    Builtin {
+        name: "transpose",
+        arity: 1,
+        elementwise: false,
+        func: |args| {
+            Ok(matrix::from_matrix(matrix::transpose(&matrix::to_matrix(
+                "transpose",
+                &args[0],
+            )?)))
+        },
+    },
+    Builtin {
+        name: "det",
+        arity: 1,
+        elementwise: false,
+        func: |args| matrix::determinant(&matrix::to_square_matrix("det", &args[0])?),
+    },
+    Builtin {
+        name: "inv",
+        arity: 1,
+        elementwise: false,
+        func: |args| {
+            Ok(matrix::from_matrix(matrix::inverse(
+                &matrix::to_square_matrix("inv", &args[0])?,
+            )?))
+        },
+    },
+    Builtin {
+        name: "linsolve",
+        arity: 2,
+        elementwise: false,
+        func: |args| matrix::solve(&matrix::to_square_matrix("linsolve", &args[0])?, &args[1]),
+    },
+    Builtin {
+        name: "eig",
+        arity: 1,
+        elementwise: false,
+        func: |args| {
+            let values = matrix::eigenvalues(&matrix::to_square_matrix("eig", &args[0])?)?;
+
+            Ok(Value::List(values.into_iter().map(Value::Number).collect()))
+        },
+    },
+    Builtin {
+        name: "identity",
+        arity: 1,
+        elementwise: false,
+        func: |args| {
+            let (n, _) = expect_shape("identity", &args[0], &args[0])?;
+
+            Ok(matrix::from_matrix(matrix::identity(n)))
+        },
+    },
+    Builtin {
+        name: "zeros",
+        arity: 2,
+        elementwise: false,
+        func: |args| {
+            let (rows, columns) = expect_shape("zeros", &args[0], &args[1])?;
+
+            Ok(matrix::from_matrix(matrix::zeros(rows, columns)))
+        },
+    },
+    Builtin {
+        name: "coeffs",
+        arity: 1,
+        elementwise: false,
+        func: |args| {
+            let polynomial = expect_polynomial("coeffs", &args[0])?;
+            let mut coefficients: Vec<Value> = polynomial
+                .coefficients()
+                .iter()
+                .rev()
+                .map(|c| Value::Number(*c))
+                .collect();
+
+            if coefficients.is_empty() {
+                coefficients.push(Value::Number(0.));
+            }
+
+            Ok(Value::List(coefficients))
+        },
+    },
+    Builtin {
+        name: "degree",
+        arity: 1,
+        elementwise: false,
+        func: |args| {
+            Ok(Value::Number(
+                expect_polynomial("degree", &args[0])?.degree() as f64,
+            ))
+        },
+    },
+    Builtin {
+        name: "roots",
+        arity: 1,
+        elementwise: false,
+        func: |args| {
+            let roots = expect_polynomial("roots", &args[0])?.roots();
+
+            Ok(Value::List(
+                roots.into_iter().map(Value::from_complex).collect(),
+            ))
+        },
+    },
+    Builtin {
+        name: "polyder",
+        arity: 1,
+        elementwise: false,
+        func: |args| {
+            Ok(Value::Polynomial(
+                expect_polynomial("polyder", &args[0])?.derivative(),
+            ))
+        },
+    },
+    Builtin {
+        name: "polygcd",
+        arity: 2,
+        elementwise: false,
+        func: |args| {
+            let p = expect_polynomial("polygcd", &args[0])?;
+            let q = expect_polynomial("polygcd", &args[1])?;
+
+            Ok(Value::Polynomial(p.gcd(&q)?))
+        },
+    },
+];
+
+static HIGHER_ORDER_BUILTINS: [HigherOrderBuiltin; 7] = [
+    HigherOrderBuiltin {
+        name: "deriv",
+        arity: 2,
+        optional: 0,
+        func: |args, symtab| {
+            let x = Value::Dual(Dual::variable(expect_real_number("deriv", &args[1])?, 0, 1));
+            let y = call_function("deriv", &args[0], &[x], symtab)?;
+
+            Ok(Value::Number(y.derivative(0)))
+        },
+    },
+    HigherOrderBuiltin {
+        name: "grad",
+        arity: 2,
+        optional: 0,
+        func: |args, symtab| {
+            let point = expect_list("grad", &args[1])?;
+            let mut xs = vec![];
+
+            for (i, coordinate) in point.iter().enumerate() {
+                let x = expect_real_number("grad", coordinate)?;
+
+                xs.push(Value::Dual(Dual::variable(x, i, point.len())));
+            }
+
+            let y = call_function("grad", &args[0], &xs, symtab)?;
+
+            Ok(Value::List(
+                (0..point.len())
+                    .map(|i| Value::Number(y.derivative(i)))
+                    .collect(),
+            ))
+        },
+    },
+    HigherOrderBuiltin {
+        name: "integrate",
+        arity: 4,
+        optional: 1,
+        func: |args, symtab| {
+            let (a, b) = expect_bounds("integrate", &args[1], &args[2])?;
+            let tolerance = expect_tolerance("integrate", args.get(3))?;
+
+            let result = math::integrate(
+                |x| call_real("integrate", &args[0], x, symtab),
+                a,
+                b,
+                tolerance,
+            )?;
+
+            if !result.converged {
+                return Err(InterpreterError::ConvergenceFailure(format!(
+                    "integrate({}, {}, {}) has error estimate {:e} above tolerance {:e}",
+                    args[0], a, b, result.error, tolerance
+                )));
+            }
+
+            Ok(Value::Number(result.value))
+        },
+    },
+    HigherOrderBuiltin {
+        name: "root",
+        arity: 4,
+        optional: 1,
+        func: |args, symtab| {
+            let (a, b) = expect_bounds("root", &args[1], &args[2])?;
+            let tolerance = expect_tolerance("root", args.get(3))?;
+            let root = math::brent(|x| call_real("root", &args[0], x, symtab), a, b, tolerance)?;
+
+            root.map(Value::Number).ok_or_else(|| {
+                InterpreterError::DomainError(format!(
+                    "{} does not change sign over [{}, {}]",
+                    args[0], a, b
+                ))
+            })
+        },
+    },
+    HigherOrderBuiltin {
+        name: "map",
+        arity: 2,
+        optional: 0,
+        func: |args, symtab| {
+            let function = expect_function("map", &args[0])?;
+
+            expect_list("map", &args[1])?
+                .iter()
+                .map(|value| function.call(std::slice::from_ref(value), symtab))
+                .collect::<Result<Vec<Value>, InterpreterError>>()
+                .map(Value::List)
+        },
+    },
+    HigherOrderBuiltin {
+        name: "filter",
+        arity: 2,
+        optional: 0,
+        func: |args, symtab| {
+            let function = expect_function("filter", &args[0])?;
+            let mut values = vec![];
+
+            for value in expect_list("filter", &args[1])?.iter() {
+                let keep = function.call(std::slice::from_ref(value), symtab)?;
+
+                if expect_real_number("filter", &keep)? != 0. {
+                    values.push(value.clone());
+                }
+            }
+
+            Ok(Value::List(values))
+        },
+    },
+    HigherOrderBuiltin {
+        name: "reduce",
+        arity: 3,
+        optional: 1,
+        func: |args, symtab| {
+            let function = expect_function("reduce", &args[0])?;
+            let mut values = expect_list("reduce", &args[1])?.iter();
+            let mut result = match args.get(2).or_else(|| values.next()) {
+                Some(initial) => initial.clone(),
+                None => {
+                    return Err(InterpreterError::DomainError(
+                        "reduce of an empty list without an initial value".to_string(),
+                    ))
+                }
+            };
+
+            for value in values {
+                result = function.call(&[result, value.clone()], symtab)?;
+            }
+
+            Ok(result)
+        },
+    },
+];
+
+/// Finite bounds of an interval for numeric methods.
+fn expect_bounds(name: &str, a: &Value, b: &Value) -> Result<(f64, f64), InterpreterError> {
+    let (a, b) = (expect_real_number(name, a)?, expect_real_number(name, b)?);
+
+    if !a.is_finite() || !b.is_finite() {
+        return Err(InterpreterError::DomainError(format!(
+            "{} over [{}, {}] is not bounded",
+            name, a, b
+        )));
+    }
+
+    Ok((a, b))
+}
+
+/// Optional tolerance argument of numeric methods, which must be positive.
+fn expect_tolerance(name: &str, tolerance: Option<&Value>) -> Result<f64, InterpreterError> {
+    let tolerance = match tolerance {
+        Some(tolerance) => expect_real_number(name, tolerance)?,
+        None => return Ok(math::DEFAULT_TOLERANCE),
+    };
+
+    if tolerance.is_nan() || tolerance <= 0. {
+        return Err(InterpreterError::DomainError(format!(
+            "{} with tolerance {}",
+            name, tolerance
+        )));
+    }
+
+    Ok(tolerance)
+}
+
+/// Calls the user function `function` on dual numbers, the result of a
+/// function that does not depend on its arguments is a constant.
+fn call_function(
+    name: &str,
+    function: &Value,
+    args: &[Value],
+    symtab: &mut SymbolTable,
+) -> Result<Dual, InterpreterError> {
+    let function = expect_function(name, function)?;
+    let result = function.call(args, symtab)?;
+
+    result.to_dual().ok_or_else(|| {
+        InterpreterError::TypeError(format!(
+            "{} expects a real-valued function, {} returned {}",
+            name, function, result
+        ))
+    })
+}
+
+/// Value of a function at `x` for the numeric methods, which cannot carry
+/// the derivatives of a dual number, say from a variable it captured.
+fn call_real(
+    name: &str,
+    function: &Value,
+    x: f64,
+    symtab: &mut SymbolTable,
+) -> Result<f64, InterpreterError> {
+    let y = expect_function(name, function)?.call(&[Value::Number(x)], symtab)?;
+
+    expect_point(name, &y)?;
+    expect_real_number(name, &y)
+}
+
+fn expect_function<'a>(name: &str, value: &'a Value) -> Result<&'a Function, InterpreterError> {
+    match value {
+        Value::Function(function) => Ok(function),
+        _ => Err(InterpreterError::TypeError(format!(
+            "{} expects a function, found {}",
+            name, value
+        ))),
+    }
+}
+
+pub(crate) fn expect_real_number(name: &str, value: &Value) -> Result<f64, InterpreterError> {
+    expect_real(name, value)?;
+
+    match value {
+        Value::List(_) | Value::Function(_) | Value::Polynomial(_) => Err(
+            InterpreterError::TypeError(format!("{} expects a real number, found {}", name, value)),
+        ),
+        _ => Ok(value.to_f64()),
+    }
+}
+
+fn expect_size(name: &str, value: &Value) -> Result<usize, InterpreterError> {
+    value
+        .to_bigint()
+        .and_then(|value| value.to_i64())
+        .and_then(|value| usize::try_from(value).ok())
+        .ok_or_else(|| {
+            InterpreterError::TypeError(format!(
+                "{} expects a non-negative integer size, found {}",
+                name, value
+            ))
+        })
+}
+
+fn expect_shape(
+    name: &str,
+    rows: &Value,
+    columns: &Value,
+) -> Result<(usize, usize), InterpreterError> {
+    let (rows, columns) = (expect_size(name, rows)?, expect_size(name, columns)?);
+
+    if rows.saturating_mul(columns) > MAX_ELEMENTS {
+        return Err(InterpreterError::DomainError(format!(
+            "{} of more than {} elements",
+            name, MAX_ELEMENTS
+        )));
+    }
+
+    Ok((rows, columns))
+}
+
+fn expect_list<'a>(name: &str, value: &'a Value) -> Result<&'a [Value], InterpreterError> {
+    match value {
+        Value::List(values) => Ok(values),
+        _ => Err(InterpreterError::TypeError(format!(
+            "{} expects a list, found {}",
+            name, value
+        ))),
+    }
+}
+
+/// The argument as a polynomial, a real number is a constant polynomial.
+fn expect_polynomial(name: &str, value: &Value) -> Result<Polynomial, InterpreterError> {
+    match value {
+        Value::Polynomial(value) => Ok(value.clone()),
+        Value::Number(_) | Value::Decimal(_) | Value::Rational(_) | Value::Integer(_) => {
+            Ok(Polynomial::constant(value.to_f64(), "x".to_string()))
+        }
+        _ => Err(InterpreterError::TypeError(format!(
+            "{} expects a polynomial, found {}",
+            name, value
+        ))),
+    }
+}
+
+/// Sum of a list, starting from its first element so that quantities keep
+/// their unit and exact numbers stay exact. The empty sum is 0.
+fn sum(values: &[Value]) -> Result<Value, InterpreterError> {
+    fold(BinaryOpType::ADD, values, Value::Number(0.))
+}
+
+fn fold(op_type: BinaryOpType, values: &[Value], empty: Value) -> Result<Value, InterpreterError> {
+    let Some((first, rest)) = values.split_first() else {
+        return Ok(empty);
+    };
+    let mut result = first.clone();
+
+    for value in rest {
+        result = op_type.apply(&result, value)?;
+    }
+
+    Ok(result)
+}
+
+/// Keeps the element for which `prefer(candidate - current)` holds, values
+/// are compared by subtraction so units and exact numbers are respected.
+/// Intervals overlap, so they are combined bound by bound with `bounds`.
+fn extremum(
+    name: &str,
+    list: &Value,
+    prefer: fn(f64) -> bool,
+    bounds: fn(&Interval, &Interval) -> Interval,
+) -> Result<Value, InterpreterError> {
+    let values = expect_list(name, list)?;
+    let mut result: Option<Value> = None;
+
+    for value in values.iter() {
+        if let Value::Complex(_) | Value::List(_) = value {
+            return Err(InterpreterError::TypeError(format!(
+                "{} expects real elements, found {}",
+                name, value
+            )));
+        }
+
+        result = match result {
+            None => Some(value.clone()),
+            Some(current)
+                if matches!(value, Value::Interval(_)) || matches!(current, Value::Interval(_)) =>
+            {
+                match (current.to_interval(), value.to_interval()) {
+                    (Some(current), Some(value)) => Some(Value::Interval(bounds(&current, &value))),
+                    _ => {
+                        return Err(InterpreterError::TypeError(format!(
+                            "{} cannot compare {} and {}",
+                            name, current, value
+                        )))
+                    }
+                }
+            }
+            Some(current) => {
+                let ordering = match BinaryOpType::SUB.apply(value, &current)? {
+                    Value::Quantity(difference) => difference.value(),
+                    Value::Uncertain(difference) => difference.value(),
+                    Value::Dual(difference) => difference.value(),
+                    difference => difference.to_f64(),
+                };
+
+                Some(if prefer(ordering) {
+                    value.clone()
+                } else {
+                    current
+                })
+            }
+        };
+    }
+
+    result.ok_or_else(|| InterpreterError::DomainError(format!("{}([])", name)))
+}
+
+/// Applies `real` to real arguments within its domain, and extends it to the
+/// complex plane with `complex` otherwise. Dual numbers are carried through
+/// with the exact `derivative` of `real`.
+fn real_or_complex(
+    name: &str,
+    value: &Value,
+    domain: fn(f64) -> bool,
+    real: fn(f64) -> f64,
+    derivative: fn(f64) -> f64,
+    complex: fn(Complex) -> Complex,
+) -> Result<Value, InterpreterError> {
+    match value {
+        Value::Complex(z) => Ok(Value::from_complex(complex(*z))),
+        Value::Quantity(_) => expect_dimensionless(name, value).map(|_| value.clone()),
+        Value::Interval(x) if domain(x.lo()) => Ok(Value::Interval(x.map(real))),
+        Value::Interval(_) => Err(InterpreterError::DomainError(format!(
+            "{}({}) is not real",
+            name, value
+        ))),
+        Value::Uncertain(x) if domain(x.value()) => {
+            x.map(real).map(Value::Uncertain).ok_or_else(|| {
+                InterpreterError::DomainError(format!("{}({}) is not differentiable", name, value))
+            })
+        }
+        Value::Uncertain(_) => Err(InterpreterError::DomainError(format!(
+            "{}({}) is not real",
+            name, value
+        ))),
+        Value::Dual(x) if domain(x.value()) => {
+            Ok(Value::Dual(x.chain(real(x.value()), derivative(x.value()))))
+        }
+        Value::Dual(x) => Err(InterpreterError::DomainError(format!(
+            "{}({}) is not real",
+            name,
+            x.value()
+        ))),
+        _ if domain(value.to_f64()) => Ok(Value::Number(real(value.to_f64()))),
+        _ => Ok(Value::from_complex(complex(value.to_complex()))),
+    }
+}
+
+fn complex_log(value: &Value, base: &Value) -> Result<Value, InterpreterError> {
+    value
+        .to_complex()
+        .ln()
+        .div(&base.to_complex().ln())
+        .map(Value::from_complex)
+        .ok_or_else(|| InterpreterError::DomainError("logarithm base 1".to_string()))
+}
+
+fn expect_real(name: &str, value: &Value) -> Result<(), InterpreterError> {
+    match value {
+        Value::Complex(_) => Err(InterpreterError::TypeError(format!(
+            "{} expects a real argument, found {}",
+            name, value
+        ))),
+        _ => {
+            expect_dimensionless(name, value)?;
+            expect_point(name, value)
+        }
+    }
+}
+
+fn expect_point(name: &str, value: &Value) -> Result<(), InterpreterError> {
+    match value {
+        Value::Interval(_) => Err(InterpreterError::TypeError(format!(
+            "{} is not defined for intervals, found {}",
+            name, value
+        ))),
+        Value::Uncertain(_) => Err(InterpreterError::TypeError(format!(
+            "{} is not defined for measurements, found {}",
+            name, value
+        ))),
+        Value::Dual(_) => Err(InterpreterError::TypeError(format!(
+            "{} is not differentiable",
+            name
+        ))),
+        _ => Ok(()),
+    }
+}
+
+fn expect_dimensionless(name: &str, value: &Value) -> Result<(), InterpreterError> {
+    match value {
+        Value::Quantity(_) => Err(InterpreterError::TypeError(format!(
+            "{} expects a dimensionless argument, found {}",
+            name, value
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Rounds exact values to an integer without going through `f64`. Decimals
+/// use the rounding mode of their context unless `rounding` is given.
+fn round_exact(
+    value: &Value,
+    round_rational: fn(&Rational) -> BigInt,
+    rounding: Option<RoundingMode>,
+) -> Option<Value> {
+    match value {
+        Value::Rational(value) => Some(Value::Rational(Rational::from_bigint(round_rational(
+            value,
+        )))),
+        Value::Decimal(value) => Some(Value::Decimal(
+            value.round_to(0, rounding.unwrap_or(value.context().rounding)),
+        )),
+        Value::Integer(_) => Some(value.clone()),
+        _ => None,
+    }
+}
+
+pub fn lookup(name: &str) -> Option<&'static Builtin> {
+    BUILTINS.iter().find(|builtin| builtin.name == name)
+}
+
+pub fn lookup_higher_order(name: &str) -> Option<&'static HigherOrderBuiltin> {
+    HIGHER_ORDER_BUILTINS
+        .iter()
+        .find(|builtin| builtin.name == name)
+}
+
+/// Calls with their own syntax, whose arguments are not all evaluated.
+const SPECIAL_FORMS: [&str; 4] = ["diff", "poly", "simplify", "solve"];
+
+/// Whether `name` is reserved for a built-in function of any kind.
+pub fn is_builtin(name: &str) -> bool {
+    lookup(name).is_some() || lookup_higher_order(name).is_some() || SPECIAL_FORMS.contains(&name)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::f64::consts;
+    use std::rc::Rc;
+
+    use crate::ast::{BinaryOpNode, BinaryOpType, CallNode, NumberNode, VarNode};
+    use crate::error::InterpreterError;
+    use crate::symbol_table::SymbolTable;
+    use crate::value::{
+        BigInt, Complex, DecimalContext, Dimension, Dual, Function, Interval, NumberMode,
+        Polynomial, Quantity, Rational, Uncertain, Unit, Value,
+    };
+
+    use super::{lookup, lookup_higher_order};
+
+    #[test]
+    fn test_lookup() {
+        assert!(lookup("sqrt").is_some());
+        assert!(lookup("log").is_some());
+        assert!(lookup("x").is_none());
+    }
+
+    #[test]
+    fn test_call() {
+        let sqrt = lookup("sqrt").unwrap();
+        let log = lookup("log").unwrap();
+
+        assert_eq!(sqrt.arity, 1);
+        assert_eq!((sqrt.func)(&[Value::Number(16.)]).unwrap(), 4.);
+        assert_eq!(log.arity, 2);
+        assert_eq!(
+            (log.func)(&[Value::Number(8.), Value::Number(2.)]).unwrap(),
+            3.
+        );
+    }
+
+    #[test]
+    fn test_call_exact() {
+        let floor = lookup("floor").unwrap();
+        let sqrt = lookup("sqrt").unwrap();
+        let value = NumberMode::RATIONAL.literal(-2.5);
+
+        assert_eq!(
+            (floor.func)(&[value]).unwrap(),
+            NumberMode::RATIONAL.literal(-3.)
+        );
+        assert_eq!(
+            (sqrt.func)(&[NumberMode::RATIONAL.literal(2.25)]).unwrap(),
+            1.5
+        );
+    }
+
+    #[test]
+    fn test_call_complex() {
+        let call = |name: &str, args: &[Value]| (lookup(name).unwrap().func)(args).unwrap();
+        let z = Value::Complex(Complex::new(3., 4.));
+
+        assert_eq!(
+            call("sqrt", &[Value::Number(-1.)]),
+            Value::Complex(Complex::new(0., 1.))
+        );
+        assert_eq!(call("abs", std::slice::from_ref(&z)), 5.);
+        assert_eq!(call("arg", &[Value::Number(-1.)]), std::f64::consts::PI);
+        assert_eq!(
+            call("conj", std::slice::from_ref(&z)),
+            Value::Complex(Complex::new(3., -4.))
+        );
+        assert_eq!(call("polar", &[Value::Number(2.), Value::Number(0.)]), 2.);
+        assert_eq!(
+            call("ln", &[Value::Number(-1.)]),
+            Value::Complex(Complex::new(0., std::f64::consts::PI))
+        );
+        assert_eq!(
+            call("exp", &[Value::Complex(Complex::new(0., consts::PI))]),
+            Value::Complex(Complex::new(-1., consts::PI.sin()))
+        );
+        assert_eq!(
+            call("sin", &[Value::Complex(Complex::new(0., 1.))]),
+            Value::Complex(Complex::new(0., 1f64.sinh()))
+        );
+        assert_eq!(call("cos", &[Value::Number(0.)]), 1.);
+        assert!((lookup("floor").unwrap().func)(&[z]).is_err());
+    }
+
+    #[test]
+    fn test_call_list() {
+        let call = |name: &str, args: &[Value]| lookup(name).unwrap().call(args);
+        let list = |values: &[f64]| Value::List(values.iter().map(|x| Value::Number(*x)).collect());
+
+        assert_eq!(call("sqrt", &[list(&[4., 9.])]).unwrap(), list(&[2., 3.]));
+        assert_eq!(
+            call("log", &[list(&[8., 9.]), list(&[2., 3.])]).unwrap(),
+            list(&[3., 2.])
+        );
+        assert!(matches!(
+            call("log", &[list(&[8., 9.]), list(&[2.])]),
+            Err(InterpreterError::ShapeMismatch(_, _))
+        ));
+        assert_eq!(call("len", &[list(&[1., 2., 3.])]).unwrap(), 3.);
+        assert_eq!(call("sum", &[list(&[1., 2., 3.])]).unwrap(), 6.);
+        assert_eq!(call("sum", &[list(&[])]).unwrap(), 0.);
+        assert_eq!(call("prod", &[list(&[2., 3., 4.])]).unwrap(), 24.);
+        assert_eq!(call("mean", &[list(&[1., 2., 3., 4.])]).unwrap(), 2.5);
+
+        // The sum starts from the first element, keeping units and exact numbers
+        let metre = Unit::new("m", 1., Dimension([1, 0, 0, 0, 0, 0, 0]));
+        let meters = |value: f64| Value::Quantity(Quantity::new(value, metre.clone()));
+        let third = |n: i64| {
+            Value::Rational(Rational::new(BigInt::from_i64(n), BigInt::from_i64(3)).unwrap())
+        };
+
+        assert_eq!(
+            call("sum", &[Value::List(vec![meters(1.), meters(2.)])]).unwrap(),
+            meters(3.)
+        );
+        assert_eq!(
+            call("mean", &[Value::List(vec![meters(1.), meters(2.)])]).unwrap(),
+            meters(1.5)
+        );
+        assert_eq!(
+            call("sum", &[Value::List(vec![third(1), third(1)])]).unwrap(),
+            third(2)
+        );
+        assert_eq!(
+            call("mean", &[Value::List(vec![third(1), third(2)])]).unwrap(),
+            Value::Rational(Rational::new(BigInt::from_i64(1), BigInt::from_i64(2)).unwrap())
+        );
+        assert_eq!(
+            call("prod", &[Value::List(vec![third(1), third(2)])]).unwrap(),
+            Value::Rational(Rational::new(BigInt::from_i64(2), BigInt::from_i64(9)).unwrap())
+        );
+        assert_eq!(call("min", &[list(&[3., -2., 5.])]).unwrap(), -2.);
+        assert_eq!(call("max", &[list(&[3., -2., 5.])]).unwrap(), 5.);
+        assert!(matches!(
+            call("max", &[list(&[])]),
+            Err(InterpreterError::DomainError(_))
+        ));
+        assert!(matches!(
+            call("len", &[Value::Number(1.)]),
+            Err(InterpreterError::TypeError(_))
+        ));
+    }
+
+    #[test]
+    fn test_call_interval() {
+        let call = |name: &str, args: &[Value]| lookup(name).unwrap().call(args);
+        let interval = |lo: f64, hi: f64| Value::Interval(Interval::new(lo, hi).unwrap());
+
+        assert_eq!(call("sqrt", &[interval(4., 9.)]).unwrap(), interval(2., 3.));
+        assert_eq!(call("abs", &[interval(-3., 2.)]).unwrap(), interval(0., 3.));
+        assert_eq!(
+            call("floor", &[interval(1.5, 2.5)]).unwrap(),
+            interval(1., 2.)
+        );
+        assert_eq!(
+            call(
+                "max",
+                &[Value::List(vec![interval(1., 3.), interval(2., 4.)])]
+            )
+            .unwrap(),
+            interval(2., 4.)
+        );
+        assert!(matches!(
+            call("ln", &[interval(-1., 1.)]),
+            Err(InterpreterError::DomainError(_))
+        ));
+
+        assert_eq!(
+            call("cos", &[interval(-1., 7.)]).unwrap(),
+            interval(-1., 1.)
+        );
+        assert!(matches!(
+            call("tan", &[interval(1., 2.)]),
+            Err(InterpreterError::DomainError(_))
+        ));
+
+        if let Value::Interval(value) = call("ln", &[interval(1., 2.)]).unwrap() {
+            assert!(value.lo() <= 0. && value.hi() >= std::f64::consts::LN_2);
+        } else {
+            panic!("ln of an interval is an interval");
+        }
+    }
+
+    #[test]
+    fn test_call_uncertain() {
+        let call = |name: &str, args: &[Value]| lookup(name).unwrap().call(args);
+        let x = Value::Uncertain(Uncertain::new(4., 0.2));
+
+        assert_eq!(
+            call("sqrt", std::slice::from_ref(&x)).unwrap().to_string(),
+            "2.00 ± 0.05"
+        );
+        assert_eq!(
+            call("pow", &[x.clone(), Value::Number(2.)])
+                .unwrap()
+                .to_string(),
+            "16.0 ± 1.6"
+        );
+        assert_eq!(
+            call("sin", &[Value::Uncertain(Uncertain::new(0., 0.1))])
+                .unwrap()
+                .to_string(),
+            "0.00 ± 0.10"
+        );
+        assert!(matches!(
+            call("sqrt", &[Value::Uncertain(Uncertain::new(0., 0.1))]),
+            Err(InterpreterError::DomainError(_))
+        ));
+        assert!(matches!(
+            call("round", &[x]),
+            Err(InterpreterError::TypeError(_))
+        ));
+    }
+
+    #[test]
+    fn test_call_matrix() {
+        let call = |name: &str, args: &[Value]| lookup(name).unwrap().call(args);
+        let list = |values: &[f64]| Value::List(values.iter().map(|x| Value::Number(*x)).collect());
+        let a = Value::List(vec![list(&[1., 2.]), list(&[3., 4.])]);
+
+        assert_eq!(call("det", std::slice::from_ref(&a)).unwrap(), -2.);
+        assert_eq!(
+            call("transpose", std::slice::from_ref(&a)).unwrap(),
+            Value::List(vec![list(&[1., 3.]), list(&[2., 4.])])
+        );
+        assert_eq!(
+            call("identity", &[Value::Number(2.)]).unwrap(),
+            Value::List(vec![list(&[1., 0.]), list(&[0., 1.])])
+        );
+        assert_eq!(
+            call("zeros", &[Value::Number(1.), Value::Number(2.)]).unwrap(),
+            Value::List(vec![list(&[0., 0.])])
+        );
+        assert_eq!(
+            call("linsolve", &[a.clone(), list(&[5., 11.])]).unwrap(),
+            list(&[1., 2.])
+        );
+        assert!(matches!(
+            call("det", &[Value::List(vec![list(&[1., 2.])])]),
+            Err(InterpreterError::TypeError(_))
+        ));
+        assert!(matches!(
+            call("identity", &[Value::Number(-1.)]),
+            Err(InterpreterError::TypeError(_))
+        ));
+        assert!(matches!(
+            call("identity", &[Value::Number(1e5)]),
+            Err(InterpreterError::DomainError(_))
+        ));
+        assert!(matches!(
+            call("zeros", &[Value::Number(1e4), Value::Number(1e4)]),
+            Err(InterpreterError::DomainError(_))
+        ));
+
+        for mode in [
+            NumberMode::DECIMAL(DecimalContext::default()),
+            NumberMode::RATIONAL,
+        ] {
+            assert_eq!(
+                call("identity", &[mode.literal(2.)]).unwrap(),
+                Value::List(vec![list(&[1., 0.]), list(&[0., 1.])])
+            );
+            assert_eq!(
+                call("zeros", &[mode.literal(1.), mode.literal(2.)]).unwrap(),
+                Value::List(vec![list(&[0., 0.])])
+            );
+        }
+    }
+
+    #[test]
+    fn test_call_polynomial() {
+        let call = |name: &str, args: &[Value]| lookup(name).unwrap().call(args);
+        let list = |values: &[f64]| Value::List(values.iter().map(|x| Value::Number(*x)).collect());
+        let poly = |coefficients: &[f64]| {
+            Value::Polynomial(Polynomial::new(coefficients.to_vec(), "x".to_string()))
+        };
+        // (x - 1)(x - 2) and (x - 1)(x + 1)
+        let p = poly(&[2., -3., 1.]);
+        let q = poly(&[-1., 0., 1.]);
+
+        assert_eq!(
+            call("coeffs", std::slice::from_ref(&p)).unwrap(),
+            list(&[1., -3., 2.])
+        );
+        assert_eq!(call("coeffs", &[poly(&[])]).unwrap(), list(&[0.]));
+        assert_eq!(call("degree", std::slice::from_ref(&p)).unwrap(), 2.);
+        assert_eq!(call("degree", &[Value::Number(5.)]).unwrap(), 0.);
+        assert_eq!(
+            call("polyder", std::slice::from_ref(&p)).unwrap(),
+            poly(&[-3., 2.])
+        );
+        assert_eq!(
+            call("polygcd", &[p.clone(), q.clone()]).unwrap(),
+            poly(&[-1., 1.])
+        );
+
+        match call("roots", std::slice::from_ref(&q)).unwrap() {
+            Value::List(roots) => {
+                assert!((roots[0].to_f64() + 1.).abs() < 1e-12);
+                assert!((roots[1].to_f64() - 1.).abs() < 1e-12);
+            }
+            roots => panic!("expected a list of roots, found {}", roots),
+        }
+
+        assert_eq!(
+            call("roots", &[poly(&[1., 0., 1.])]).unwrap(),
+            Value::List(vec![
+                Value::Complex(Complex::new(0., -1.)),
+                Value::Complex(Complex::new(0., 1.))
+            ])
+        );
+        assert!(matches!(
+            call("sqrt", &[p]),
+            Err(InterpreterError::TypeError(_))
+        ));
+        assert!(matches!(
+            call("degree", &[list(&[1.])]),
+            Err(InterpreterError::TypeError(_))
+        ));
+    }
+
+    #[test]
+    fn test_call_deriv() {
+        let mut symtab = SymbolTable::new();
+        let mut call = |name: &str, args: &[Value]| {
+            (lookup_higher_order(name).unwrap().func)(args, &mut symtab)
+        };
+        let var = |name: &str| Box::new(VarNode::new(name.to_string()));
+        // f(x, y) = x^2 * y
+        let f = Value::Function(Function::new(
+            "f".to_string(),
+            vec!["x".to_string(), "y".to_string()],
+            Rc::new(BinaryOpNode::new(
+                Box::new(BinaryOpNode::new(
+                    var("x"),
+                    Box::new(NumberNode::new(Value::Number(2.))),
+                    BinaryOpType::POW,
+                )),
+                var("y"),
+                BinaryOpType::MUL,
+            )),
+        ));
+        // g(x) = ln(x)
+        let g = Value::Function(Function::new(
+            "g".to_string(),
+            vec!["x".to_string()],
+            Rc::new(CallNode::new("ln".to_string(), vec![var("x")])),
+        ));
+
+        assert_eq!(
+            call(
+                "grad",
+                &[f.clone(), Value::List(vec![3f64.into(), 2f64.into()])]
+            )
+            .unwrap(),
+            Value::List(vec![12f64.into(), 9f64.into()])
+        );
+        assert_eq!(call("deriv", &[g.clone(), 4f64.into()]).unwrap(), 0.25);
+        assert!(matches!(
+            call("deriv", &[g, (-1f64).into()]),
+            Err(InterpreterError::DomainError(_))
+        ));
+        assert!(matches!(
+            call("deriv", &[f, 1f64.into()]),
+            Err(InterpreterError::ArgumentCountMismatch(_, 2, 1))
+        ));
+        assert!(matches!(
+            call("deriv", &[1f64.into(), 1f64.into()]),
+            Err(InterpreterError::TypeError(_))
+        ));
+    }
+
+    #[test]
+    fn test_call_integrate() {
+        let mut symtab = SymbolTable::new();
+        let mut call =
+            |args: &[Value]| (lookup_higher_order("integrate").unwrap().func)(args, &mut symtab);
+        let var = || Box::new(VarNode::new("x".to_string()));
+        // f(x) = x^2
+        let f = Value::Function(Function::new(
+            "f".to_string(),
+            vec!["x".to_string()],
+            Rc::new(BinaryOpNode::new(
+                var(),
+                Box::new(NumberNode::new(Value::Number(2.))),
+                BinaryOpType::POW,
+            )),
+        ));
+        // g(x) = 1 / x
+        let g = Value::Function(Function::new(
+            "g".to_string(),
+            vec!["x".to_string()],
+            Rc::new(BinaryOpNode::new(
+                Box::new(NumberNode::new(Value::Number(1.))),
+                var(),
+                BinaryOpType::DIV,
+            )),
+        ));
+
+        let result = call(&[f.clone(), 0f64.into(), 3f64.into()]).unwrap();
+        assert!((result.to_f64() - 9.).abs() < 1e-12);
+
+        let result = call(&[g.clone(), 1f64.into(), consts::E.into(), 1e-6.into()]).unwrap();
+        assert!((result.to_f64() - 1.).abs() < 1e-6);
+
+        assert!(matches!(
+            call(&[g, (-1f64).into(), 1f64.into()]),
+            Err(InterpreterError::ConvergenceFailure(_))
+        ));
+        assert!(matches!(
+            call(&[f.clone(), 0f64.into(), 1f64.into(), 0f64.into()]),
+            Err(InterpreterError::DomainError(_))
+        ));
+        assert!(matches!(
+            call(&[f, 0f64.into(), f64::INFINITY.into()]),
+            Err(InterpreterError::DomainError(_))
+        ));
+        assert!(matches!(
+            call(&[1f64.into(), 0f64.into(), 1f64.into()]),
+            Err(InterpreterError::TypeError(_))
+        ));
+
+        // t -> t * x with x a dual number, as in deriv(x -> integrate(...), 2)
+        let h = Value::Function(Function::closure(
+            vec!["t".to_string()],
+            Rc::new(BinaryOpNode::new(
+                Box::new(VarNode::new("t".to_string())),
+                var(),
+                BinaryOpType::MUL,
+            )),
+            HashMap::from([("x".to_string(), Value::Dual(Dual::variable(2., 0, 1)))]),
+        ));
+
+        assert!(matches!(
+            call(&[h, 0f64.into(), 1f64.into()]),
+            Err(InterpreterError::TypeError(_))
+        ));
+    }
+
+    #[test]
+    fn test_call_root() {
+        let mut symtab = SymbolTable::new();
+        let mut call =
+            |args: &[Value]| (lookup_higher_order("root").unwrap().func)(args, &mut symtab);
+        // f(x) = x^2 - 2
+        let f = Value::Function(Function::new(
+            "f".to_string(),
+            vec!["x".to_string()],
+            Rc::new(BinaryOpNode::new(
+                Box::new(BinaryOpNode::new(
+                    Box::new(VarNode::new("x".to_string())),
+                    Box::new(NumberNode::new(Value::Number(2.))),
+                    BinaryOpType::POW,
+                )),
+                Box::new(NumberNode::new(Value::Number(2.))),
+                BinaryOpType::SUB,
+            )),
+        ));
+
+        let x = call(&[f.clone(), 0f64.into(), 2f64.into()])
+            .unwrap()
+            .to_f64();
+        assert!((x - consts::SQRT_2).abs() < 1e-10);
+
+        let x = call(&[f.clone(), 0f64.into(), 2f64.into(), 1e-3.into()])
+            .unwrap()
+            .to_f64();
+        assert!((x - consts::SQRT_2).abs() < 1e-3);
+
+        assert!(matches!(
+            call(&[f, 2f64.into(), 3f64.into()]),
+            Err(InterpreterError::DomainError(_))
+        ));
+    }
+
+    #[test]
+    fn test_call_map_filter_reduce() {
+        let mut symtab = SymbolTable::new();
+        let mut call = |name: &str, args: &[Value]| {
+            (lookup_higher_order(name).unwrap().func)(args, &mut symtab)
+        };
+        let list = |values: &[f64]| Value::List(values.iter().map(|x| Value::Number(*x)).collect());
+        let var = |name: &str| Box::new(VarNode::new(name.to_string()));
+        // x -> x % 2
+        let odd = Value::Function(Function::anonymous(
+            vec!["x".to_string()],
+            Rc::new(BinaryOpNode::new(
+                var("x"),
+                Box::new(NumberNode::new(Value::Number(2.))),
+                BinaryOpType::MOD,
+            )),
+        ));
+        // (a, b) -> a * b
+        let mul = Value::Function(Function::anonymous(
+            vec!["a".to_string(), "b".to_string()],
+            Rc::new(BinaryOpNode::new(var("a"), var("b"), BinaryOpType::MUL)),
+        ));
+
+        assert_eq!(
+            call("map", &[odd.clone(), list(&[1., 2., 3.])]).unwrap(),
+            list(&[1., 0., 1.])
+        );
+        assert_eq!(
+            call("filter", &[odd.clone(), list(&[1., 2., 3.])]).unwrap(),
+            list(&[1., 3.])
+        );
+        assert_eq!(
+            call("reduce", &[mul.clone(), list(&[2., 3., 4.])]).unwrap(),
+            24.
+        );
+        assert_eq!(
+            call("reduce", &[mul.clone(), list(&[]), Value::Number(1.)]).unwrap(),
+            1.
+        );
+        assert!(matches!(
+            call("reduce", &[mul.clone(), list(&[])]),
+            Err(InterpreterError::DomainError(_))
+        ));
+        assert!(matches!(
+            call("map", &[mul, list(&[1.])]),
+            Err(InterpreterError::ArgumentCountMismatch(_, 2, 1))
+        ));
+        assert!(matches!(
+            call("filter", &[Value::Number(1.), list(&[1.])]),
+            Err(InterpreterError::TypeError(_))
+        ));
+    }
+}
@@ -8,7 +8,19 @@ pub enum InterpreterError {
     InvalidSyntax(String),
     UnexpectedToken(Token),
     UndefinedSymbol(String),
+    UndefinedFunction(String),
     RedefineBuiltinSymbol(String),
+    ReassignConstant(String),
+    ArgumentCountMismatch(String, usize, usize),
+    AmbiguousSyntax(String),
+    DomainError(String),
+    TypeError(String),
+    UndefinedUnit(String),
+    IncompatibleUnits(String, String),
+    ShapeMismatch(String, String),
+    IndexOutOfRange(i64, usize),
+    StackOverflow(usize),
+    ConvergenceFailure(String),
     EOF,
 }
 
@@ -26,9 +38,49 @@ impl fmt::Display for InterpreterError {
             InterpreterError::UndefinedSymbol(symbol) => {
                 write!(f, "Undefined symbol: {}", symbol)
             }
+            InterpreterError::UndefinedFunction(name) => {
+                write!(f, "Undefined function: {}", name)
+            }
             InterpreterError::RedefineBuiltinSymbol(symbol) => {
                 write!(f, "Redefine builtin symbol: {}", symbol)
             }
+            InterpreterError::ReassignConstant(symbol) => {
+                write!(f, "Reassign constant: {}", symbol)
+            }
+            InterpreterError::ArgumentCountMismatch(name, expected, found) => {
+                write!(
+                    f,
+                    "Function {} expects {} arguments, found {}",
+                    name, expected, found
+                )
+            }
+            InterpreterError::AmbiguousSyntax(syntax) => {
+                write!(f, "Ambiguous syntax: {}", syntax)
+            }
+            InterpreterError::DomainError(expression) => {
+                write!(f, "Math domain error: {}", expression)
+            }
+            InterpreterError::TypeError(message) => {
+                write!(f, "Type error: {}", message)
+            }
+            InterpreterError::UndefinedUnit(unit) => {
+                write!(f, "Undefined unit: {}", unit)
+            }
+            InterpreterError::IncompatibleUnits(left, right) => {
+                write!(f, "Incompatible units: {} and {}", left, right)
+            }
+            InterpreterError::ShapeMismatch(left, right) => {
+                write!(f, "Shape mismatch: {} and {}", left, right)
+            }
+            InterpreterError::IndexOutOfRange(index, len) => {
+                write!(f, "Index {} out of range for length {}", index, len)
+            }
+            InterpreterError::StackOverflow(depth) => {
+                write!(f, "Stack overflow: more than {} nested calls", depth)
+            }
+            InterpreterError::ConvergenceFailure(message) => {
+                write!(f, "No convergence: {}", message)
+            }
             InterpreterError::EOF => {
                 write!(f, "End of file.")
             }
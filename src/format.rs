@@ -0,0 +1,706 @@
+use crate::ast::{
+    ASTSemanticExpression, ASTSemanticStatement, BinaryOpType, CompareOpType, NodeKind,
+    UnaryOpType, UnitExpr,
+};
+use crate::units;
+use crate::value::{BigInt, Rational, Value, MAX_EXACT_INTEGER};
+
+/// Binding strength of each level of the grammar, from `expr` to `atom`. A
+/// subexpression is parenthesized when it binds more loosely than the
+/// position it appears in allows.
+pub(crate) const LAMBDA: u8 = 0;
+pub(crate) const COMPARE: u8 = 1;
+pub(crate) const CONVERT: u8 = 2;
+pub(crate) const BITOR: u8 = 3;
+pub(crate) const XOR: u8 = 4;
+pub(crate) const BITAND: u8 = 5;
+pub(crate) const SHIFT: u8 = 6;
+pub(crate) const ARITH: u8 = 7;
+pub(crate) const TERM: u8 = 8;
+pub(crate) const FACTOR: u8 = 9;
+pub(crate) const POWER: u8 = 10;
+pub(crate) const POSTFIX: u8 = 11;
+pub(crate) const ATOM: u8 = 12;
+
+/// Formats a statement back into source text, e.g. `a, b = b, a + b`.
+/// Parsing the text gives the same tree again.
+pub fn statement(node: &dyn ASTSemanticStatement) -> String {
+    match node.kind() {
+        NodeKind::Assign(vars, expressions, op_type, constant) => {
+            let vars: Vec<&str> = vars.iter().map(|var| var.name().as_str()).collect();
+            let expressions: Vec<String> = expressions
+                .iter()
+                .map(|node| expression(node.as_ref()))
+                .collect();
+            let assign = match op_type {
+                Some(op_type) => format!("{}=", binary(op_type).0),
+                None => "=".to_string(),
+            };
+
+            format!(
+                "{}{} {} {}",
+                if constant { "const " } else { "" },
+                vars.join(", "),
+                assign,
+                expressions.join(", ")
+            )
+        }
+        NodeKind::Function(name, params, body, memo) => format!(
+            "{}{}({}) = {}",
+            if memo { "memo " } else { "" },
+            name,
+            params.join(", "),
+            expression(body)
+        ),
+        NodeKind::StatementList(nodes) => {
+            let nodes: Vec<String> = nodes.iter().map(|node| statement(node.as_ref())).collect();
+
+            nodes.join("; ")
+        }
+        _ => "…".to_string(),
+    }
+}
+
+/// Formats an expression back into source text with as few parentheses as
+/// the precedence of its operators allows, e.g. `2 * x^(n - 1)`.
+pub fn expression(node: &dyn ASTSemanticExpression) -> String {
+    format(node).0
+}
+
+fn format(node: &dyn ASTSemanticExpression) -> (String, u8) {
+    match node.kind() {
+        NodeKind::Number(value) => number(value),
+        NodeKind::Var(name) => (name.clone(), ATOM),
+        NodeKind::Unary(operand, op_type) => match op_type {
+            UnaryOpType::FACTORIAL => (format!("{}!", operand_at(operand, POSTFIX)), POSTFIX),
+            UnaryOpType::PERCENT => (format!("{}%", operand_at(operand, POSTFIX)), POSTFIX),
+            UnaryOpType::PLUS => (format!("+{}", operand_at(operand, FACTOR)), FACTOR),
+            UnaryOpType::MINUS => (format!("-{}", operand_at(operand, FACTOR)), FACTOR),
+            UnaryOpType::BITNOT => (format!("~{}", operand_at(operand, FACTOR)), FACTOR),
+        },
+        NodeKind::Binary(left, right, BinaryOpType::POW) => {
+            // A unit would take the exponent, `5 km^2` is a quantity.
+            let base = match left.kind() {
+                NodeKind::Quantity(..) => format!("({})", expression(left)),
+                _ => operand_at(left, POSTFIX),
+            };
+
+            (format!("{}^{}", base, operand_at(right, FACTOR)), POWER)
+        }
+        NodeKind::Binary(left, right, op_type) => {
+            let (symbol, precedence) = binary(op_type);
+            let mut left_text = operand_at(left, precedence);
+            let mut right_text = operand_at(right, precedence + 1);
+
+            // A unit would continue into `5 km * h`, and in calculator mode
+            // `x + 10%` is a percentage change.
+            if matches!(op_type, BinaryOpType::MUL | BinaryOpType::DIV)
+                && ends_with_unit(left)
+                && !left_text.ends_with(')')
+                && starts_with_unit(&right_text)
+            {
+                left_text = format!("({})", left_text);
+            }
+
+            if precedence == ARITH
+                && matches!(right.kind(), NodeKind::Unary(_, UnaryOpType::PERCENT))
+            {
+                right_text = format!("({})", right_text);
+            }
+
+            // `7 % -2` would be `7%` minus 2.
+            if op_type == BinaryOpType::MOD && right_text.starts_with(['+', '-', '~']) {
+                right_text = format!("({})", right_text);
+            }
+
+            (
+                format!("{} {} {}", left_text, symbol, right_text),
+                precedence,
+            )
+        }
+        NodeKind::PercentChange(base, percent, op_type) => {
+            let (symbol, precedence) = binary(op_type);
+
+            (
+                format!(
+                    "{} {} {}",
+                    operand_at(base, precedence),
+                    symbol,
+                    operand_at(percent, precedence + 1)
+                ),
+                precedence,
+            )
+        }
+        NodeKind::Call(name, args) => (format!("{}({})", name, list(args)), ATOM),
+        NodeKind::List(nodes) => (format!("[{}]", matrix(nodes)), ATOM),
+        NodeKind::Index(node, index) => {
+            let mut text = operand_at(node, POSTFIX);
+
+            // `%[` would be the modulo operator.
+            if text.ends_with('%') {
+                text = format!("({})", text);
+            }
+
+            (format!("{}[{}]", text, expression(index)), POSTFIX)
+        }
+        NodeKind::Interval(lo, hi) => (format!("[{} .. {}]", expression(lo), expression(hi)), ATOM),
+        NodeKind::Quantity(node, unit_expr) => {
+            (format!("{} {}", expression(node), unit(unit_expr)), ATOM)
+        }
+        NodeKind::Convert(node, unit_expr) => (
+            format!("{} to {}", operand_at(node, CONVERT), unit(unit_expr)),
+            CONVERT,
+        ),
+        NodeKind::Diff(node, var) => (format!("diff({}, {})", expression(node), var), ATOM),
+        NodeKind::Simplify(node) => (format!("simplify({})", expression(node)), ATOM),
+        NodeKind::Lambda(params, body) => (lambda(params, body), LAMBDA),
+        NodeKind::Compare(left, right, op_type) => (
+            format!(
+                "{} {} {}",
+                operand_at(left, CONVERT),
+                compare(op_type),
+                operand_at(right, CONVERT)
+            ),
+            COMPARE,
+        ),
+        NodeKind::Conditional(condition, then_branch, else_branch) => (
+            format!(
+                "if {} then {} else {}",
+                expression(condition),
+                expression(then_branch),
+                expression(else_branch)
+            ),
+            LAMBDA,
+        ),
+        NodeKind::Poly(node, Some(var)) => (format!("poly({}, {})", expression(node), var), ATOM),
+        NodeKind::Poly(node, None) => (format!("poly({})", expression(node)), ATOM),
+        NodeKind::Series(op_type, index, lo, hi, body) => (
+            format!(
+                "{}({}, {}, {}, {})",
+                if op_type == BinaryOpType::MUL {
+                    "prod"
+                } else {
+                    "sum"
+                },
+                index,
+                expression(lo),
+                expression(hi),
+                expression(body)
+            ),
+            ATOM,
+        ),
+        NodeKind::Solve(lhs, rhs, var, bounds) => {
+            let equation = format!("{} = {}", expression(lhs), expression(rhs));
+
+            (
+                format!("solve({}, {}{})", equation, var, bounds_list(bounds)),
+                ATOM,
+            )
+        }
+        _ => ("…".to_string(), ATOM),
+    }
+}
+
+/// Anonymous function, `x -> body` or `(a, b) -> body`.
+pub(crate) fn lambda(params: &[String], body: &dyn ASTSemanticExpression) -> String {
+    match params {
+        [param] => format!("{} -> {}", param, expression(body)),
+        params => format!("({}) -> {}", params.join(", "), expression(body)),
+    }
+}
+
+fn operand_at(node: &dyn ASTSemanticExpression, precedence: u8) -> String {
+    match format(node) {
+        (text, inner) if inner < precedence => format!("({})", text),
+        (text, _) => text,
+    }
+}
+
+fn list(nodes: &[Box<dyn ASTSemanticExpression>]) -> String {
+    let nodes: Vec<String> = nodes.iter().map(|node| expression(node.as_ref())).collect();
+
+    nodes.join(", ")
+}
+
+/// Trailing arguments after a comma, such as the bounds of `solve`.
+fn bounds_list(nodes: &[Box<dyn ASTSemanticExpression>]) -> String {
+    nodes
+        .iter()
+        .map(|node| format!(", {}", expression(node.as_ref())))
+        .collect()
+}
+
+/// Rows of a list that is a matrix of more than one row.
+pub(crate) fn matrix_rows(
+    nodes: &[Box<dyn ASTSemanticExpression>],
+) -> Option<Vec<&[Box<dyn ASTSemanticExpression>]>> {
+    let rows: Vec<&[Box<dyn ASTSemanticExpression>]> = nodes
+        .iter()
+        .map(|node| match node.kind() {
+            NodeKind::List(row) if !row.is_empty() => Some(row),
+            _ => None,
+        })
+        .collect::<Option<_>>()?;
+
+    (rows.len() > 1 && rows.iter().all(|row| row.len() == rows[0].len())).then_some(rows)
+}
+
+/// Elements of a list, with rows separated by `;` when it is a matrix.
+fn matrix(nodes: &[Box<dyn ASTSemanticExpression>]) -> String {
+    match matrix_rows(nodes) {
+        Some(rows) => {
+            let rows: Vec<String> = rows.into_iter().map(list).collect();
+
+            rows.join("; ")
+        }
+        None => list(nodes),
+    }
+}
+
+/// Whether the text of a node ends with a unit that a following `*` or `/`
+/// would extend.
+fn ends_with_unit(node: &dyn ASTSemanticExpression) -> bool {
+    match node.kind() {
+        NodeKind::Quantity(..) => true,
+        NodeKind::Unary(operand, UnaryOpType::PLUS | UnaryOpType::MINUS | UnaryOpType::BITNOT) => {
+            ends_with_unit(operand)
+        }
+        NodeKind::Binary(_, right, _) => ends_with_unit(right),
+        _ => false,
+    }
+}
+
+fn starts_with_unit(text: &str) -> bool {
+    let name: String = text
+        .chars()
+        .take_while(|ch| ch.is_ascii_alphanumeric() || *ch == '_')
+        .collect();
+
+    units::lookup(&name).is_some()
+}
+
+fn unit(unit: &UnitExpr) -> String {
+    unit.iter()
+        .enumerate()
+        .map(|(i, (name, exponent))| {
+            let (separator, exponent) = match (i, *exponent) {
+                (0, exponent) => ("", exponent),
+                (_, exponent) if exponent < 0 => ("/", -exponent),
+                (_, exponent) => ("*", exponent),
+            };
+
+            match exponent {
+                1 => format!("{}{}", separator, name),
+                exponent => format!("{}{}^{}", separator, name, exponent),
+            }
+        })
+        .collect()
+}
+
+/// Literals keep their exact form, a fraction binds like a division.
+pub(crate) fn number(value: &Value) -> (String, u8) {
+    let text = match value {
+        // Without a fraction, the literal would be a big integer.
+        Value::Number(value) if value.fract() == 0. && value.abs() > MAX_EXACT_INTEGER => {
+            format!("{}.0", value)
+        }
+        Value::Rational(value) => decimal(value).unwrap_or_else(|| value.to_string()),
+        value => value.to_string(),
+    };
+    let precedence = match value {
+        Value::Rational(_) if text.contains('/') => TERM,
+        Value::Complex(value) if value.re != 0. => ARITH,
+        _ if text.starts_with('-') => FACTOR,
+        _ => ATOM,
+    };
+
+    (text, precedence)
+}
+
+/// Digits of a fraction whose denominator divides a power of ten, as the
+/// literals in rational mode do, e.g. `0.125`.
+fn decimal(value: &Rational) -> Option<String> {
+    let digits = (0..=64).find(|digits| {
+        BigInt::pow10(*digits)
+            .div_rem(value.denominator())
+            .is_some_and(|(_, remainder)| remainder.is_zero())
+    })?;
+    let (scaled, _) = value
+        .numerator()
+        .mul(&BigInt::pow10(digits))
+        .div_rem(value.denominator())?;
+
+    if digits == 0 {
+        return Some(scaled.to_string());
+    }
+
+    let text = format!("{:0>width$}", scaled.abs().to_string(), width = digits + 1);
+    let (integer, fraction) = text.split_at(text.len() - digits);
+    let sign = if scaled.is_negative() { "-" } else { "" };
+
+    Some(format!("{}{}.{}", sign, integer, fraction))
+}
+
+/// Letters that variable names such as `theta` stand for in mathematical
+/// notation.
+pub(crate) const GREEK_LETTERS: [(&str, char); 34] = [
+    ("alpha", 'α'),
+    ("beta", 'β'),
+    ("gamma", 'γ'),
+    ("delta", 'δ'),
+    ("epsilon", 'ε'),
+    ("zeta", 'ζ'),
+    ("eta", 'η'),
+    ("theta", 'θ'),
+    ("iota", 'ι'),
+    ("kappa", 'κ'),
+    ("lambda", 'λ'),
+    ("mu", 'μ'),
+    ("nu", 'ν'),
+    ("xi", 'ξ'),
+    ("pi", 'π'),
+    ("rho", 'ρ'),
+    ("sigma", 'σ'),
+    ("tau", 'τ'),
+    ("upsilon", 'υ'),
+    ("phi", 'φ'),
+    ("chi", 'χ'),
+    ("psi", 'ψ'),
+    ("omega", 'ω'),
+    ("Gamma", 'Γ'),
+    ("Delta", 'Δ'),
+    ("Theta", 'Θ'),
+    ("Lambda", 'Λ'),
+    ("Xi", 'Ξ'),
+    ("Pi", 'Π'),
+    ("Sigma", 'Σ'),
+    ("Upsilon", 'Υ'),
+    ("Phi", 'Φ'),
+    ("Psi", 'Ψ'),
+    ("Omega", 'Ω'),
+];
+
+pub(crate) fn precedence(op_type: BinaryOpType) -> u8 {
+    binary(op_type).1
+}
+
+fn compare(op_type: CompareOpType) -> &'static str {
+    match op_type {
+        CompareOpType::LT => "<",
+        CompareOpType::LE => "<=",
+        CompareOpType::GT => ">",
+        CompareOpType::GE => ">=",
+        CompareOpType::EQ => "==",
+        CompareOpType::NE => "!=",
+    }
+}
+
+fn binary(op_type: BinaryOpType) -> (&'static str, u8) {
+    match op_type {
+        BinaryOpType::ADD => ("+", ARITH),
+        BinaryOpType::SUB => ("-", ARITH),
+        BinaryOpType::PLUSMINUS => ("±", ARITH),
+        BinaryOpType::MUL => ("*", TERM),
+        BinaryOpType::MATMUL => ("@", TERM),
+        BinaryOpType::DIV => ("/", TERM),
+        BinaryOpType::FLOORDIV => ("//", TERM),
+        BinaryOpType::MOD => ("%", TERM),
+        BinaryOpType::POW => ("^", POWER),
+        BinaryOpType::BITAND => ("&", BITAND),
+        BinaryOpType::BITOR => ("|", BITOR),
+        BinaryOpType::XOR => ("xor", XOR),
+        BinaryOpType::SHL => ("<<", SHIFT),
+        BinaryOpType::SHR => (">>", SHIFT),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::tests::var;
+    use crate::ast::{
+        ASTSemanticExpression, ASTSemanticStatement, BinaryOpNode, BinaryOpType, NodeKind,
+        NumberNode, UnaryOpNode, UnaryOpType,
+    };
+
+    use crate::parser::Parser;
+    use crate::tokenizer::Tokenizer;
+    use crate::value::NumberMode;
+
+    use super::{expression, statement};
+
+    fn binary(
+        left: Box<dyn ASTSemanticExpression>,
+        right: Box<dyn ASTSemanticExpression>,
+        op_type: BinaryOpType,
+    ) -> Box<dyn ASTSemanticExpression> {
+        Box::new(BinaryOpNode::new(left, right, op_type))
+    }
+
+    #[test]
+    fn test_precedence() {
+        // (a + b) * c - (d - e)
+        let node = binary(
+            binary(
+                binary(var("a"), var("b"), BinaryOpType::ADD),
+                var("c"),
+                BinaryOpType::MUL,
+            ),
+            binary(var("d"), var("e"), BinaryOpType::SUB),
+            BinaryOpType::SUB,
+        );
+
+        assert_eq!(expression(node.as_ref()), "(a + b) * c - (d - e)");
+    }
+
+    #[test]
+    fn test_power() {
+        // (-x)^2^n
+        let node = binary(
+            Box::new(UnaryOpNode::new(var("x"), UnaryOpType::MINUS)),
+            binary(Box::new(NumberNode::new(2.)), var("n"), BinaryOpType::POW),
+            BinaryOpType::POW,
+        );
+
+        assert_eq!(expression(node.as_ref()), "(-x)^2^n");
+
+        let node = binary(
+            binary(var("x"), var("y"), BinaryOpType::POW),
+            Box::new(NumberNode::new(-1.)),
+            BinaryOpType::POW,
+        );
+
+        assert_eq!(expression(node.as_ref()), "(x^y)^-1");
+    }
+
+    /// Structure of a tree with every node spelled out, to compare trees.
+    fn tree(node: &dyn ASTSemanticExpression) -> String {
+        let trees = |nodes: &[Box<dyn ASTSemanticExpression>]| {
+            nodes
+                .iter()
+                .map(|node| tree(node.as_ref()))
+                .collect::<Vec<String>>()
+                .join(" ")
+        };
+
+        match node.kind() {
+            NodeKind::Number(value) => format!("{:?}", value),
+            NodeKind::Var(name) => name.clone(),
+            NodeKind::Unary(operand, op_type) => format!("({:?} {})", op_type, tree(operand)),
+            NodeKind::Binary(left, right, op_type) => {
+                format!("({:?} {} {})", op_type, tree(left), tree(right))
+            }
+            NodeKind::PercentChange(base, percent, op_type) => {
+                format!("(%{:?} {} {})", op_type, tree(base), tree(percent))
+            }
+            NodeKind::Call(name, args) => format!("({} {})", name, trees(args)),
+            NodeKind::List(nodes) => format!("[{}]", trees(nodes)),
+            NodeKind::Index(node, index) => format!("(index {} {})", tree(node), tree(index)),
+            NodeKind::Interval(lo, hi) => format!("(.. {} {})", tree(lo), tree(hi)),
+            NodeKind::Quantity(node, unit) => format!("({:?} {})", unit, tree(node)),
+            NodeKind::Convert(node, unit) => format!("(to {:?} {})", unit, tree(node)),
+            NodeKind::Diff(node, var) => format!("(diff {} {})", tree(node), var),
+            NodeKind::Simplify(node) => format!("(simplify {})", tree(node)),
+            NodeKind::Lambda(params, body) => {
+                format!("(-> ({}) {})", params.join(" "), tree(body))
+            }
+            NodeKind::Compare(left, right, op_type) => {
+                format!("({:?} {} {})", op_type, tree(left), tree(right))
+            }
+            NodeKind::Conditional(condition, then_branch, else_branch) => format!(
+                "(if {} {} {})",
+                tree(condition),
+                tree(then_branch),
+                tree(else_branch)
+            ),
+            NodeKind::Poly(node, Some(var)) => format!("(poly {} {})", tree(node), var),
+            NodeKind::Poly(node, None) => format!("(poly {})", tree(node)),
+            NodeKind::Series(op_type, index, lo, hi, body) => format!(
+                "({:?} {} {} {} {})",
+                op_type,
+                index,
+                tree(lo),
+                tree(hi),
+                tree(body)
+            ),
+            NodeKind::Solve(lhs, rhs, var, bounds) => format!(
+                "(solve (= {} {}) {} {})",
+                tree(lhs),
+                tree(rhs),
+                var,
+                trees(bounds)
+            ),
+            _ => "?".to_string(),
+        }
+    }
+
+    fn statement_tree(node: &dyn ASTSemanticStatement) -> String {
+        match node.kind() {
+            NodeKind::Assign(vars, expressions, op_type, constant) => {
+                let vars: Vec<&String> = vars.iter().map(|var| var.name()).collect();
+                let expressions: Vec<String> =
+                    expressions.iter().map(|node| tree(node.as_ref())).collect();
+
+                format!(
+                    "({:?} {:?} {:?} {:?})",
+                    constant, vars, op_type, expressions
+                )
+            }
+            NodeKind::Function(name, params, body, memo) => {
+                format!("(define {} {} {:?} {})", memo, name, params, tree(body))
+            }
+            NodeKind::StatementList(nodes) => nodes
+                .iter()
+                .map(|node| statement_tree(node.as_ref()))
+                .collect::<Vec<String>>()
+                .join("; "),
+            _ => "?".to_string(),
+        }
+    }
+
+    fn parse(content: &str, calculator_mode: bool, number_mode: NumberMode) -> String {
+        let tokens = Tokenizer::new(content).try_collect().unwrap();
+        let node = Parser::new(tokens)
+            .implicit_multiplication(true)
+            .calculator_mode(calculator_mode)
+            .number_mode(number_mode)
+            .parse()
+            .unwrap();
+        let text = statement(node.as_ref());
+        let tokens = Tokenizer::new(&text).try_collect().unwrap();
+        let reparsed = Parser::new(tokens)
+            .implicit_multiplication(true)
+            .calculator_mode(calculator_mode)
+            .number_mode(number_mode)
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            statement_tree(reparsed.as_ref()),
+            statement_tree(node.as_ref()),
+            "{} is formatted as {}",
+            content,
+            text
+        );
+
+        text
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let cases = [
+            ("x=1+2*(3-4)/5", "x = 1 + 2 * (3 - 4) / 5"),
+            ("x = 2x^2(a+b)", "x = 2 * x^2 * (a + b)"),
+            ("x = a - (b - c) - d", "x = a - (b - c) - d"),
+            ("x = -(a^b)^c! + (-a)!", "x = -(a^b)^c! + (-a)!"),
+            ("x = 2^-x^2 + 2^(-x)^2", "x = 2^-x^2 + 2^(-x)^2"),
+            ("x = ~a << 1 xor b | c & d", "x = ~a << 1 xor b | c & d"),
+            ("x = (a | b) & c", "x = (a | b) & c"),
+            ("const g = 9.81; f(x) = g x", "const g = 9.81; f(x) = g * x"),
+            ("a, b = b, a + b", "a, b = b, a + b"),
+            ("a ^= 1 + 1", "a ^= 1 + 1"),
+            ("m = [1, 2; 3, 4][0][1]", "m = [1, 2; 3, 4][0][1]"),
+            ("m = [[1, 2]] @ [[1], [2]]", "m = [[1, 2]] @ [1; 2]"),
+            (
+                "v = [9.5 .. 10.5] + [] + [x]",
+                "v = [9.5 .. 10.5] + [] + [x]",
+            ),
+            ("v = 12.3 ± 0.2", "v = 12.3 ± 0.2"),
+            (
+                "d = diff(x^2, x)(3) + simplify(x + x)",
+                "d = diff(x^2, x) * 3 + simplify(x + x)",
+            ),
+            (
+                "r = solve(x^2=2, x) + solve(2^x = 3x, x, 0, a + 1)",
+                "r = solve(x^2 = 2, x) + solve(2^x = 3 * x, x, 0, a + 1)",
+            ),
+            (
+                "p = poly([1, -3, 2]) + poly((x-1)^2, x)",
+                "p = poly([1, -3, 2]) + poly((x - 1)^2, x)",
+            ),
+            (
+                "s = sum(k, 1, n, k^2) * prod(k,1,3,1 + r/k) + sum([1, 2])",
+                "s = sum(k, 1, n, k^2) * prod(k, 1, 3, 1 + r / k) + sum([1, 2])",
+            ),
+            (
+                "f = map(x->x^2+1, [1, 2]) + reduce((a,b) -> a * b, v) + (() -> 1)",
+                "f = map(x -> x^2 + 1, [1, 2]) + reduce((a, b) -> a * b, v) + (() -> 1)",
+            ),
+            ("g = x -> y -> x + y to m", "g = x -> y -> x + y to m"),
+            (
+                "memo fib(n) = if n<2 then n else fib(n-1)+fib(n-2)",
+                "memo fib(n) = if n < 2 then n else fib(n - 1) + fib(n - 2)",
+            ),
+            (
+                "s = (if a then 1 else 2) * 3 + (a == b) - (x < 1 m to cm)",
+                "s = (if a then 1 else 2) * 3 + (a == b) - (x < 1 m to cm)",
+            ),
+            (
+                "t = if a <= b then if c then 1 else 2 else x -> -x != 0",
+                "t = if a <= b then if c then 1 else 2 else x -> -x != 0",
+            ),
+            ("z = 3 + 4i + 2.5j", "z = 3 + 4i + 2.5i"),
+            (
+                "n = 100000000000000000000 + 100000000000000000000.0",
+                "n = 100000000000000000000 + 100000000000000000000.0",
+            ),
+        ];
+
+        for (content, expected) in cases {
+            assert_eq!(parse(content, false, NumberMode::FLOAT), expected);
+        }
+    }
+
+    #[test]
+    fn test_round_trip_units() {
+        let cases = [
+            ("v = 60 mph to km/h", "v = 60 mph to km/h"),
+            ("a = 9.81 m/s^2 * 2 kg", "a = 9.81 m/s^2 * 2 kg"),
+            ("a = 1 s^-1 * (5 km) * h", "a = (1 s^-1 * 5 km) * h"),
+            ("a = (5 km)^2 + (1 m to cm)", "a = (5 km)^2 + (1 m to cm)"),
+        ];
+
+        for (content, expected) in cases {
+            assert_eq!(parse(content, false, NumberMode::FLOAT), expected);
+        }
+    }
+
+    #[test]
+    fn test_round_trip_percent() {
+        assert_eq!(
+            parse("p = x + 10% - 5%", true, NumberMode::FLOAT),
+            "p = x + 10% - 5%"
+        );
+        assert_eq!(
+            parse("p = x + (10%) + 2^10% + y * 10%", true, NumberMode::FLOAT),
+            "p = x + (10%) + 2^10% + y * 10%"
+        );
+        assert_eq!(
+            parse("p = (x%)[0] + (x % y)", false, NumberMode::FLOAT),
+            "p = (x%)[0] + x % y"
+        );
+        assert_eq!(
+            parse("p = (10%) - 5", false, NumberMode::FLOAT),
+            "p = 10% - 5"
+        );
+        assert_eq!(
+            parse(
+                "m = 7 % (-2) + x % (+y) + x % (~y)",
+                false,
+                NumberMode::FLOAT
+            ),
+            "m = 7 % (-2) + x % (+y) + x % (~y)"
+        );
+        assert_eq!(
+            parse("m = 7 % (-2)", true, NumberMode::FLOAT),
+            "m = 7 % (-2)"
+        );
+    }
+
+    #[test]
+    fn test_round_trip_rational() {
+        assert_eq!(
+            parse("r = 0.125 + 1/3 + 12", false, NumberMode::RATIONAL),
+            "r = 0.125 + 1 / 3 + 12"
+        );
+    }
+}
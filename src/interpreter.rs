@@ -1,13 +1,24 @@
-use crate::ast::{ASTStatement, StatementListNode, ASTSemanticAnalysis};
+use crate::ast::{
+    ASTSemanticAnalysis, ASTSemanticExpression, ASTSemanticStatement, ASTStatement,
+    StatementListNode,
+};
 use crate::error::InterpreterError;
+use crate::format;
 use crate::parser::Parser;
 use crate::symbol_table::SymbolTable;
+use crate::token::Token;
 use crate::tokenizer::Tokenizer;
+use crate::value::{NumberMode, Value};
 
 pub struct Interpreter {
     symtab: SymbolTable,
     semantic_symtab: SymbolTable,
-    nodes: Vec<Box<StatementListNode>>,
+    nodes: Vec<StatementListNode>,
+    functions: Vec<String>,
+    variables: Vec<String>,
+    implicit_multiplication: bool,
+    calculator_mode: bool,
+    number_mode: NumberMode,
 }
 
 impl Interpreter {
@@ -16,27 +27,174 @@ impl Interpreter {
             symtab: SymbolTable::new(),
             semantic_symtab: SymbolTable::new(),
             nodes: vec![],
+            functions: vec![],
+            variables: vec![],
+            implicit_multiplication: false,
+            calculator_mode: false,
+            number_mode: NumberMode::FLOAT,
         }
     }
 
-    pub fn interpret(&mut self, content: &str) -> Result<f64, InterpreterError> {
+    pub fn set_implicit_multiplication(&mut self, enabled: bool) {
+        self.implicit_multiplication = enabled;
+    }
+
+    pub fn set_calculator_mode(&mut self, enabled: bool) {
+        self.calculator_mode = enabled;
+    }
+
+    /// Limits the nesting of user function calls, deeper recursion is a
+    /// stack overflow error.
+    pub fn set_max_call_depth(&mut self, depth: usize) {
+        self.symtab.set_max_call_depth(depth);
+        self.semantic_symtab.set_max_call_depth(depth);
+    }
+
+    /// Selects the number backend used for literals, e.g. decimal arithmetic
+    /// with a given precision and rounding mode.
+    pub fn set_number_mode(&mut self, number_mode: NumberMode) {
+        self.number_mode = number_mode;
+    }
+
+    fn parser(&self, tokens: Vec<Token>, functions: Vec<String>, variables: Vec<String>) -> Parser {
+        Parser::new(tokens)
+            .implicit_multiplication(self.implicit_multiplication)
+            .calculator_mode(self.calculator_mode)
+            .number_mode(self.number_mode)
+            .user_functions(functions)
+            .user_variables(variables)
+    }
+
+    pub fn interpret(&mut self, content: &str) -> Result<Value, InterpreterError> {
         let tokens = Tokenizer::new(content).try_collect()?;
-        let statement_list_node = Parser::new(tokens).parse()?;
+        let statement_list_node = self
+            .parser(tokens, self.symtab.functions(), self.symtab.variables())
+            .parse()?;
 
-        statement_list_node.check_semantic(&mut self.semantic_symtab)?;
+        let result = statement_list_node
+            .check_semantic(&mut self.semantic_symtab)
+            .and_then(|_| statement_list_node.execute(&mut self.symtab));
 
-        let value = statement_list_node.execute(&mut self.symtab)?;
-        self.nodes.push(statement_list_node);
+        // The check records the symbols a statement defines before it runs,
+        // after an error only those the statements that ran defined remain.
+        if result.is_err() {
+            self.semantic_symtab = self.symtab.clone();
+        }
+
+        let value = result?;
+        self.nodes.push(*statement_list_node);
 
         Ok(value)
     }
 
+    /// Formats a line of a script consistently, keeping its comment. The
+    /// line is only parsed, the functions it defines are remembered so that
+    /// later lines can call them with implicit multiplication.
+    pub fn format(&mut self, line: &str) -> Result<String, InterpreterError> {
+        let (code, comment) = match line.split_once('#') {
+            Some((code, comment)) => (code, Some(comment.trim_end())),
+            None => (line, None),
+        };
+        let mut text = String::new();
+
+        if !code.trim().is_empty() {
+            let tokens = Tokenizer::new(code).try_collect()?;
+            let mut parser = self.parser(tokens, self.functions.clone(), self.variables.clone());
+            let statement_list_node = parser.parse()?;
+
+            self.functions = parser.functions().to_vec();
+            self.variables = parser.variables().to_vec();
+            text = format::statement(statement_list_node.as_ref());
+        }
+
+        if let Some(comment) = comment {
+            if !text.is_empty() {
+                text.push(' ');
+            }
+
+            text.push('#');
+            text.push_str(comment);
+        }
+
+        Ok(text)
+    }
+
+    /// Renders statements, or an expression when the content is not a
+    /// statement, without evaluating them, e.g. with `latex::statement` and
+    /// `latex::expression`.
+    pub fn render(
+        &self,
+        content: &str,
+        statement: fn(&dyn ASTSemanticStatement) -> String,
+        expression: fn(&dyn ASTSemanticExpression) -> String,
+    ) -> Result<String, InterpreterError> {
+        let tokens: Vec<Token> = Tokenizer::new(content).try_collect()?;
+
+        if let Ok(node) = self
+            .parser(
+                tokens.clone(),
+                self.symtab.functions(),
+                self.symtab.variables(),
+            )
+            .parse()
+        {
+            return Ok(statement(node.as_ref()));
+        }
+
+        let node = self
+            .parser(tokens, self.symtab.functions(), self.symtab.variables())
+            .parse_expression()?;
+
+        Ok(expression(node.as_ref()))
+    }
+
     pub fn clear_state(&mut self) {
         self.nodes.clear();
+        self.functions.clear();
+        self.variables.clear();
         self.symtab.clear();
+        self.semantic_symtab.clear();
     }
 
-    pub fn query(&self, symbol: &String) -> Option<&f64> {
+    pub fn query(&self, symbol: &String) -> Option<&Value> {
         self.symtab.get(symbol)
     }
 }
+
+impl Default for Interpreter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::InterpreterError;
+
+    use super::Interpreter;
+
+    #[test]
+    fn test_interpret_error_rolls_back() {
+        let mut interpreter = Interpreter::new();
+
+        interpreter.interpret("v = [1]").unwrap();
+
+        assert!(matches!(
+            interpreter.interpret("const c = v[9]"),
+            Err(InterpreterError::IndexOutOfRange(..))
+        ));
+        assert_eq!(interpreter.interpret("c = 2").unwrap(), 2.);
+
+        // Statements before the error keep their effect
+        assert!(interpreter.interpret("a = 1; const b = v[9]").is_err());
+        assert_eq!(interpreter.interpret("b = a + 1").unwrap(), 2.);
+        assert!(matches!(
+            interpreter.interpret("d = 1; e = f"),
+            Err(InterpreterError::UndefinedSymbol(_))
+        ));
+        assert!(matches!(
+            interpreter.interpret("g = d"),
+            Err(InterpreterError::UndefinedSymbol(_))
+        ));
+    }
+}
@@ -0,0 +1,466 @@
+use crate::ast::{
+    ASTSemanticExpression, ASTSemanticStatement, BinaryOpType, CompareOpType, NodeKind,
+    UnaryOpType, UnitExpr,
+};
+use crate::format::{
+    self, matrix_rows, ARITH, ATOM, COMPARE, CONVERT, FACTOR, GREEK_LETTERS, LAMBDA, POSTFIX,
+    POWER, TERM,
+};
+use crate::value::Value;
+
+/// Renders a statement as LaTeX math, e.g. `f\left(x\right) = \frac{x}{2}`.
+/// Compound assignments are written out, `x += 1` is `x = x + 1`.
+pub fn statement(node: &dyn ASTSemanticStatement) -> String {
+    match node.kind() {
+        NodeKind::Assign(vars, expressions, op_type, constant) => {
+            let vars: Vec<String> = vars.iter().map(|var| identifier(var.name())).collect();
+            let expressions: Vec<String> = match op_type {
+                Some(op_type) => vars
+                    .iter()
+                    .zip(expressions)
+                    .map(|(var, node)| binary((var.clone(), ATOM), node.as_ref(), op_type).0)
+                    .collect(),
+                None => expressions
+                    .iter()
+                    .map(|node| expression(node.as_ref()))
+                    .collect(),
+            };
+
+            format!(
+                "{}{} = {}",
+                if constant { "\\mathrm{const}\\ " } else { "" },
+                vars.join(", "),
+                expressions.join(", ")
+            )
+        }
+        NodeKind::Function(name, params, body, memo) => {
+            let params: Vec<String> = params.iter().map(|param| identifier(param)).collect();
+
+            format!(
+                "{}{}\\left({}\\right) = {}",
+                if memo { "\\mathrm{memo}\\ " } else { "" },
+                identifier(name),
+                params.join(", "),
+                expression(body)
+            )
+        }
+        NodeKind::StatementList(nodes) => {
+            let nodes: Vec<String> = nodes.iter().map(|node| statement(node.as_ref())).collect();
+
+            nodes.join(";\\quad ")
+        }
+        _ => "\\ldots".to_string(),
+    }
+}
+
+/// Renders an expression as LaTeX math, with divisions as `\frac{a}{b}`,
+/// exponents as `x^{2}` and square roots as `\sqrt{x}`.
+pub fn expression(node: &dyn ASTSemanticExpression) -> String {
+    latex(node).0
+}
+
+fn latex(node: &dyn ASTSemanticExpression) -> (String, u8) {
+    match node.kind() {
+        NodeKind::Number(value) => number(value),
+        NodeKind::Var(name) => (identifier(name), ATOM),
+        NodeKind::Unary(operand, op_type) => match op_type {
+            UnaryOpType::FACTORIAL => (format!("{}!", operand_at(operand, POSTFIX)), POSTFIX),
+            UnaryOpType::PERCENT => (format!("{}\\%", operand_at(operand, POSTFIX)), POSTFIX),
+            UnaryOpType::PLUS => (format!("+{}", operand_at(operand, FACTOR)), FACTOR),
+            UnaryOpType::MINUS => (format!("-{}", operand_at(operand, FACTOR)), FACTOR),
+            UnaryOpType::BITNOT => (format!("\\lnot {}", operand_at(operand, FACTOR)), FACTOR),
+        },
+        NodeKind::Binary(left, right, op_type) | NodeKind::PercentChange(left, right, op_type) => {
+            binary(latex(left), right, op_type)
+        }
+        NodeKind::Call(name, args) => call(name, args),
+        NodeKind::List(nodes) => match matrix_rows(nodes) {
+            Some(rows) => {
+                let rows: Vec<String> = rows.into_iter().map(|row| list(row).join(" & ")).collect();
+
+                (
+                    format!(
+                        "\\begin{{bmatrix}} {} \\end{{bmatrix}}",
+                        rows.join(" \\\\ ")
+                    ),
+                    ATOM,
+                )
+            }
+            None => (format!("\\left[{}\\right]", list(nodes).join(", ")), ATOM),
+        },
+        NodeKind::Index(node, index) => (
+            format!(
+                "{}\\left[{}\\right]",
+                operand_at(node, POSTFIX),
+                expression(index)
+            ),
+            POSTFIX,
+        ),
+        NodeKind::Interval(lo, hi) => (
+            format!("\\left[{}, {}\\right]", expression(lo), expression(hi)),
+            ATOM,
+        ),
+        NodeKind::Quantity(node, unit_expr) => (
+            format!("{}\\,{}", operand_at(node, FACTOR), unit(unit_expr)),
+            TERM,
+        ),
+        NodeKind::Convert(node, unit_expr) => (
+            format!("{} \\to {}", operand_at(node, CONVERT), unit(unit_expr)),
+            CONVERT,
+        ),
+        NodeKind::Diff(node, var) => (
+            format!(
+                "\\frac{{d}}{{d{}}}\\left({}\\right)",
+                identifier(var),
+                expression(node)
+            ),
+            ATOM,
+        ),
+        NodeKind::Solve(lhs, rhs, var, bounds) => {
+            let mut args = vec![
+                format!("{} = {}", expression(lhs), expression(rhs)),
+                identifier(var),
+            ];
+
+            args.extend(list(bounds));
+
+            (
+                format!("\\operatorname{{solve}}{}", parenthesize(&args.join(", "))),
+                ATOM,
+            )
+        }
+        NodeKind::Lambda(params, body) => {
+            let params: Vec<String> = params.iter().map(|param| identifier(param)).collect();
+            let params = match params.as_slice() {
+                [param] => param.clone(),
+                params => parenthesize(&params.join(", ")),
+            };
+
+            (format!("{} \\mapsto {}", params, expression(body)), LAMBDA)
+        }
+        NodeKind::Compare(left, right, op_type) => (
+            format!(
+                "{} {} {}",
+                operand_at(left, CONVERT),
+                compare(op_type),
+                operand_at(right, CONVERT)
+            ),
+            COMPARE,
+        ),
+        NodeKind::Conditional(condition, then_branch, else_branch) => (
+            format!(
+                "\\begin{{cases}} {} & \\text{{if }} {} \\\\ {} & \\text{{otherwise}} \\end{{cases}}",
+                expression(then_branch),
+                expression(condition),
+                expression(else_branch)
+            ),
+            ATOM,
+        ),
+        NodeKind::Poly(node, var) => {
+            let mut args = vec![expression(node)];
+
+            args.extend(var.map(|var| identifier(var)));
+
+            (
+                format!("\\operatorname{{poly}}{}", parenthesize(&args.join(", "))),
+                ATOM,
+            )
+        }
+        // The body extends to the right, so the series is parenthesized as
+        // an operand of a product.
+        NodeKind::Series(op_type, index, lo, hi, body) => (
+            format!(
+                "{}_{{{} = {}}}^{{{}}} {}",
+                if op_type == BinaryOpType::MUL {
+                    "\\prod"
+                } else {
+                    "\\sum"
+                },
+                identifier(index),
+                expression(lo),
+                expression(hi),
+                operand_at(body, TERM)
+            ),
+            ARITH,
+        ),
+        NodeKind::Simplify(node) => (
+            format!(
+                "\\operatorname{{simplify}}\\left({}\\right)",
+                expression(node)
+            ),
+            ATOM,
+        ),
+        _ => ("\\ldots".to_string(), ATOM),
+    }
+}
+
+fn compare(op_type: CompareOpType) -> &'static str {
+    match op_type {
+        CompareOpType::LT => "<",
+        CompareOpType::LE => "\\le",
+        CompareOpType::GT => ">",
+        CompareOpType::GE => "\\ge",
+        CompareOpType::EQ => "=",
+        CompareOpType::NE => "\\ne",
+    }
+}
+
+fn operand_at(node: &dyn ASTSemanticExpression, precedence: u8) -> String {
+    match latex(node) {
+        (text, inner) if inner < precedence => parenthesize(&text),
+        (text, _) => text,
+    }
+}
+
+fn parenthesize(text: &str) -> String {
+    format!("\\left({}\\right)", text)
+}
+
+/// A binary operation on an already rendered left operand, which compound
+/// assignments share with expressions.
+fn binary(
+    (left, left_precedence): (String, u8),
+    right: &dyn ASTSemanticExpression,
+    op_type: BinaryOpType,
+) -> (String, u8) {
+    let precedence = format::precedence(op_type);
+    let left_at = |precedence: u8| match left_precedence < precedence {
+        true => parenthesize(&left),
+        false => left.clone(),
+    };
+
+    // Braces group the exponent and both parts of a fraction. A fraction
+    // binds like a power, so it is parenthesized as a base.
+    match op_type {
+        BinaryOpType::POW => (
+            format!("{}^{{{}}}", left_at(POSTFIX), expression(right)),
+            POWER,
+        ),
+        BinaryOpType::DIV => (
+            format!("\\frac{{{}}}{{{}}}", left, expression(right)),
+            POWER,
+        ),
+        BinaryOpType::FLOORDIV => (
+            format!(
+                "\\left\\lfloor \\frac{{{}}}{{{}}} \\right\\rfloor",
+                left,
+                expression(right)
+            ),
+            ATOM,
+        ),
+        op_type => {
+            let symbol = match op_type {
+                BinaryOpType::ADD => "+",
+                BinaryOpType::SUB => "-",
+                BinaryOpType::PLUSMINUS => "\\pm",
+                BinaryOpType::MUL | BinaryOpType::MATMUL => "\\cdot",
+                BinaryOpType::MOD => "\\bmod",
+                BinaryOpType::BITAND => "\\mathbin{\\&}",
+                BinaryOpType::BITOR => "\\mathbin{|}",
+                BinaryOpType::XOR => "\\oplus",
+                BinaryOpType::SHL => "\\ll",
+                _ => "\\gg",
+            };
+
+            (
+                format!(
+                    "{} {} {}",
+                    left_at(precedence),
+                    symbol,
+                    operand_at(right, precedence + 1)
+                ),
+                precedence,
+            )
+        }
+    }
+}
+
+/// Functions with a notation of their own, such as `\sqrt{x}` and
+/// `\log_{b}\left(x\right)`, others are written as operators.
+fn call(name: &str, args: &[Box<dyn ASTSemanticExpression>]) -> (String, u8) {
+    let args = list(args);
+    let text = match (name, args.as_slice()) {
+        ("sqrt", [x]) => format!("\\sqrt{{{}}}", x),
+        ("abs", [x]) => format!("\\left|{}\\right|", x),
+        ("ceil", [x]) => format!("\\left\\lceil {} \\right\\rceil", x),
+        ("floor", [x]) => format!("\\left\\lfloor {} \\right\\rfloor", x),
+        ("ln", [x]) => format!("\\ln{}", parenthesize(x)),
+        ("sin" | "cos" | "tan", [x]) => format!("\\{}{}", name, parenthesize(x)),
+        ("log2", [x]) => format!("\\log_{{2}}{}", parenthesize(x)),
+        ("log10", [x]) => format!("\\log_{{10}}{}", parenthesize(x)),
+        ("log", [x, base]) => format!("\\log_{{{}}}{}", base, parenthesize(x)),
+        ("pow2", [x]) => return (format!("2^{{{}}}", x), POWER),
+        ("pow10", [x]) => return (format!("10^{{{}}}", x), POWER),
+        (name, args) if name.len() == 1 => {
+            format!("{}{}", identifier(name), parenthesize(&args.join(", ")))
+        }
+        (name, args) => format!(
+            "\\operatorname{{{}}}{}",
+            name.replace('_', "\\_"),
+            parenthesize(&args.join(", "))
+        ),
+    };
+
+    (text, ATOM)
+}
+
+fn list(nodes: &[Box<dyn ASTSemanticExpression>]) -> Vec<String> {
+    nodes.iter().map(|node| expression(node.as_ref())).collect()
+}
+
+/// Greek letters by name, single letters in italics, longer names upright,
+/// and the part after the first `_` as a subscript, e.g. `x_{1}`.
+fn identifier(name: &str) -> String {
+    if let Some((name, subscript)) = name.split_once('_') {
+        if !name.is_empty() && !subscript.is_empty() {
+            return format!("{}_{{{}}}", identifier(name), subscript.replace('_', "\\_"));
+        }
+    }
+
+    if GREEK_LETTERS.iter().any(|(letter, _)| *letter == name) {
+        format!("\\{}", name)
+    } else if name.chars().count() == 1 {
+        name.to_string()
+    } else {
+        format!("\\mathrm{{{}}}", name.replace('_', "\\_"))
+    }
+}
+
+/// Units upright, with negative exponents after a `/`, e.g. `\mathrm{m}/\mathrm{s}^{2}`.
+fn unit(unit: &UnitExpr) -> String {
+    unit.iter()
+        .enumerate()
+        .map(|(i, (name, exponent))| {
+            let (separator, exponent) = match (i, *exponent) {
+                (0, exponent) => ("", exponent),
+                (_, exponent) if exponent < 0 => ("/", -exponent),
+                (_, exponent) => ("\\,", exponent),
+            };
+
+            match exponent {
+                1 => format!("{}\\mathrm{{{}}}", separator, name),
+                exponent => format!("{}\\mathrm{{{}}}^{{{}}}", separator, name, exponent),
+            }
+        })
+        .collect()
+}
+
+/// Fractions are rendered as `\frac{p}{q}`, other literals as the formatter
+/// writes them.
+fn number(value: &Value) -> (String, u8) {
+    let (text, precedence) = format::number(value);
+
+    match text.split_once('/') {
+        Some((numerator, denominator)) => match numerator.strip_prefix('-') {
+            Some(numerator) => (
+                format!("-\\frac{{{}}}{{{}}}", numerator, denominator),
+                FACTOR,
+            ),
+            None => (format!("\\frac{{{}}}{{{}}}", numerator, denominator), POWER),
+        },
+        None => (text, precedence),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::Parser;
+    use crate::tokenizer::Tokenizer;
+    use crate::value::NumberMode;
+
+    use super::{expression, statement};
+
+    fn latex(content: &str) -> String {
+        let tokens = Tokenizer::new(content).try_collect().unwrap();
+        let mut parser = Parser::new(tokens).implicit_multiplication(true);
+
+        match parser.parse() {
+            Ok(node) => statement(node.as_ref()),
+            Err(_) => {
+                let tokens = Tokenizer::new(content).try_collect().unwrap();
+                let node = Parser::new(tokens)
+                    .implicit_multiplication(true)
+                    .parse_expression()
+                    .unwrap();
+
+                expression(node.as_ref())
+            }
+        }
+    }
+
+    #[test]
+    fn test_expression() {
+        let cases = [
+            ("(a + b) / 2", "\\frac{a + b}{2}"),
+            ("x^(n - 1)", "x^{n - 1}"),
+            ("(a + b)^2", "\\left(a + b\\right)^{2}"),
+            ("(1 / x)^2", "\\left(\\frac{1}{x}\\right)^{2}"),
+            ("sqrt(x^2 + 1)", "\\sqrt{x^{2} + 1}"),
+            ("sin(x) * cos(x)", "\\sin\\left(x\\right) \\cdot \\cos\\left(x\\right)"),
+            ("2 pi r", "2 \\cdot \\pi \\cdot r"),
+            ("-(a - b) * c", "-\\left(a - b\\right) \\cdot c"),
+            (
+                "abs(x) + log(x, 2)",
+                "\\left|x\\right| + \\log_{2}\\left(x\\right)",
+            ),
+            (
+                "x_1 + max(a, b)",
+                "x_{1} + \\operatorname{max}\\left(a, b\\right)",
+            ),
+            (
+                "7 // 2 + n!",
+                "\\left\\lfloor \\frac{7}{2} \\right\\rfloor + n!",
+            ),
+            (
+                "[1, 2; 3, 4]",
+                "\\begin{bmatrix} 1 & 2 \\\\ 3 & 4 \\end{bmatrix}",
+            ),
+            ("9.81 m/s^2", "9.81\\,\\mathrm{m}/\\mathrm{s}^{2}"),
+            ("diff(x^2, x)", "\\frac{d}{dx}\\left(x^{2}\\right)"),
+            (
+                "solve(x^2 = 2, x, 0, 5)",
+                "\\operatorname{solve}\\left(x^{2} = 2, x, 0, 5\\right)",
+            ),
+            (
+                "(a <= b) != (c > d)",
+                "\\left(a \\le b\\right) \\ne \\left(c > d\\right)",
+            ),
+            (
+                "if n < 2 then n else 2 n",
+                "\\begin{cases} n & \\text{if } n < 2 \\\\ 2 \\cdot n & \\text{otherwise} \\end{cases}",
+            ),
+        ];
+
+        for (content, expected) in cases {
+            assert_eq!(latex(content), expected, "{}", content);
+        }
+    }
+
+    #[test]
+    fn test_statement() {
+        assert_eq!(
+            latex("f(x) = x / 2; const g = 9.81"),
+            "f\\left(x\\right) = \\frac{x}{2};\\quad \\mathrm{const}\\ g = 9.81"
+        );
+        assert_eq!(latex("a, b = b, a + b"), "a, b = b, a + b");
+        assert_eq!(
+            latex("memo f(n) = n"),
+            "\\mathrm{memo}\\ f\\left(n\\right) = n"
+        );
+        assert_eq!(latex("x *= y + 1"), "x = x \\cdot \\left(y + 1\\right)");
+    }
+
+    #[test]
+    fn test_rational() {
+        let tokens = Tokenizer::new("1/3 * x - 2/3").try_collect().unwrap();
+        let node = Parser::new(tokens)
+            .number_mode(NumberMode::RATIONAL)
+            .parse_expression()
+            .unwrap();
+
+        assert_eq!(
+            expression(node.as_ref()),
+            "\\frac{1}{3} \\cdot x - \\frac{2}{3}"
+        );
+    }
+}
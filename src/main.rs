@@ -1,31 +1,210 @@
 pub mod ast;
+pub mod builtins;
 pub mod error;
+pub mod format;
 pub mod interpreter;
+pub mod latex;
+pub mod math;
+pub mod mathml;
+pub mod matrix;
 pub mod parser;
+pub mod radix;
 pub mod symbol_table;
+pub mod symbolic;
 pub mod token;
 pub mod tokenizer;
+pub mod units;
+pub mod value;
 
+use std::env;
+use std::fs;
 use std::io;
-use std::io::Write;
+use std::io::{Read, Write};
+use std::panic;
+use std::process;
+use std::thread;
 
+use error::InterpreterError;
 use interpreter::Interpreter;
+use radix::Radix;
+use symbol_table::MAX_CALL_DEPTH;
+use value::{DecimalContext, NumberMode, RoundingMode};
 
+/// Native stack reserved for each nested user function call, which
+/// evaluates the nodes of the function body recursively.
+const STACK_PER_CALL: usize = 64 * 1024;
+
+const MIN_STACK_SIZE: usize = 8 * 1024 * 1024;
+
+/// Formats every line of a script, or returns the number of the first line
+/// that does not parse.
+fn format_script(
+    interpreter: &mut Interpreter,
+    content: &str,
+) -> Result<String, (usize, InterpreterError)> {
+    let mut text = String::new();
+
+    for (i, line) in content.lines().enumerate() {
+        // REPL commands such as `:hex` are kept as they are.
+        if line.trim().starts_with(':') {
+            text.push_str(line.trim());
+        } else {
+            text.push_str(&interpreter.format(line).map_err(|err| (i + 1, err))?);
+        }
+
+        text.push('\n');
+    }
+
+    Ok(text)
+}
+
+/// `mathr fmt FILE...` rewrites each script in place, `mathr fmt` formats
+/// standard input to standard output.
+fn format_scripts(interpreter: &mut Interpreter, files: &[String]) -> io::Result<()> {
+    if files.is_empty() {
+        let mut content = String::new();
+
+        io::stdin().read_to_string(&mut content)?;
+
+        match format_script(interpreter, &content) {
+            Ok(text) => print!("{}", text),
+            Err((line, err)) => {
+                eprintln!("<stdin>:{}: {}", line, err);
+                process::exit(1);
+            }
+        }
+
+        return Ok(());
+    }
+
+    for file in files {
+        let content = fs::read_to_string(file)?;
+
+        interpreter.clear_state();
+
+        match format_script(interpreter, &content) {
+            Ok(text) if text != content => fs::write(file, text)?,
+            Ok(_) => {}
+            Err((line, err)) => {
+                eprintln!("{}:{}: {}", file, line, err);
+                process::exit(1);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Runs the interpreter on a thread whose stack fits `--max-depth` nested
+/// calls, so that deep recursion is a stack overflow error rather than a
+/// crash.
 fn main() -> io::Result<()> {
+    let depth = env::args()
+        .find_map(|arg| arg.strip_prefix("--max-depth=")?.parse::<usize>().ok())
+        .unwrap_or(MAX_CALL_DEPTH);
+    let stack_size = depth.saturating_mul(STACK_PER_CALL).max(MIN_STACK_SIZE);
+
+    let handle = match thread::Builder::new().stack_size(stack_size).spawn(run) {
+        Ok(handle) => handle,
+        Err(err) => {
+            eprintln!("Cannot reserve a stack for {} nested calls: {}", depth, err);
+            process::exit(1);
+        }
+    };
+
+    handle
+        .join()
+        .unwrap_or_else(|err| panic::resume_unwind(err))
+}
+
+fn run() -> io::Result<()> {
     let mut interpreter = Interpreter::new();
 
+    let mut decimal_context: Option<DecimalContext> = None;
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let fmt = args.first().is_some_and(|arg| arg == "fmt");
+    let mut files: Vec<String> = vec![];
+
+    if fmt {
+        args.remove(0);
+    }
+
+    for arg in args {
+        let context = decimal_context.unwrap_or_default();
+
+        match arg.split_once('=').unwrap_or((arg.as_str(), "")) {
+            ("--implicit-mul", "") => interpreter.set_implicit_multiplication(true),
+            ("--calculator", "") => interpreter.set_calculator_mode(true),
+            ("--decimal", "") => decimal_context = Some(context),
+            ("--rational", "") => interpreter.set_number_mode(NumberMode::RATIONAL),
+            ("--max-depth", depth) if depth.parse::<usize>().is_ok() => {
+                interpreter.set_max_call_depth(depth.parse().unwrap())
+            }
+            ("--precision", precision) if precision.parse::<usize>().is_ok_and(|p| p > 0) => {
+                decimal_context = Some(DecimalContext::new(
+                    precision.parse().unwrap(),
+                    context.rounding,
+                ))
+            }
+            ("--rounding", name) if RoundingMode::parse(name).is_some() => {
+                decimal_context = Some(DecimalContext::new(
+                    context.precision,
+                    RoundingMode::parse(name).unwrap(),
+                ))
+            }
+            _ if fmt && !arg.starts_with("--") => files.push(arg),
+            _ => {
+                eprintln!("Unknown option: {}", arg);
+                return Ok(());
+            }
+        }
+    }
+
+    if let Some(context) = decimal_context {
+        interpreter.set_number_mode(NumberMode::DECIMAL(context));
+    }
+
+    if fmt {
+        return format_scripts(&mut interpreter, &files);
+    }
+
+    let mut radix = Radix::DEC;
+
     loop {
         let mut buffer = String::new();
 
         print!(">>> ");
         io::stdout().flush()?;
-        io::stdin().read_line(&mut buffer)?;
+
+        if io::stdin().read_line(&mut buffer)? == 0 {
+            return Ok(());
+        }
 
         buffer.pop();
 
-        match interpreter.interpret(&buffer) {
-            Ok(value) => println!("{}", value),
-            Err(err) => println!("{}", err),
+        match buffer.trim() {
+            ":dec" => radix = Radix::DEC,
+            ":hex" => radix = Radix::HEX,
+            ":bin" => radix = Radix::BIN,
+            line if line.starts_with(":latex ") || line.starts_with(":mathml ") => {
+                let result = match line.split_once(' ').unwrap() {
+                    (":latex", content) => {
+                        interpreter.render(content, latex::statement, latex::expression)
+                    }
+                    (_, content) => {
+                        interpreter.render(content, mathml::statement, mathml::expression)
+                    }
+                };
+
+                match result {
+                    Ok(text) => println!("{}", text),
+                    Err(err) => println!("{}", err),
+                }
+            }
+            _ => match interpreter.interpret(&buffer) {
+                Ok(value) => println!("{}", radix::format_value(&value, radix)),
+                Err(err) => println!("{}", err),
+            },
         }
     }
 }
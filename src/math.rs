@@ -0,0 +1,535 @@
+use std::f64::consts;
+
+use crate::value::BigInt;
+
+const LANCZOS_G: f64 = 7.;
+const LANCZOS_COEFFICIENTS: [f64; 9] = [
+    0.999_999_999_999_809_9,
+    676.520_368_121_885_1,
+    -1_259.139_216_722_402_8,
+    771.323_428_777_653_1,
+    -176.615_029_162_140_6,
+    12.507_343_278_686_905,
+    -0.138_571_095_265_720_12,
+    9.984_369_578_019_572e-6,
+    1.505_632_735_149_311_6e-7,
+];
+
+/// Gamma function computed with the Lanczos approximation, using the
+/// reflection formula for arguments below 0.5.
+pub fn gamma(x: f64) -> f64 {
+    if x < 0.5 {
+        return consts::PI / ((consts::PI * x).sin() * gamma(1. - x));
+    }
+
+    let x = x - 1.;
+    let mut sum = LANCZOS_COEFFICIENTS[0];
+
+    for (i, coefficient) in LANCZOS_COEFFICIENTS.iter().enumerate().skip(1) {
+        sum += coefficient / (x + i as f64);
+    }
+
+    let t = x + LANCZOS_G + 0.5;
+
+    (2. * consts::PI).sqrt() * t.powf(x + 0.5) * (-t).exp() * sum
+}
+
+/// Digamma function, the derivative of `ln(gamma(x))`. Small arguments are
+/// shifted up with its recurrence before applying the asymptotic series,
+/// and the reflection formula is used below 0.5.
+pub fn digamma(x: f64) -> f64 {
+    if x < 0.5 {
+        return digamma(1. - x) - consts::PI / (consts::PI * x).tan();
+    }
+
+    let mut x = x;
+    let mut result = 0.;
+
+    while x < 10. {
+        result -= 1. / x;
+        x += 1.;
+    }
+
+    let x2 = 1. / (x * x);
+
+    result + x.ln()
+        - 0.5 / x
+        - x2 * (1. / 12. - x2 * (1. / 120. - x2 * (1. / 252. - x2 * (1. / 240. - x2 / 132.))))
+}
+
+/// Factorial of `x`, exact for non-negative integers and `gamma(x + 1)`
+/// otherwise. Returns `None` for negative integers where it is undefined.
+pub fn factorial(x: f64) -> Option<f64> {
+    if x.fract() != 0. {
+        return Some(gamma(x + 1.));
+    }
+
+    if x < 0. {
+        return None;
+    }
+
+    let mut result: f64 = 1.;
+    let mut i = 2.;
+
+    while i <= x && result.is_finite() {
+        result *= i;
+        i += 1.;
+    }
+
+    Some(result)
+}
+
+/// Exact factorial of `n`.
+pub fn big_factorial(n: u32) -> BigInt {
+    (2..=n as i64).fold(BigInt::from_i64(1), |result, i| {
+        result.mul(&BigInt::from_i64(i))
+    })
+}
+
+/// Converts `x` to an integer if it is integral and fits in an `i64`.
+pub fn to_integer(x: f64) -> Option<i64> {
+    if x.fract() == 0. && x >= i64::MIN as f64 && x < i64::MAX as f64 {
+        Some(x as i64)
+    } else {
+        None
+    }
+}
+
+/// Nodes of the 15-point Kronrod rule on `[-1, 1]` from the outside in,
+/// every other one is a node of the embedded 7-point Gauss rule.
+const KRONROD_NODES: [f64; 8] = [
+    0.991_455_371_120_812_6,
+    0.949_107_912_342_758_5,
+    0.864_864_423_359_769_1,
+    0.741_531_185_599_394_4,
+    0.586_087_235_467_691_1,
+    0.405_845_151_377_397_2,
+    0.207_784_955_007_898_5,
+    0.,
+];
+const KRONROD_WEIGHTS: [f64; 8] = [
+    0.022_935_322_010_529_22,
+    0.063_092_092_629_978_55,
+    0.104_790_010_322_250_2,
+    0.140_653_259_715_525_9,
+    0.169_004_726_639_267_9,
+    0.190_350_578_064_785_4,
+    0.204_432_940_075_298_9,
+    0.209_482_141_084_727_8,
+];
+const GAUSS_WEIGHTS: [f64; 4] = [
+    0.129_484_966_168_869_7,
+    0.279_705_391_489_276_7,
+    0.381_830_050_505_118_9,
+    0.417_959_183_673_469_4,
+];
+const MAX_SUBINTERVALS: usize = 1000;
+const MAX_ITERATIONS: usize = 200;
+const ROOT_SAMPLES: usize = 200;
+
+/// Tolerance of numeric methods such as `integrate` unless one is given.
+pub const DEFAULT_TOLERANCE: f64 = 1e-10;
+
+/// Result of a numeric integration, `error` estimates the absolute error of
+/// `value`.
+#[derive(Debug, Clone, Copy)]
+pub struct Quadrature {
+    pub value: f64,
+    pub error: f64,
+    pub converged: bool,
+}
+
+/// Estimate of the integral over one subinterval.
+struct Panel {
+    lo: f64,
+    hi: f64,
+    value: f64,
+    error: f64,
+}
+
+/// Integral of `f` over `[a, b]` by adaptive Gauss–Kronrod quadrature. The
+/// subinterval with the largest error estimate is bisected until the total
+/// estimate is within `tolerance`, relative to the value once it exceeds 1.
+/// The endpoints are never evaluated, so integrable singularities there are
+/// handled.
+pub fn integrate<E>(
+    mut f: impl FnMut(f64) -> Result<f64, E>,
+    a: f64,
+    b: f64,
+    tolerance: f64,
+) -> Result<Quadrature, E> {
+    let mut panels = vec![gauss_kronrod(&mut f, a, b)?];
+
+    loop {
+        let value: f64 = panels.iter().map(|panel| panel.value).sum();
+        let error: f64 = panels.iter().map(|panel| panel.error).sum();
+
+        if value.is_finite() && error <= tolerance * value.abs().max(1.) {
+            return Ok(Quadrature {
+                value,
+                error,
+                converged: true,
+            });
+        }
+
+        let (i, panel) = panels
+            .iter()
+            .enumerate()
+            .max_by(|(_, x), (_, y)| x.error.total_cmp(&y.error))
+            .unwrap();
+        let (lo, hi) = (panel.lo, panel.hi);
+        let mid = 0.5 * (lo + hi);
+
+        // The integrand is not finite or too rough to reduce the error.
+        if !error.is_finite() || panels.len() >= MAX_SUBINTERVALS || mid == lo || mid == hi {
+            return Ok(Quadrature {
+                value,
+                error,
+                converged: false,
+            });
+        }
+
+        panels[i] = gauss_kronrod(&mut f, lo, mid)?;
+        panels.push(gauss_kronrod(&mut f, mid, hi)?);
+    }
+}
+
+/// The 15-point Kronrod estimate of the integral over `[lo, hi]`, with the
+/// difference to the 7-point Gauss estimate as its error.
+fn gauss_kronrod<E>(
+    f: &mut impl FnMut(f64) -> Result<f64, E>,
+    lo: f64,
+    hi: f64,
+) -> Result<Panel, E> {
+    let center = 0.5 * (lo + hi);
+    let half_width = 0.5 * (hi - lo);
+    let mut kronrod = 0.;
+    let mut gauss = 0.;
+
+    for (i, (node, weight)) in KRONROD_NODES.iter().zip(KRONROD_WEIGHTS).enumerate() {
+        let y = match *node {
+            0. => f(center)?,
+            node => f(center - half_width * node)? + f(center + half_width * node)?,
+        };
+
+        kronrod += weight * y;
+
+        if i % 2 == 1 {
+            gauss += GAUSS_WEIGHTS[i / 2] * y;
+        }
+    }
+
+    Ok(Panel {
+        lo,
+        hi,
+        value: kronrod * half_width,
+        error: ((kronrod - gauss) * half_width).abs(),
+    })
+}
+
+/// Root of `f` by Newton's method from `x0`, where `f` returns its value and
+/// derivative. Returns `None` when the iteration reaches a zero derivative
+/// or does not settle within `tolerance`, relative to the root once it
+/// exceeds 1.
+pub fn newton<E>(
+    mut f: impl FnMut(f64) -> Result<(f64, f64), E>,
+    x0: f64,
+    tolerance: f64,
+) -> Result<Option<f64>, E> {
+    let mut x = x0;
+
+    for _ in 0..MAX_ITERATIONS {
+        let (y, dy) = f(x)?;
+
+        if y == 0. {
+            return Ok(Some(x));
+        }
+
+        if dy == 0. || !y.is_finite() || !dy.is_finite() {
+            return Ok(None);
+        }
+
+        let step = y / dy;
+
+        x -= step;
+
+        if step.abs() <= tolerance * x.abs().max(1.) {
+            return Ok(Some(x));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Root of `f` in `[a, b]` by Brent's method, which keeps the root bracketed
+/// and falls back to bisection when interpolation is slow. Returns `None`
+/// when `f` does not change sign over the interval, or only changes sign at
+/// a pole such as that of `1 / x`.
+pub fn brent<E>(
+    mut f: impl FnMut(f64) -> Result<f64, E>,
+    a: f64,
+    b: f64,
+    tolerance: f64,
+) -> Result<Option<f64>, E> {
+    let (mut a, mut b) = (a, b);
+    let (mut fa, mut fb) = (f(a)?, f(b)?);
+    let bound = fa.abs().min(fb.abs());
+
+    if fa == 0. {
+        return Ok(Some(a));
+    }
+
+    if fb == 0. {
+        return Ok(Some(b));
+    }
+
+    if fa.is_nan() || fb.is_nan() || fa.signum() == fb.signum() {
+        return Ok(None);
+    }
+
+    let (mut c, mut fc) = (a, fa);
+    let mut d = b - a;
+    let mut e = d;
+
+    for _ in 0..MAX_ITERATIONS {
+        if fb.signum() == fc.signum() {
+            (c, fc) = (a, fa);
+            d = b - a;
+            e = d;
+        }
+
+        if fc.abs() < fb.abs() {
+            (a, fa) = (b, fb);
+            (b, fb) = (c, fc);
+            (c, fc) = (a, fa);
+        }
+
+        let step_tolerance = 2. * f64::EPSILON * b.abs() + 0.5 * tolerance * b.abs().max(1.);
+        let m = 0.5 * (c - b);
+
+        if m.abs() <= step_tolerance || fb == 0. {
+            return Ok((fb.abs() <= bound).then_some(b));
+        }
+
+        if e.abs() >= step_tolerance && fa.abs() > fb.abs() {
+            // Secant step, or inverse quadratic interpolation through three
+            // distinct points.
+            let s = fb / fa;
+            let (mut p, mut q) = if a == c {
+                (2. * m * s, 1. - s)
+            } else {
+                let q = fa / fc;
+                let r = fb / fc;
+
+                (
+                    s * (2. * m * q * (q - r) - (b - a) * (r - 1.)),
+                    (q - 1.) * (r - 1.) * (s - 1.),
+                )
+            };
+
+            if p > 0. {
+                q = -q;
+            } else {
+                p = -p;
+            }
+
+            if 2. * p < (3. * m * q - (step_tolerance * q).abs()).min((e * q).abs()) {
+                e = d;
+                d = p / q;
+            } else {
+                d = m;
+                e = m;
+            }
+        } else {
+            d = m;
+            e = m;
+        }
+
+        (a, fa) = (b, fb);
+        b += if d.abs() > step_tolerance {
+            d
+        } else {
+            step_tolerance.copysign(m)
+        };
+        fb = f(b)?;
+    }
+
+    Ok(Some(b))
+}
+
+/// Roots of `f` in `[a, b]` in increasing order, found with Brent's method
+/// where `f` changes sign between evenly spaced sample points. A root where
+/// the sign does not change, such as that of `x^2`, is only found when it
+/// is one of the sample points.
+pub fn roots<E>(
+    mut f: impl FnMut(f64) -> Result<f64, E>,
+    a: f64,
+    b: f64,
+    tolerance: f64,
+) -> Result<Vec<f64>, E> {
+    let (a, b) = (a.min(b), a.max(b));
+    let mut roots = vec![];
+    let mut lo = a;
+    let mut f_lo = f(a)?;
+
+    if f_lo == 0. {
+        roots.push(a);
+    }
+
+    if a == b {
+        return Ok(roots);
+    }
+
+    for i in 1..=ROOT_SAMPLES {
+        let hi = match i {
+            ROOT_SAMPLES => b,
+            i => a + (b - a) * i as f64 / ROOT_SAMPLES as f64,
+        };
+        let f_hi = f(hi)?;
+
+        if f_hi == 0. {
+            roots.push(hi);
+        } else if f_lo != 0. && f_lo.signum() == -f_hi.signum() {
+            if let Some(root) = brent(&mut f, lo, hi, tolerance)? {
+                roots.push(root);
+            }
+        }
+
+        (lo, f_lo) = (hi, f_hi);
+    }
+
+    // Sample points too close together for a float to tell apart
+    roots.dedup();
+
+    Ok(roots)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use super::{
+        big_factorial, brent, digamma, factorial, gamma, integrate, newton, roots, to_integer,
+    };
+
+    #[test]
+    fn test_gamma() {
+        assert!((gamma(5.) - 24.).abs() < 1e-9);
+        assert!((gamma(0.5) - std::f64::consts::PI.sqrt()).abs() < 1e-12);
+        assert!((gamma(-0.5) + 2. * std::f64::consts::PI.sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_digamma() {
+        const EULER_GAMMA: f64 = 0.577_215_664_901_532_9;
+
+        assert!((digamma(1.) + EULER_GAMMA).abs() < 1e-13);
+        assert!((digamma(0.5) + EULER_GAMMA + 2. * std::f64::consts::LN_2).abs() < 1e-13);
+        assert!((digamma(10.) - 2.251_752_589_066_721).abs() < 1e-13);
+        assert!((digamma(-0.5) - 0.036_489_973_978_576_5).abs() < 1e-13);
+    }
+
+    #[test]
+    fn test_factorial() {
+        assert_eq!(factorial(0.), Some(1.));
+        assert_eq!(factorial(5.), Some(120.));
+        assert_eq!(factorial(20.), Some(2_432_902_008_176_640_000.));
+        assert_eq!(factorial(-3.), None);
+        assert!((factorial(0.5).unwrap() - 0.886_226_925_452_758).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_big_factorial() {
+        assert_eq!(big_factorial(0).to_string(), "1");
+        assert_eq!(
+            big_factorial(30).to_string(),
+            "265252859812191058636308480000000"
+        );
+    }
+
+    #[test]
+    fn test_to_integer() {
+        assert_eq!(to_integer(42.), Some(42));
+        assert_eq!(to_integer(-7.), Some(-7));
+        assert_eq!(to_integer(1.5), None);
+        assert_eq!(to_integer(f64::NAN), None);
+        assert_eq!(to_integer(f64::INFINITY), None);
+    }
+
+    #[test]
+    fn test_integrate() {
+        let integral = |f: fn(f64) -> f64, a: f64, b: f64| {
+            integrate(|x| Ok::<f64, Infallible>(f(x)), a, b, 1e-10).unwrap()
+        };
+
+        let result = integral(f64::sin, 0., std::f64::consts::PI);
+        assert!(result.converged);
+        assert!((result.value - 2.).abs() < 1e-12);
+        assert!(result.error < 1e-10);
+
+        let result = integral(|x| x * x, 3., 0.);
+        assert!((result.value + 9.).abs() < 1e-12);
+
+        // Singular at an endpoint
+        let result = integral(|x| 1. / x.sqrt(), 0., 1.);
+        assert!(result.converged);
+        assert!((result.value - 2.).abs() < 1e-8);
+
+        // Not integrable
+        assert!(!integral(|x| 1. / x, -1., 1.).converged);
+        assert!(!integral(|x| 1. / (x * x), 0., 1.).converged);
+    }
+
+    #[test]
+    fn test_newton() {
+        let root = |f: fn(f64) -> (f64, f64), x0: f64| {
+            newton(|x| Ok::<(f64, f64), Infallible>(f(x)), x0, 1e-12).unwrap()
+        };
+
+        let x = root(|x| (x * x - 2., 2. * x), 1.).unwrap();
+        assert!((x - std::f64::consts::SQRT_2).abs() < 1e-12);
+
+        let x = root(|x| (x.cos() - x, -x.sin() - 1.), 0.).unwrap();
+        assert!((x.cos() - x).abs() < 1e-12);
+
+        assert_eq!(root(|x| (x * x + 1., 2. * x), 1.), None);
+        assert_eq!(root(|x| (x * x - 2., 2. * x), 0.), None);
+    }
+
+    #[test]
+    fn test_brent() {
+        let root = |f: fn(f64) -> f64, a: f64, b: f64| {
+            brent(|x| Ok::<f64, Infallible>(f(x)), a, b, 1e-12).unwrap()
+        };
+
+        let x = root(|x| x * x * x - 2. * x - 5., 2., 3.).unwrap();
+        assert!((x - 2.094_551_481_542_326_5).abs() < 1e-12);
+
+        assert_eq!(root(|x| x - 1., 1., 2.), Some(1.));
+        assert_eq!(root(|x| x * x + 1., -1., 1.), None);
+        assert_eq!(root(|x| 1. / x, -1., 2.), None);
+    }
+
+    #[test]
+    fn test_roots() {
+        let all = |f: fn(f64) -> f64, a: f64, b: f64| {
+            roots(|x| Ok::<f64, Infallible>(f(x)), a, b, 1e-12).unwrap()
+        };
+
+        let xs = all(f64::sin, 10., -1.);
+        assert_eq!(xs.len(), 4);
+
+        for (i, x) in xs.iter().enumerate() {
+            assert!((x - i as f64 * std::f64::consts::PI).abs() < 1e-12);
+        }
+
+        assert_eq!(all(|x| x * x, -1., 1.), vec![0.]);
+        assert!(all(|x| x * x + 1., -1., 1.).is_empty());
+        assert!(all(f64::tan, 1., 2.).is_empty());
+
+        // A single point, or an interval narrower than the samples
+        assert_eq!(all(|x| x * x - 1., 1., 1.), vec![1.]);
+        assert!(all(|x| x * x - 1., 2., 2.).is_empty());
+        assert_eq!(all(|x| x - 1., 1., 1. + f64::EPSILON), vec![1.]);
+    }
+}
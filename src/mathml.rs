@@ -0,0 +1,563 @@
+use crate::ast::{
+    ASTSemanticExpression, ASTSemanticStatement, BinaryOpType, CompareOpType, NodeKind,
+    UnaryOpType, UnitExpr,
+};
+use crate::format::{
+    self, matrix_rows, ARITH, ATOM, COMPARE, CONVERT, FACTOR, GREEK_LETTERS, LAMBDA, POSTFIX,
+    POWER, TERM,
+};
+use crate::value::Value;
+
+/// Renders a statement as a Presentation MathML `<math>` element. Compound
+/// assignments are written out, `x += 1` is `x = x + 1`.
+pub fn statement(node: &dyn ASTSemanticStatement) -> String {
+    math(&mathml_statement(node))
+}
+
+/// Renders an expression as a Presentation MathML `<math>` element, with
+/// divisions as `<mfrac>`, exponents as `<msup>` and square roots as
+/// `<msqrt>`.
+pub fn expression(node: &dyn ASTSemanticExpression) -> String {
+    math(&mathml(node).0)
+}
+
+fn math(content: &str) -> String {
+    format!(
+        "<math xmlns=\"http://www.w3.org/1998/Math/MathML\">{}</math>",
+        content
+    )
+}
+
+fn mathml_statement(node: &dyn ASTSemanticStatement) -> String {
+    match node.kind() {
+        NodeKind::Assign(vars, expressions, op_type, constant) => {
+            let vars: Vec<String> = vars.iter().map(|var| identifier(var.name())).collect();
+            let expressions: Vec<String> = match op_type {
+                Some(op_type) => vars
+                    .iter()
+                    .zip(expressions)
+                    .map(|(var, node)| binary((var.clone(), ATOM), node.as_ref(), op_type).0)
+                    .collect(),
+                None => expressions
+                    .iter()
+                    .map(|node| mathml(node.as_ref()).0)
+                    .collect(),
+            };
+
+            format!(
+                "<mrow>{}{}<mo>=</mo>{}</mrow>",
+                if constant {
+                    "<mtext>const</mtext><mspace width=\"0.5em\"/>"
+                } else {
+                    ""
+                },
+                separated(&vars),
+                separated(&expressions)
+            )
+        }
+        NodeKind::Function(name, params, body, memo) => {
+            let params: Vec<String> = params.iter().map(|param| identifier(param)).collect();
+
+            format!(
+                "<mrow>{}{}<mo>&#x2061;</mo>{}<mo>=</mo>{}</mrow>",
+                if memo {
+                    "<mtext>memo</mtext><mspace width=\"0.5em\"/>"
+                } else {
+                    ""
+                },
+                identifier(name),
+                parenthesize(&separated(&params)),
+                mathml(body).0
+            )
+        }
+        NodeKind::StatementList(nodes) => {
+            let nodes: Vec<String> = nodes
+                .iter()
+                .map(|node| mathml_statement(node.as_ref()))
+                .collect();
+
+            format!("<mrow>{}</mrow>", nodes.join("<mo>;</mo>"))
+        }
+        _ => "<mo>&#x2026;</mo>".to_string(),
+    }
+}
+
+fn mathml(node: &dyn ASTSemanticExpression) -> (String, u8) {
+    match node.kind() {
+        NodeKind::Number(value) => number(value),
+        NodeKind::Var(name) => (identifier(name), ATOM),
+        NodeKind::Unary(operand, op_type) => match op_type {
+            UnaryOpType::FACTORIAL => (postfix(operand, "!"), POSTFIX),
+            UnaryOpType::PERCENT => (postfix(operand, "%"), POSTFIX),
+            UnaryOpType::PLUS => (prefix("+", operand), FACTOR),
+            UnaryOpType::MINUS => (prefix("&#x2212;", operand), FACTOR),
+            UnaryOpType::BITNOT => (prefix("&#xAC;", operand), FACTOR),
+        },
+        NodeKind::Binary(left, right, op_type) | NodeKind::PercentChange(left, right, op_type) => {
+            binary(mathml(left), right, op_type)
+        }
+        NodeKind::Call(name, args) => call(name, args),
+        NodeKind::List(nodes) => match matrix_rows(nodes) {
+            Some(rows) => {
+                let rows: Vec<String> = rows
+                    .into_iter()
+                    .map(|row| {
+                        let cells: Vec<String> = list(row)
+                            .into_iter()
+                            .map(|cell| format!("<mtd>{}</mtd>", cell))
+                            .collect();
+
+                        format!("<mtr>{}</mtr>", cells.concat())
+                    })
+                    .collect();
+
+                (
+                    format!(
+                        "<mrow><mo>[</mo><mtable>{}</mtable><mo>]</mo></mrow>",
+                        rows.concat()
+                    ),
+                    ATOM,
+                )
+            }
+            None => (brackets(&separated(&list(nodes))), ATOM),
+        },
+        NodeKind::Index(node, index) => (
+            format!(
+                "<mrow>{}{}</mrow>",
+                operand_at(node, POSTFIX),
+                brackets(&mathml(index).0)
+            ),
+            POSTFIX,
+        ),
+        NodeKind::Interval(lo, hi) => (brackets(&separated(&[mathml(lo).0, mathml(hi).0])), ATOM),
+        NodeKind::Quantity(node, unit_expr) => (
+            format!(
+                "<mrow>{}<mspace width=\"0.167em\"/>{}</mrow>",
+                operand_at(node, FACTOR),
+                unit(unit_expr)
+            ),
+            TERM,
+        ),
+        NodeKind::Convert(node, unit_expr) => (
+            format!(
+                "<mrow>{}<mo>&#x2192;</mo>{}</mrow>",
+                operand_at(node, CONVERT),
+                unit(unit_expr)
+            ),
+            CONVERT,
+        ),
+        NodeKind::Diff(node, var) => (
+            format!(
+                "<mrow><mfrac><mi>d</mi><mrow><mi>d</mi>{}</mrow></mfrac>{}</mrow>",
+                identifier(var),
+                parenthesize(&mathml(node).0)
+            ),
+            ATOM,
+        ),
+        NodeKind::Solve(lhs, rhs, var, bounds) => {
+            let mut args = vec![
+                format!("<mrow>{}<mo>=</mo>{}</mrow>", mathml(lhs).0, mathml(rhs).0),
+                identifier(var),
+            ];
+
+            args.extend(list(bounds));
+
+            (function("<mi>solve</mi>", &args), ATOM)
+        }
+        NodeKind::Lambda(params, body) => {
+            let params: Vec<String> = params.iter().map(|param| identifier(param)).collect();
+            let params = match params.as_slice() {
+                [param] => param.clone(),
+                params => parenthesize(&separated(params)),
+            };
+
+            (
+                format!("<mrow>{}<mo>&#x21A6;</mo>{}</mrow>", params, mathml(body).0),
+                LAMBDA,
+            )
+        }
+        NodeKind::Compare(left, right, op_type) => (
+            format!(
+                "<mrow>{}<mo>{}</mo>{}</mrow>",
+                operand_at(left, CONVERT),
+                compare(op_type),
+                operand_at(right, CONVERT)
+            ),
+            COMPARE,
+        ),
+        NodeKind::Conditional(condition, then_branch, else_branch) => (
+            format!(
+                "<mrow><mo>{{</mo><mtable columnalign=\"left\">\
+                 <mtr><mtd>{}</mtd><mtd><mrow><mtext>if&#xA0;</mtext>{}</mrow></mtd></mtr>\
+                 <mtr><mtd>{}</mtd><mtd><mtext>otherwise</mtext></mtd></mtr></mtable></mrow>",
+                mathml(then_branch).0,
+                mathml(condition).0,
+                mathml(else_branch).0
+            ),
+            ATOM,
+        ),
+        NodeKind::Poly(node, var) => {
+            let mut args = vec![mathml(node).0];
+
+            args.extend(var.map(|var| identifier(var)));
+
+            (function("<mi>poly</mi>", &args), ATOM)
+        }
+        // The body extends to the right, so the series is parenthesized as
+        // an operand of a product.
+        NodeKind::Series(op_type, index, lo, hi, body) => (
+            format!(
+                "<mrow><munderover><mo>{}</mo><mrow>{}<mo>=</mo>{}</mrow>{}</munderover>{}</mrow>",
+                if op_type == BinaryOpType::MUL {
+                    "&#x220F;"
+                } else {
+                    "&#x2211;"
+                },
+                identifier(index),
+                mathml(lo).0,
+                mathml(hi).0,
+                operand_at(body, TERM)
+            ),
+            ARITH,
+        ),
+        NodeKind::Simplify(node) => (function("<mi>simplify</mi>", &[mathml(node).0]), ATOM),
+        _ => ("<mo>&#x2026;</mo>".to_string(), ATOM),
+    }
+}
+
+fn compare(op_type: CompareOpType) -> &'static str {
+    match op_type {
+        CompareOpType::LT => "&lt;",
+        CompareOpType::LE => "&#x2264;",
+        CompareOpType::GT => "&gt;",
+        CompareOpType::GE => "&#x2265;",
+        CompareOpType::EQ => "=",
+        CompareOpType::NE => "&#x2260;",
+    }
+}
+
+fn operand_at(node: &dyn ASTSemanticExpression, precedence: u8) -> String {
+    match mathml(node) {
+        (text, inner) if inner < precedence => parenthesize(&text),
+        (text, _) => text,
+    }
+}
+
+fn parenthesize(content: &str) -> String {
+    format!("<mrow><mo>(</mo>{}<mo>)</mo></mrow>", content)
+}
+
+fn brackets(content: &str) -> String {
+    format!("<mrow><mo>[</mo>{}<mo>]</mo></mrow>", content)
+}
+
+fn separated(items: &[String]) -> String {
+    items.join("<mo>,</mo>")
+}
+
+fn prefix(operator: &str, operand: &dyn ASTSemanticExpression) -> String {
+    format!(
+        "<mrow><mo>{}</mo>{}</mrow>",
+        operator,
+        operand_at(operand, FACTOR)
+    )
+}
+
+fn postfix(operand: &dyn ASTSemanticExpression, operator: &str) -> String {
+    format!(
+        "<mrow>{}<mo>{}</mo></mrow>",
+        operand_at(operand, POSTFIX),
+        operator
+    )
+}
+
+/// A binary operation on an already rendered left operand, which compound
+/// assignments share with expressions.
+fn binary(
+    (left, left_precedence): (String, u8),
+    right: &dyn ASTSemanticExpression,
+    op_type: BinaryOpType,
+) -> (String, u8) {
+    let precedence = format::precedence(op_type);
+    let left_at = |precedence: u8| match left_precedence < precedence {
+        true => parenthesize(&left),
+        false => left.clone(),
+    };
+
+    // The layout groups the exponent and both parts of a fraction. A
+    // fraction binds like a power, so it is parenthesized as a base.
+    match op_type {
+        BinaryOpType::POW => (
+            format!("<msup>{}{}</msup>", left_at(POSTFIX), mathml(right).0),
+            POWER,
+        ),
+        BinaryOpType::DIV => (format!("<mfrac>{}{}</mfrac>", left, mathml(right).0), POWER),
+        BinaryOpType::FLOORDIV => (
+            format!(
+                "<mrow><mo>&#x230A;</mo><mfrac>{}{}</mfrac><mo>&#x230B;</mo></mrow>",
+                left,
+                mathml(right).0
+            ),
+            ATOM,
+        ),
+        op_type => {
+            let symbol = match op_type {
+                BinaryOpType::ADD => "+",
+                BinaryOpType::SUB => "&#x2212;",
+                BinaryOpType::PLUSMINUS => "&#xB1;",
+                BinaryOpType::MUL | BinaryOpType::MATMUL => "&#x22C5;",
+                BinaryOpType::MOD => "mod",
+                BinaryOpType::BITAND => "&amp;",
+                BinaryOpType::BITOR => "|",
+                BinaryOpType::XOR => "&#x2295;",
+                BinaryOpType::SHL => "&#x226A;",
+                _ => "&#x226B;",
+            };
+
+            (
+                format!(
+                    "<mrow>{}<mo>{}</mo>{}</mrow>",
+                    left_at(precedence),
+                    symbol,
+                    operand_at(right, precedence + 1)
+                ),
+                precedence,
+            )
+        }
+    }
+}
+
+/// Functions with a notation of their own, such as `<msqrt>` and `log` with
+/// the base as a subscript, others are applied to their arguments.
+fn call(name: &str, args: &[Box<dyn ASTSemanticExpression>]) -> (String, u8) {
+    let args = list(args);
+    let text = match (name, args.as_slice()) {
+        ("sqrt", [x]) => format!("<msqrt>{}</msqrt>", x),
+        ("abs", [x]) => fenced("|", x, "|"),
+        ("ceil", [x]) => fenced("&#x2308;", x, "&#x2309;"),
+        ("floor", [x]) => fenced("&#x230A;", x, "&#x230B;"),
+        ("log2", [x]) => function(
+            "<msub><mi>log</mi><mn>2</mn></msub>",
+            std::slice::from_ref(x),
+        ),
+        ("log10", [x]) => function(
+            "<msub><mi>log</mi><mn>10</mn></msub>",
+            std::slice::from_ref(x),
+        ),
+        ("log", [x, base]) => function(
+            &format!("<msub><mi>log</mi>{}</msub>", base),
+            std::slice::from_ref(x),
+        ),
+        ("pow2", [x]) => return (format!("<msup><mn>2</mn>{}</msup>", x), POWER),
+        ("pow10", [x]) => return (format!("<msup><mn>10</mn>{}</msup>", x), POWER),
+        (name, args) => function(&identifier(name), args),
+    };
+
+    (text, ATOM)
+}
+
+fn fenced(open: &str, content: &str, close: &str) -> String {
+    format!(
+        "<mrow><mo>{}</mo>{}<mo>{}</mo></mrow>",
+        open, content, close
+    )
+}
+
+/// A function applied to its arguments, with the invisible function
+/// application operator between them.
+fn function(name: &str, args: &[String]) -> String {
+    format!(
+        "<mrow>{}<mo>&#x2061;</mo>{}</mrow>",
+        name,
+        parenthesize(&separated(args))
+    )
+}
+
+fn list(nodes: &[Box<dyn ASTSemanticExpression>]) -> Vec<String> {
+    nodes.iter().map(|node| mathml(node.as_ref()).0).collect()
+}
+
+/// Greek letters by name and the part after the first `_` as a subscript,
+/// e.g. `<msub><mi>x</mi><mn>1</mn></msub>`.
+fn identifier(name: &str) -> String {
+    if let Some((name, subscript)) = name.split_once('_') {
+        if !name.is_empty() && !subscript.is_empty() {
+            let subscript = match subscript.chars().all(|ch| ch.is_ascii_digit()) {
+                true => format!("<mn>{}</mn>", subscript),
+                false => format!("<mi>{}</mi>", subscript),
+            };
+
+            return format!("<msub>{}{}</msub>", identifier(name), subscript);
+        }
+    }
+
+    match GREEK_LETTERS.iter().find(|(letter, _)| *letter == name) {
+        Some((_, ch)) => format!("<mi>{}</mi>", ch),
+        None => format!("<mi>{}</mi>", name),
+    }
+}
+
+/// Units upright, with negative exponents after a `/`.
+fn unit(unit: &UnitExpr) -> String {
+    unit.iter()
+        .enumerate()
+        .map(|(i, (name, exponent))| {
+            let (separator, exponent) = match (i, *exponent) {
+                (0, exponent) => ("", exponent),
+                (_, exponent) if exponent < 0 => ("<mo>/</mo>", -exponent),
+                (_, exponent) => ("<mo>&#x2062;</mo>", exponent),
+            };
+            let name = format!("<mi mathvariant=\"normal\">{}</mi>", name);
+
+            match exponent {
+                1 => format!("{}{}", separator, name),
+                exponent => format!("{}<msup>{}<mn>{}</mn></msup>", separator, name, exponent),
+            }
+        })
+        .collect()
+}
+
+/// Literals as the formatter writes them, fractions as `<mfrac>` and the
+/// imaginary unit as an identifier.
+fn number(value: &Value) -> (String, u8) {
+    let (text, precedence) = format::number(value);
+    let (sign, digits) = match text.strip_prefix('-') {
+        Some(digits) => ("<mo>&#x2212;</mo>", digits),
+        None => ("", text.as_str()),
+    };
+    let (content, precedence) = match digits.split_once('/') {
+        Some((numerator, denominator)) => (
+            format!(
+                "<mfrac><mn>{}</mn><mn>{}</mn></mfrac>",
+                numerator, denominator
+            ),
+            if sign.is_empty() { POWER } else { FACTOR },
+        ),
+        None => match (digits.split_once(" + "), digits.split_once(" - ")) {
+            (Some((re, im)), _) => (
+                format!("<mrow>{}<mo>+</mo>{}</mrow>", literal(re), literal(im)),
+                precedence,
+            ),
+            (_, Some((re, im))) => (
+                format!(
+                    "<mrow>{}<mo>&#x2212;</mo>{}</mrow>",
+                    literal(re),
+                    literal(im)
+                ),
+                precedence,
+            ),
+            _ => (literal(digits), precedence),
+        },
+    };
+
+    match sign {
+        "" => (content, precedence),
+        sign => (format!("<mrow>{}{}</mrow>", sign, content), precedence),
+    }
+}
+
+/// Digits as a number, with a trailing `i` as the imaginary unit.
+fn literal(text: &str) -> String {
+    match text.strip_suffix('i') {
+        Some(digits) => format!(
+            "<mrow><mn>{}</mn><mo>&#x2062;</mo><mi>i</mi></mrow>",
+            digits
+        ),
+        None => format!("<mn>{}</mn>", text),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::Parser;
+    use crate::tokenizer::Tokenizer;
+
+    use super::{expression, statement};
+
+    fn mathml(content: &str) -> String {
+        let tokens = Tokenizer::new(content).try_collect().unwrap();
+        let node = Parser::new(tokens)
+            .implicit_multiplication(true)
+            .parse_expression()
+            .unwrap();
+
+        expression(node.as_ref())
+    }
+
+    fn math(content: &str) -> String {
+        format!(
+            "<math xmlns=\"http://www.w3.org/1998/Math/MathML\">{}</math>",
+            content
+        )
+    }
+
+    #[test]
+    fn test_expression() {
+        let cases = [
+            (
+                "(a + b) / 2",
+                "<mfrac><mrow><mi>a</mi><mo>+</mo><mi>b</mi></mrow><mn>2</mn></mfrac>",
+            ),
+            (
+                "(x - 1)^2",
+                "<msup><mrow><mo>(</mo><mrow><mi>x</mi><mo>&#x2212;</mo><mn>1</mn></mrow>\
+                 <mo>)</mo></mrow><mn>2</mn></msup>",
+            ),
+            (
+                "sqrt(2 pi)",
+                "<msqrt><mrow><mn>2</mn><mo>&#x22C5;</mo><mi>π</mi></mrow></msqrt>",
+            ),
+            (
+                "-x_1 & 3",
+                "<mrow><mrow><mo>&#x2212;</mo><msub><mi>x</mi><mn>1</mn></msub></mrow>\
+                 <mo>&amp;</mo><mn>3</mn></mrow>",
+            ),
+            (
+                "max(a, b)",
+                "<mrow><mi>max</mi><mo>&#x2061;</mo>\
+                 <mrow><mo>(</mo><mi>a</mi><mo>,</mo><mi>b</mi><mo>)</mo></mrow></mrow>",
+            ),
+            (
+                "[1, 2; 3, 4]",
+                "<mrow><mo>[</mo><mtable><mtr><mtd><mn>1</mn></mtd><mtd><mn>2</mn></mtd></mtr>\
+                 <mtr><mtd><mn>3</mn></mtd><mtd><mn>4</mn></mtd></mtr></mtable><mo>]</mo></mrow>",
+            ),
+            (
+                "3 + 4i",
+                "<mrow><mn>3</mn><mo>+</mo>\
+                 <mrow><mn>4</mn><mo>&#x2062;</mo><mi>i</mi></mrow></mrow>",
+            ),
+            (
+                "5 km/h",
+                "<mrow><mn>5</mn><mspace width=\"0.167em\"/><mi mathvariant=\"normal\">km</mi>\
+                 <mo>/</mo><mi mathvariant=\"normal\">h</mi></mrow>",
+            ),
+            (
+                "if x < 0 then -x else x",
+                "<mrow><mo>{</mo><mtable columnalign=\"left\">\
+                 <mtr><mtd><mrow><mo>&#x2212;</mo><mi>x</mi></mrow></mtd>\
+                 <mtd><mrow><mtext>if&#xA0;</mtext><mrow><mi>x</mi><mo>&lt;</mo><mn>0</mn></mrow></mrow></mtd></mtr>\
+                 <mtr><mtd><mi>x</mi></mtd><mtd><mtext>otherwise</mtext></mtd></mtr></mtable></mrow>",
+            ),
+        ];
+
+        for (content, expected) in cases {
+            assert_eq!(mathml(content), math(expected), "{}", content);
+        }
+    }
+
+    #[test]
+    fn test_statement() {
+        let tokens = Tokenizer::new("f(x) = x^2; y -= 1").try_collect().unwrap();
+        let node = Parser::new(tokens).parse().unwrap();
+
+        assert_eq!(
+            statement(node.as_ref()),
+            math(
+                "<mrow><mrow><mi>f</mi><mo>&#x2061;</mo><mrow><mo>(</mo><mi>x</mi><mo>)</mo></mrow>\
+                 <mo>=</mo><msup><mi>x</mi><mn>2</mn></msup></mrow><mo>;</mo>\
+                 <mrow><mi>y</mi><mo>=</mo><mrow><mi>y</mi><mo>&#x2212;</mo><mn>1</mn></mrow></mrow></mrow>"
+            )
+        );
+    }
+}
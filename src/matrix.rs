@@ -0,0 +1,448 @@
+use crate::ast::{BinaryOpType, UnaryOpType};
+use crate::error::InterpreterError;
+use crate::value::Value;
+
+const JACOBI_SWEEPS: usize = 64;
+
+/// Rows of a matrix, all of the same length.
+pub type Matrix = Vec<Vec<Value>>;
+
+/// Reads a non-empty list of equally long lists of scalars as a matrix.
+pub fn to_matrix(name: &str, value: &Value) -> Result<Matrix, InterpreterError> {
+    let error =
+        || InterpreterError::TypeError(format!("{} expects a matrix, found {}", name, value));
+
+    let rows = match value {
+        Value::List(rows) if !rows.is_empty() => rows,
+        _ => return Err(error()),
+    };
+
+    let mut matrix = vec![];
+
+    for row in rows.iter() {
+        match row {
+            Value::List(row)
+                if !row.is_empty()
+                    && row.len() == rows_len(rows)
+                    && row.iter().all(|value| !matches!(value, Value::List(_))) =>
+            {
+                matrix.push(row.clone())
+            }
+            _ => return Err(error()),
+        }
+    }
+
+    Ok(matrix)
+}
+
+fn rows_len(rows: &[Value]) -> usize {
+    match rows.first() {
+        Some(Value::List(row)) => row.len(),
+        _ => 0,
+    }
+}
+
+pub fn to_square_matrix(name: &str, value: &Value) -> Result<Matrix, InterpreterError> {
+    let matrix = to_matrix(name, value)?;
+
+    if matrix.len() != matrix[0].len() {
+        return Err(InterpreterError::TypeError(format!(
+            "{} expects a square matrix, found shape {:?}",
+            name,
+            value.shape()
+        )));
+    }
+
+    Ok(matrix)
+}
+
+pub fn from_matrix(matrix: Matrix) -> Value {
+    Value::List(matrix.into_iter().map(Value::List).collect())
+}
+
+pub fn identity(n: usize) -> Matrix {
+    (0..n)
+        .map(|i| {
+            (0..n)
+                .map(|j| Value::Number(if i == j { 1. } else { 0. }))
+                .collect()
+        })
+        .collect()
+}
+
+pub fn zeros(rows: usize, columns: usize) -> Matrix {
+    vec![vec![Value::Number(0.); columns]; rows]
+}
+
+pub fn transpose(matrix: &Matrix) -> Matrix {
+    (0..matrix[0].len())
+        .map(|j| matrix.iter().map(|row| row[j].clone()).collect())
+        .collect()
+}
+
+fn dot(left: &[Value], right: &[Value]) -> Result<Value, InterpreterError> {
+    let mut result: Option<Value> = None;
+
+    for (lvalue, rvalue) in left.iter().zip(right) {
+        let product = BinaryOpType::MUL.apply(lvalue, rvalue)?;
+
+        result = Some(match result {
+            Some(sum) => BinaryOpType::ADD.apply(&sum, &product)?,
+            None => product,
+        });
+    }
+
+    Ok(result.unwrap_or(Value::Number(0.)))
+}
+
+/// Matrix product. A vector on the left is a row and a vector on the right is
+/// a column, so two vectors give their dot product.
+pub fn matmul(left: &Value, right: &Value) -> Result<Value, InterpreterError> {
+    let operand = |value: &Value| match value.shape().len() {
+        1 | 2 => Ok(value.shape().len() == 1),
+        _ => Err(InterpreterError::TypeError(format!(
+            "{:?} expects vectors or matrices, found {}",
+            BinaryOpType::MATMUL,
+            value
+        ))),
+    };
+    let lvector = operand(left)?;
+    let rvector = operand(right)?;
+
+    let lmatrix = match (lvector, left) {
+        (true, Value::List(values)) => vec![values.clone()],
+        _ => to_matrix("@", left)?,
+    };
+    let rmatrix = match (rvector, right) {
+        (true, Value::List(values)) => values.iter().map(|value| vec![value.clone()]).collect(),
+        _ => to_matrix("@", right)?,
+    };
+
+    if lmatrix[0].len() != rmatrix.len() {
+        return Err(InterpreterError::ShapeMismatch(
+            format!("{:?}", left.shape()),
+            format!("{:?}", right.shape()),
+        ));
+    }
+
+    let columns = transpose(&rmatrix);
+    let mut result = vec![];
+
+    for row in lmatrix.iter() {
+        let mut values = vec![];
+
+        for column in columns.iter() {
+            values.push(dot(row, column)?);
+        }
+
+        result.push(values);
+    }
+
+    Ok(match (lvector, rvector) {
+        (true, true) => result[0][0].clone(),
+        (true, false) => Value::List(result.swap_remove(0)),
+        (false, true) => Value::List(result.into_iter().map(|mut row| row.remove(0)).collect()),
+        (false, false) => from_matrix(result),
+    })
+}
+
+fn magnitude(value: &Value) -> f64 {
+    match value {
+        Value::Complex(z) => z.norm(),
+        Value::Quantity(value) => value.value().abs(),
+        Value::Uncertain(value) => value.value().abs(),
+        Value::Dual(value) => value.value().abs(),
+        value => value.to_f64().abs(),
+    }
+}
+
+/// Reduces the first `n` columns of `matrix` to the identity with
+/// Gauss-Jordan elimination and partial pivoting, applying the same row
+/// operations to any further columns. Returns the determinant of the leading
+/// `n` by `n` block, or `None` when it is singular.
+fn gauss_jordan(matrix: &mut Matrix, n: usize) -> Result<Option<Value>, InterpreterError> {
+    let mut determinant: Option<Value> = None;
+
+    for column in 0..n {
+        let pivot = (column..n)
+            .max_by(|a, b| {
+                magnitude(&matrix[*a][column]).total_cmp(&magnitude(&matrix[*b][column]))
+            })
+            .unwrap();
+
+        if magnitude(&matrix[pivot][column]) == 0. {
+            return Ok(None);
+        }
+
+        let value = matrix[pivot][column].clone();
+
+        determinant = Some(match determinant {
+            Some(determinant) => BinaryOpType::MUL.apply(&determinant, &value)?,
+            None => value.clone(),
+        });
+
+        if pivot != column {
+            matrix.swap(pivot, column);
+            determinant = determinant
+                .map(|value| UnaryOpType::MINUS.apply(&value))
+                .transpose()?;
+        }
+
+        for element in matrix[column].iter_mut() {
+            *element = BinaryOpType::DIV.apply(element, &value)?;
+        }
+
+        let pivot_row = matrix[column].clone();
+
+        for (i, row) in matrix.iter_mut().enumerate() {
+            if i == column || magnitude(&row[column]) == 0. {
+                continue;
+            }
+
+            let factor = row[column].clone();
+
+            for (element, pivot) in row.iter_mut().zip(pivot_row.iter()) {
+                *element =
+                    BinaryOpType::SUB.apply(element, &BinaryOpType::MUL.apply(&factor, pivot)?)?;
+            }
+        }
+    }
+
+    Ok(determinant)
+}
+
+fn singular() -> InterpreterError {
+    InterpreterError::DomainError("singular matrix".to_string())
+}
+
+pub fn determinant(matrix: &Matrix) -> Result<Value, InterpreterError> {
+    let mut matrix = matrix.clone();
+    let n = matrix.len();
+
+    Ok(gauss_jordan(&mut matrix, n)?.unwrap_or(Value::Number(0.)))
+}
+
+pub fn inverse(matrix: &Matrix) -> Result<Matrix, InterpreterError> {
+    let n = matrix.len();
+    let mut augmented: Matrix = matrix
+        .iter()
+        .zip(identity(n))
+        .map(|(row, unit)| row.iter().cloned().chain(unit).collect())
+        .collect();
+
+    gauss_jordan(&mut augmented, n)?.ok_or_else(singular)?;
+
+    Ok(augmented
+        .into_iter()
+        .map(|row| row.into_iter().skip(n).collect())
+        .collect())
+}
+
+/// Solves `A x = b`, where `b` is a vector or a matrix with one column per
+/// right-hand side.
+pub fn solve(matrix: &Matrix, rhs: &Value) -> Result<Value, InterpreterError> {
+    let n = matrix.len();
+    let vector = rhs.shape().len() == 1;
+    let columns = match (vector, rhs) {
+        (true, Value::List(values)) => values.iter().map(|value| vec![value.clone()]).collect(),
+        _ => to_matrix("linsolve", rhs)?,
+    };
+
+    if columns.len() != n {
+        return Err(InterpreterError::ShapeMismatch(
+            format!("{:?}", [n, n]),
+            format!("{:?}", rhs.shape()),
+        ));
+    }
+
+    let mut augmented: Matrix = matrix
+        .iter()
+        .zip(columns)
+        .map(|(row, rhs)| row.iter().cloned().chain(rhs).collect())
+        .collect();
+
+    gauss_jordan(&mut augmented, n)?.ok_or_else(singular)?;
+
+    let solution: Matrix = augmented
+        .into_iter()
+        .map(|row| row.into_iter().skip(n).collect())
+        .collect();
+
+    Ok(if vector {
+        Value::List(solution.into_iter().map(|mut row| row.remove(0)).collect())
+    } else {
+        from_matrix(solution)
+    })
+}
+
+/// Eigenvalues of a real symmetric matrix in ascending order, computed with
+/// the cyclic Jacobi method.
+pub fn eigenvalues(matrix: &Matrix) -> Result<Vec<f64>, InterpreterError> {
+    let n = matrix.len();
+    let mut a = vec![vec![0.; n]; n];
+
+    for (i, row) in matrix.iter().enumerate() {
+        for (j, value) in row.iter().enumerate() {
+            if let Value::Complex(_) | Value::Quantity(_) = value {
+                return Err(InterpreterError::TypeError(format!(
+                    "eig expects real elements, found {}",
+                    value
+                )));
+            }
+
+            a[i][j] = value.to_f64();
+        }
+    }
+
+    let norm = a.iter().flatten().map(|x| x * x).sum::<f64>().sqrt();
+
+    if (0..n).any(|i| (0..i).any(|j| (a[i][j] - a[j][i]).abs() > f64::EPSILON * norm.max(1.))) {
+        return Err(InterpreterError::DomainError(
+            "eig expects a symmetric matrix".to_string(),
+        ));
+    }
+
+    for _ in 0..JACOBI_SWEEPS {
+        let off_diagonal = (0..n)
+            .flat_map(|i| (0..n).filter(move |j| *j != i).map(move |j| (i, j)))
+            .map(|(i, j)| a[i][j] * a[i][j])
+            .sum::<f64>()
+            .sqrt();
+
+        if off_diagonal <= f64::EPSILON * norm {
+            break;
+        }
+
+        for p in 0..n {
+            for q in p + 1..n {
+                if a[p][q] == 0. {
+                    continue;
+                }
+
+                // Rotate in the (p, q) plane so that `a[p][q]` vanishes.
+                let theta = (a[q][q] - a[p][p]) / (2. * a[p][q]);
+                let t = if theta == 0. {
+                    1.
+                } else {
+                    theta.signum() / (theta.abs() + (theta * theta + 1.).sqrt())
+                };
+                let c = 1. / (t * t + 1.).sqrt();
+                let s = t * c;
+
+                for row in a.iter_mut() {
+                    (row[p], row[q]) = (c * row[p] - s * row[q], s * row[p] + c * row[q]);
+                }
+
+                let (upper, lower) = a.split_at_mut(q);
+
+                for (x, y) in upper[p].iter_mut().zip(lower[0].iter_mut()) {
+                    (*x, *y) = (c * *x - s * *y, s * *x + c * *y);
+                }
+            }
+        }
+    }
+
+    let mut values: Vec<f64> = (0..n).map(|i| a[i][i]).collect();
+
+    values.sort_by(f64::total_cmp);
+
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::InterpreterError;
+    use crate::value::Value;
+
+    use super::{determinant, eigenvalues, inverse, matmul, solve, to_matrix, Matrix};
+
+    fn matrix(rows: &[&[f64]]) -> Matrix {
+        rows.iter()
+            .map(|row| row.iter().map(|x| Value::Number(*x)).collect())
+            .collect()
+    }
+
+    fn vector(values: &[f64]) -> Value {
+        Value::List(values.iter().map(|x| Value::Number(*x)).collect())
+    }
+
+    fn value(rows: &[&[f64]]) -> Value {
+        super::from_matrix(matrix(rows))
+    }
+
+    #[test]
+    fn test_to_matrix() {
+        assert!(to_matrix("det", &value(&[&[1., 2.], &[3., 4.]])).is_ok());
+        assert!(to_matrix("det", &vector(&[1., 2.])).is_err());
+        assert!(to_matrix("det", &Value::List(vec![vector(&[1., 2.]), vector(&[3.])])).is_err());
+    }
+
+    #[test]
+    fn test_matmul() {
+        let a = value(&[&[1., 2.], &[3., 4.]]);
+
+        assert_eq!(
+            matmul(&a, &value(&[&[5., 6.], &[7., 8.]])).unwrap(),
+            value(&[&[19., 22.], &[43., 50.]])
+        );
+        assert_eq!(matmul(&a, &vector(&[1., 1.])).unwrap(), vector(&[3., 7.]));
+        assert_eq!(matmul(&vector(&[1., 1.]), &a).unwrap(), vector(&[4., 6.]));
+        assert_eq!(matmul(&vector(&[1., 2.]), &vector(&[3., 4.])).unwrap(), 11.);
+        assert!(matches!(
+            matmul(&a, &vector(&[1., 2., 3.])),
+            Err(InterpreterError::ShapeMismatch(_, _))
+        ));
+        assert!(matches!(
+            matmul(&a, &Value::Number(2.)),
+            Err(InterpreterError::TypeError(_))
+        ));
+    }
+
+    #[test]
+    fn test_determinant_and_inverse() {
+        let a = matrix(&[&[4., 7.], &[2., 6.]]);
+
+        assert_eq!(determinant(&a).unwrap(), 10.);
+        assert_eq!(determinant(&matrix(&[&[0., 1.], &[1., 0.]])).unwrap(), -1.);
+        assert_eq!(determinant(&matrix(&[&[1., 2.], &[2., 4.]])).unwrap(), 0.);
+        assert_eq!(
+            inverse(&matrix(&[&[2., 1.], &[1., 1.]])).unwrap(),
+            matrix(&[&[1., -1.], &[-1., 2.]])
+        );
+        assert!(matches!(
+            inverse(&matrix(&[&[1., 2.], &[2., 4.]])),
+            Err(InterpreterError::DomainError(_))
+        ));
+    }
+
+    #[test]
+    fn test_solve() {
+        let a = matrix(&[&[2., 1.], &[1., 3.]]);
+
+        assert_eq!(solve(&a, &vector(&[3., 5.])).unwrap(), vector(&[0.8, 1.4]));
+        assert!(matches!(
+            solve(&a, &vector(&[1., 2., 3.])),
+            Err(InterpreterError::ShapeMismatch(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_eigenvalues() {
+        let values = eigenvalues(&matrix(&[&[2., 1.], &[1., 2.]])).unwrap();
+
+        assert!((values[0] - 1.).abs() < 1e-12 && (values[1] - 3.).abs() < 1e-12);
+
+        let values =
+            eigenvalues(&matrix(&[&[2., -1., 0.], &[-1., 2., -1.], &[0., -1., 2.]])).unwrap();
+        let expected = [2. - 2f64.sqrt(), 2., 2. + 2f64.sqrt()];
+
+        for (value, expected) in values.iter().zip(expected) {
+            assert!((value - expected).abs() < 1e-12);
+        }
+
+        assert!(matches!(
+            eigenvalues(&matrix(&[&[1., 2.], &[3., 4.]])),
+            Err(InterpreterError::DomainError(_))
+        ));
+    }
+}
@@ -2,25 +2,111 @@ use std::iter::Peekable;
 use std::vec::IntoIter;
 
 use crate::ast::{
-    ASTSemanticExpression, AssignNode, BinaryOpNode, BinaryOpType, NumberNode, StatementListNode,
-    UnaryOpNode, UnaryOpType, VarNode,
+    ASTSemanticExpression, ASTSemanticStatement, AssignNode, BinaryOpNode, BinaryOpType, CallNode,
+    CompareNode, CompareOpType, ConditionalNode, ConvertNode, DiffNode, FunctionNode, IndexNode,
+    IntervalNode, LambdaNode, ListNode, NodeKind, NumberNode, PercentChangeNode, PolyNode,
+    QuantityNode, SeriesNode, SimplifyNode, SolveNode, StatementListNode, UnaryOpNode, UnaryOpType,
+    UnitExpr, VarNode,
 };
+use crate::builtins;
 use crate::error::InterpreterError;
 use crate::token::Token;
+use crate::units;
+use crate::value::{Complex, NumberMode, Value};
+
+/// The two sides of `lhs = rhs`.
+type Equation = (
+    Box<dyn ASTSemanticExpression>,
+    Box<dyn ASTSemanticExpression>,
+);
 
 pub struct Parser {
     tokens: Peekable<IntoIter<Token>>,
+    previous_token: Option<Token>,
+    implicit_multiplication: bool,
+    calculator_mode: bool,
+    number_mode: NumberMode,
+    functions: Vec<String>,
+    variables: Vec<String>,
 }
 
 impl Parser {
     pub fn new(tokens: Vec<Token>) -> Self {
         Self {
             tokens: tokens.into_iter().peekable(),
+            previous_token: None,
+            implicit_multiplication: false,
+            calculator_mode: false,
+            number_mode: NumberMode::FLOAT,
+            functions: vec![],
+            variables: vec![],
         }
     }
 
+    /// Enables juxtaposition as multiplication, e.g. `2pi`, `3x` or
+    /// `(a+b)(a-b)`. Implicit multiplication binds tighter than `*` and `/`,
+    /// so `1/2x` is parsed as `1/(2*x)`.
+    pub fn implicit_multiplication(mut self, enabled: bool) -> Self {
+        self.implicit_multiplication = enabled;
+        self
+    }
+
+    /// Enables calculator-style percentages, where `x + 10%` means `x * 1.1`
+    /// and `x - 10%` means `x * 0.9`.
+    pub fn calculator_mode(mut self, enabled: bool) -> Self {
+        self.calculator_mode = enabled;
+        self
+    }
+
+    /// Selects how number literals are represented, e.g. as exact decimals.
+    pub fn number_mode(mut self, number_mode: NumberMode) -> Self {
+        self.number_mode = number_mode;
+        self
+    }
+
+    /// Names of the user functions defined so far, which can be called like
+    /// built-in functions when implicit multiplication is enabled.
+    pub fn user_functions(mut self, functions: Vec<String>) -> Self {
+        self.functions = functions;
+        self
+    }
+
+    /// Names of the user functions known so far, including the ones defined
+    /// by the parsed statements.
+    pub fn functions(&self) -> &[String] {
+        &self.functions
+    }
+
+    /// Names of the user variables defined so far, which a unit after a
+    /// number does not extend into, `5 km * h` multiplies by `h`.
+    pub fn user_variables(mut self, variables: Vec<String>) -> Self {
+        self.variables = variables;
+        self
+    }
+
+    /// Names of the user variables known so far, including the ones assigned
+    /// by the parsed statements.
+    pub fn variables(&self) -> &[String] {
+        &self.variables
+    }
+
     pub fn parse(&mut self) -> Result<Box<StatementListNode>, InterpreterError> {
-        self.statement_list()
+        let node = self.statement_list()?;
+
+        match self.next_token() {
+            Some(token) => Err(InterpreterError::UnexpectedToken(token)),
+            None => Ok(node),
+        }
+    }
+
+    /// Parses the tokens as a single expression, such as `x^2 / 2`.
+    pub fn parse_expression(&mut self) -> Result<Box<dyn ASTSemanticExpression>, InterpreterError> {
+        let node = self.expr()?;
+
+        match self.next_token() {
+            Some(token) => Err(InterpreterError::UnexpectedToken(token)),
+            None => Ok(node),
+        }
     }
 
     pub fn variable(&mut self) -> Result<Box<VarNode>, InterpreterError> {
@@ -35,22 +121,171 @@ impl Parser {
     }
 
     pub fn assignment_statement(&mut self) -> Result<Box<AssignNode>, InterpreterError> {
-        let var_node = self.variable()?;
+        let mut var_nodes = vec![self.variable()?];
+
+        while let Some(Token::COMMA) = self.peek_token() {
+            self.next_token();
+            var_nodes.push(self.variable()?);
+        }
+
+        let token = self.next_token().ok_or(InterpreterError::EOF)?;
+        let op_type = match token {
+            Token::ASSIGN => None,
+            Token::ADDASSIGN => Some(BinaryOpType::ADD),
+            Token::SUBASSIGN => Some(BinaryOpType::SUB),
+            Token::MULASSIGN => Some(BinaryOpType::MUL),
+            Token::DIVASSIGN => Some(BinaryOpType::DIV),
+            Token::POWASSIGN => Some(BinaryOpType::POW),
+            _ => return Err(InterpreterError::UnexpectedToken(token)),
+        };
+
+        if let Some(op_type) = op_type {
+            if var_nodes.len() > 1 {
+                return Err(InterpreterError::InvalidSyntax(
+                    "compound assignment with multiple targets".to_string(),
+                ));
+            }
+
+            let var_node = var_nodes.pop().unwrap();
+
+            self.variables.push(var_node.name().clone());
+
+            return Ok(Box::new(AssignNode::compound(
+                *var_node,
+                self.expr()?,
+                op_type,
+            )));
+        }
+
+        let mut expr_nodes = vec![self.expr()?];
+
+        while let Some(Token::COMMA) = self.peek_token() {
+            self.next_token();
+            expr_nodes.push(self.expr()?);
+        }
+
+        if var_nodes.len() != expr_nodes.len() {
+            return Err(InterpreterError::InvalidSyntax(format!(
+                "cannot assign {} values to {} variables",
+                expr_nodes.len(),
+                var_nodes.len()
+            )));
+        }
+
+        for (i, var_node) in var_nodes.iter().enumerate() {
+            if var_nodes[..i]
+                .iter()
+                .any(|other| other.name() == var_node.name())
+            {
+                return Err(InterpreterError::InvalidSyntax(format!(
+                    "variable {} is assigned more than once",
+                    var_node.name()
+                )));
+            }
+        }
+
+        self.variables
+            .extend(var_nodes.iter().map(|var_node| var_node.name().clone()));
+
+        if var_nodes.len() == 1 {
+            Ok(Box::new(AssignNode::new(
+                *var_nodes.pop().unwrap(),
+                expr_nodes.pop().unwrap(),
+            )))
+        } else {
+            Ok(Box::new(AssignNode::multiple(
+                var_nodes.into_iter().map(|node| *node).collect(),
+                expr_nodes,
+            )))
+        }
+    }
+
+    pub fn const_statement(&mut self) -> Result<Box<AssignNode>, InterpreterError> {
+        self.next_token();
+
+        let node = self.assignment_statement()?;
+
+        if node.is_compound() {
+            return Err(InterpreterError::InvalidSyntax(
+                "compound assignment in const declaration".to_string(),
+            ));
+        }
+
+        Ok(Box::new(node.constant()))
+    }
+
+    /// Parses a function definition, `f(x, y) = expr`.
+    pub fn function_definition(&mut self) -> Result<Box<FunctionNode>, InterpreterError> {
+        let name = self.variable()?.name().clone();
+        let params = self.params()?;
+
+        self.expect(Token::ASSIGN)?;
+        self.functions.push(name.clone());
+
+        let body = self.scoped(&params, Self::expr)?;
+
+        Ok(Box::new(FunctionNode::new(name, params, body)))
+    }
+
+    /// Parses a memoized function definition, `memo f(x) = expr`.
+    pub fn memo_definition(&mut self) -> Result<Box<FunctionNode>, InterpreterError> {
+        self.next_token();
+
+        if self.peek_second_token() != Some(Token::LPAREN) {
+            return Err(InterpreterError::InvalidSyntax(
+                "memo without a function definition".to_string(),
+            ));
+        }
+
+        Ok(Box::new(self.function_definition()?.memoized()))
+    }
+
+    /// Parses a parameter list, `(x, y)`.
+    fn params(&mut self) -> Result<Vec<String>, InterpreterError> {
+        let mut params: Vec<String> = vec![];
+
+        self.expect(Token::LPAREN)?;
+
+        if let Some(Token::RPAREN) = self.peek_token() {
+            self.next_token();
+            return Ok(params);
+        }
+
+        loop {
+            let param = self.variable()?.name().clone();
 
-        self.next_token()
-            .ok_or(InterpreterError::EOF)
-            .and_then(move |token| match token {
-                Token::ASSIGN => Ok(Box::new(AssignNode::new(var_node, self.expr()?))),
-                _ => Err(InterpreterError::UnexpectedToken(token)),
-            })
+            if params.contains(&param) {
+                return Err(InterpreterError::InvalidSyntax(format!(
+                    "parameter {} is declared more than once",
+                    param
+                )));
+            }
+
+            params.push(param);
+
+            match self.next_token() {
+                Some(Token::COMMA) => continue,
+                Some(Token::RPAREN) => return Ok(params),
+                Some(token) => return Err(InterpreterError::UnexpectedToken(token)),
+                None => return Err(InterpreterError::EOF),
+            }
+        }
     }
 
-    pub fn statement(&mut self) -> Result<Box<AssignNode>, InterpreterError> {
-        self.assignment_statement()
+    pub fn statement(&mut self) -> Result<Box<dyn ASTSemanticStatement>, InterpreterError> {
+        let definition = self.peek_second_token() == Some(Token::LPAREN);
+        let node: Box<dyn ASTSemanticStatement> = match self.peek_token() {
+            Some(Token::CONST) => self.const_statement()?,
+            Some(Token::MEMO) => self.memo_definition()?,
+            Some(Token::ID(_)) if definition => self.function_definition()?,
+            _ => self.assignment_statement()?,
+        };
+
+        Ok(node)
     }
 
     pub fn statement_list(&mut self) -> Result<Box<StatementListNode>, InterpreterError> {
-        let mut nodes: Vec<Box<AssignNode>> = vec![self.statement()?];
+        let mut nodes: Vec<Box<dyn ASTSemanticStatement>> = vec![self.statement()?];
 
         while let Some(token) = self.peek_token() {
             match token {
@@ -68,28 +303,119 @@ impl Parser {
     }
 
     pub fn factor(&mut self) -> Result<Box<dyn ASTSemanticExpression>, InterpreterError> {
+        if self.signed_literal_ahead() {
+            self.next_token();
+
+            match self.tokens.peek_mut() {
+                Some(Token::NUMBER(value)) => *value = -*value,
+                Some(Token::INTEGER(value)) => *value = value.neg(),
+                Some(Token::DECIMAL(text)) => *text = format!("-{}", text),
+                _ => unreachable!(),
+            }
+
+            return self.power();
+        }
+
+        match self.peek_token() {
+            Some(Token::PLUS) => {
+                self.next_token();
+                Ok(Box::new(UnaryOpNode::new(
+                    self.factor()?,
+                    UnaryOpType::PLUS,
+                )))
+            }
+            Some(Token::MINUS) => {
+                self.next_token();
+                Ok(Box::new(UnaryOpNode::new(
+                    self.factor()?,
+                    UnaryOpType::MINUS,
+                )))
+            }
+            Some(Token::BITNOT) => {
+                self.next_token();
+                Ok(Box::new(UnaryOpNode::new(
+                    self.factor()?,
+                    UnaryOpType::BITNOT,
+                )))
+            }
+            _ => self.power(),
+        }
+    }
+
+    pub fn power(&mut self) -> Result<Box<dyn ASTSemanticExpression>, InterpreterError> {
+        let base = self.postfix()?;
+
+        if let Some(Token::POW) = self.peek_token() {
+            self.next_token();
+            Ok(Box::new(BinaryOpNode::new(
+                base,
+                self.factor()?,
+                BinaryOpType::POW,
+            )))
+        } else {
+            Ok(base)
+        }
+    }
+
+    pub fn postfix(&mut self) -> Result<Box<dyn ASTSemanticExpression>, InterpreterError> {
+        let mut node = self.atom()?;
+
+        while let Some(token) = self.peek_token().cloned() {
+            let op_type = match token {
+                Token::FACTORIAL => UnaryOpType::FACTORIAL,
+                Token::PERCENT if !self.modulo_ahead() => UnaryOpType::PERCENT,
+                Token::LBRACKET => {
+                    self.next_token();
+
+                    let index = self.expr()?;
+
+                    self.expect(Token::RBRACKET)?;
+                    node = Box::new(IndexNode::new(node, index));
+                    continue;
+                }
+                _ => break,
+            };
+
+            self.next_token();
+            node = Box::new(UnaryOpNode::new(node, op_type));
+        }
+
+        Ok(node)
+    }
+
+    pub fn atom(&mut self) -> Result<Box<dyn ASTSemanticExpression>, InterpreterError> {
         if let Some(token) = self.peek_token() {
             match token {
-                Token::PLUS => {
+                Token::NUMBER(value) => {
+                    let value = *value;
+                    let node = Box::new(NumberNode::new(self.number_mode.literal(value)));
+
                     self.next_token();
-                    Ok(Box::new(UnaryOpNode::new(
-                        self.factor()?,
-                        UnaryOpType::PLUS,
-                    )))
+                    self.unit_suffix(node)
                 }
-                Token::MINUS => {
+                Token::INTEGER(value) => {
+                    let value = value.clone();
+                    let node = Box::new(NumberNode::new(self.number_mode.integer(value)));
+
                     self.next_token();
-                    Ok(Box::new(UnaryOpNode::new(
-                        self.factor()?,
-                        UnaryOpType::MINUS,
-                    )))
+                    self.unit_suffix(node)
                 }
-                Token::NUMBER(value) => {
-                    let node = Box::new(NumberNode::new(value.clone()));
+                Token::DECIMAL(text) => {
+                    let text = text.clone();
+                    let node = Box::new(NumberNode::new(self.number_mode.decimal(&text)));
+
+                    self.next_token();
+                    self.unit_suffix(node)
+                }
+                Token::IMAGINARY(value) => {
+                    let node = Box::new(NumberNode::new(Value::from_complex(Complex::new(
+                        0., *value,
+                    ))));
 
                     self.next_token();
                     Ok(node)
                 }
+                Token::LBRACKET => self.list(),
                 Token::LPAREN => {
                     self.next_token();
                     let node = self.expr()?;
@@ -101,6 +427,15 @@ impl Parser {
                             _ => Err(InterpreterError::UnexpectedToken(next_token)),
                         })
                 }
+                Token::ID(_) => {
+                    let var_node = self.variable()?;
+
+                    if let Some(Token::LPAREN) = self.peek_token() {
+                        self.call(*var_node)
+                    } else {
+                        Ok(var_node)
+                    }
+                }
                 _ => self
                     .variable()
                     .map(|node| node as Box<dyn ASTSemanticExpression>),
@@ -110,132 +445,733 @@ impl Parser {
         }
     }
 
-    pub fn term(&mut self) -> Result<Box<dyn ASTSemanticExpression>, InterpreterError> {
-        let mut left = self.factor()?;
+    /// Parses a list, a matrix when rows are separated by `;`, as in
+    /// `[1, 2; 3, 4]`, or an interval, as in `[9.5 .. 10.5]`.
+    pub fn list(&mut self) -> Result<Box<dyn ASTSemanticExpression>, InterpreterError> {
+        self.next_token();
 
-        while let Some(token) = self.peek_token() {
-            match token {
-                Token::MUL => {
-                    self.next_token();
-                    left = Box::new(BinaryOpNode::new(left, self.factor()?, BinaryOpType::MUL));
-                }
-                Token::DIV => {
-                    self.next_token();
-                    left = Box::new(BinaryOpNode::new(left, self.factor()?, BinaryOpType::DIV));
-                }
-                _ => {
-                    break;
-                }
+        let mut rows: Vec<Vec<Box<dyn ASTSemanticExpression>>> = vec![vec![]];
+
+        if let Some(Token::RBRACKET) = self.peek_token() {
+            self.next_token();
+            return Ok(Box::new(ListNode::new(vec![])));
+        }
+
+        let first = self.expr()?;
+
+        if let Some(Token::RANGE) = self.peek_token() {
+            self.next_token();
+
+            let hi = self.expr()?;
+
+            self.expect(Token::RBRACKET)?;
+            return Ok(Box::new(IntervalNode::new(first, hi)));
+        }
+
+        rows[0].push(first);
+
+        loop {
+            match self.next_token() {
+                Some(Token::COMMA) => {}
+                Some(Token::SEMI) => rows.push(vec![]),
+                Some(Token::RBRACKET) => break,
+                Some(token) => return Err(InterpreterError::UnexpectedToken(token)),
+                None => return Err(InterpreterError::EOF),
             }
+
+            rows.last_mut().unwrap().push(self.expr()?);
         }
 
-        Ok(left)
+        if rows.len() == 1 {
+            return Ok(Box::new(ListNode::new(rows.pop().unwrap())));
+        }
+
+        if let Some(row) = rows.iter().find(|row| row.len() != rows[0].len()) {
+            return Err(InterpreterError::ShapeMismatch(
+                format!("{:?}", [rows[0].len()]),
+                format!("{:?}", [row.len()]),
+            ));
+        }
+
+        let rows: Vec<Box<dyn ASTSemanticExpression>> = rows
+            .into_iter()
+            .map(|row| Box::new(ListNode::new(row)) as Box<dyn ASTSemanticExpression>)
+            .collect();
+
+        Ok(Box::new(ListNode::new(rows)))
     }
 
-    pub fn expr(&mut self) -> Result<Box<dyn ASTSemanticExpression>, InterpreterError> {
-        let mut left = self.term()?;
+    /// Attaches the unit following a number literal, as in `5 km`.
+    fn unit_suffix(
+        &mut self,
+        node: Box<dyn ASTSemanticExpression>,
+    ) -> Result<Box<dyn ASTSemanticExpression>, InterpreterError> {
+        match self.peek_token() {
+            Some(Token::ID(name)) if units::lookup(name).is_some() => {
+                Ok(Box::new(QuantityNode::new(node, self.unit_of(true)?)))
+            }
+            _ => Ok(node),
+        }
+    }
 
-        while let Some(token) = self.peek_token() {
-            match token {
-                Token::PLUS => {
-                    self.next_token();
-                    left = Box::new(BinaryOpNode::new(left, self.term()?, BinaryOpType::ADD));
-                }
-                Token::MINUS => {
-                    self.next_token();
-                    left = Box::new(BinaryOpNode::new(left, self.term()?, BinaryOpType::SUB));
-                }
-                _ => {
-                    break;
+    /// Parses a unit such as `km/h` or `m/s^2`. `*` and `/` only continue the
+    /// unit when a unit name follows them.
+    pub fn unit(&mut self) -> Result<UnitExpr, InterpreterError> {
+        self.unit_of(false)
+    }
+
+    /// The unit of a quantity also ends before a user variable or function,
+    /// while the target of `to` is only units.
+    fn unit_of(&mut self, quantity: bool) -> Result<UnitExpr, InterpreterError> {
+        let mut unit = vec![self.unit_power(1)?];
+
+        loop {
+            let sign = match self.peek_token() {
+                Some(Token::MUL) => 1,
+                Some(Token::DIV) => -1,
+                _ => break,
+            };
+
+            match self.peek_second_token() {
+                Some(Token::ID(name))
+                    if quantity
+                        && (self.variables.contains(&name) || self.functions.contains(&name)) =>
+                {
+                    break
                 }
+                Some(Token::ID(name)) if units::lookup(&name).is_some() => {}
+                _ => break,
             }
+
+            self.next_token();
+            unit.push(self.unit_power(sign)?);
         }
 
-        Ok(left)
+        Ok(unit)
     }
 
-    fn peek_token(&mut self) -> Option<&Token> {
-        self.tokens.peek()
+    fn unit_power(&mut self, sign: i32) -> Result<(String, i32), InterpreterError> {
+        let name = match self.next_token() {
+            Some(Token::ID(name)) if units::lookup(&name).is_some() => name,
+            Some(Token::ID(name)) => return Err(InterpreterError::UndefinedUnit(name)),
+            Some(token) => return Err(InterpreterError::UnexpectedToken(token)),
+            None => return Err(InterpreterError::EOF),
+        };
+
+        if self.peek_token() != Some(&Token::POW) {
+            return Ok((name, sign));
+        }
+
+        self.next_token();
+
+        let negative = self.peek_token() == Some(&Token::MINUS);
+
+        if negative {
+            self.next_token();
+        }
+
+        match self.next_token() {
+            Some(Token::NUMBER(exponent)) if exponent.fract() == 0. && exponent <= 64. => {
+                let exponent = exponent as i32;
+
+                Ok((
+                    name,
+                    if negative {
+                        -sign * exponent
+                    } else {
+                        sign * exponent
+                    },
+                ))
+            }
+            Some(token) => Err(InterpreterError::UnexpectedToken(token)),
+            None => Err(InterpreterError::EOF),
+        }
     }
 
-    fn next_token(&mut self) -> Option<Token> {
-        self.tokens.next()
+    pub fn call(
+        &mut self,
+        var_node: VarNode,
+    ) -> Result<Box<dyn ASTSemanticExpression>, InterpreterError> {
+        let name = var_node.name().clone();
+
+        if self.implicit_multiplication
+            && !builtins::is_builtin(&name)
+            && !self.functions.contains(&name)
+        {
+            return Err(InterpreterError::AmbiguousSyntax(format!(
+                "{}(...) is not a function call, use {} * (...) for multiplication",
+                name, name
+            )));
+        }
+
+        self.next_token();
+
+        match name.as_str() {
+            "diff" => return self.diff(),
+            "poly" => return self.poly(),
+            "simplify" => return self.simplify(),
+            "solve" => return self.solve(),
+            _ => {}
+        }
+
+        let mut args: Vec<Box<dyn ASTSemanticExpression>> = vec![];
+
+        if let Some(Token::RPAREN) = self.peek_token() {
+            self.next_token();
+            return Ok(Box::new(CallNode::new(name, args)));
+        }
+
+        loop {
+            args.push(self.expr()?);
+
+            match self.next_token() {
+                Some(Token::COMMA) => continue,
+                Some(Token::RPAREN) => break,
+                Some(token) => return Err(InterpreterError::UnexpectedToken(token)),
+                None => return Err(InterpreterError::EOF),
+            }
+        }
+
+        match name.as_str() {
+            "sum" | "prod" if args.len() == 4 => self.series(&name, args),
+            _ => Ok(Box::new(CallNode::new(name, args))),
+        }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::ast::ASTStatement;
-    use crate::symbol_table::SymbolTable;
-    use crate::token::Token;
+    /// `sum(ID, expr, expr, expr)` or `prod(...)` from the arguments of the
+    /// call, whose first names the index.
+    fn series(
+        &self,
+        name: &str,
+        args: Vec<Box<dyn ASTSemanticExpression>>,
+    ) -> Result<Box<dyn ASTSemanticExpression>, InterpreterError> {
+        let op_type = if name == "prod" {
+            BinaryOpType::MUL
+        } else {
+            BinaryOpType::ADD
+        };
+        let mut args = args.into_iter();
+        let index = match args.next().unwrap().kind() {
+            NodeKind::Var(index) => index.clone(),
+            _ => {
+                return Err(InterpreterError::InvalidSyntax(format!(
+                    "{} expects an index variable as its first argument",
+                    name
+                )))
+            }
+        };
+        let (lo, hi, body) = (
+            args.next().unwrap(),
+            args.next().unwrap(),
+            args.next().unwrap(),
+        );
 
-    use super::Parser;
+        Ok(Box::new(SeriesNode::new(op_type, index, lo, hi, body)))
+    }
 
-    #[test]
-    fn test_factor_integer() {
-        let tokens = vec![Token::NUMBER(123.)];
-        let mut parser = Parser::new(tokens);
-        let mut symtab = SymbolTable::new();
-        let expression = parser.factor();
+    /// Rest of `diff(expr, ID)`, after the opening parenthesis.
+    fn diff(&mut self) -> Result<Box<dyn ASTSemanticExpression>, InterpreterError> {
+        let expr = self.expr()?;
 
-        assert!(expression.is_ok());
-        assert_eq!(expression.unwrap().eval(&mut symtab).unwrap(), 123f64);
+        match self.next_token() {
+            Some(Token::COMMA) => {}
+            Some(token) => return Err(InterpreterError::UnexpectedToken(token)),
+            None => return Err(InterpreterError::EOF),
+        }
+
+        let var = match self.next_token() {
+            Some(Token::ID(var)) => var,
+            Some(_) => {
+                return Err(InterpreterError::InvalidSyntax(
+                    "diff expects a variable name as its second argument".to_string(),
+                ))
+            }
+            None => return Err(InterpreterError::EOF),
+        };
+
+        match self.next_token() {
+            Some(Token::RPAREN) => Ok(Box::new(DiffNode::new(expr, var))),
+            Some(token) => Err(InterpreterError::UnexpectedToken(token)),
+            None => Err(InterpreterError::EOF),
+        }
     }
 
-    #[test]
-    fn test_term() {
-        let tokens = vec![Token::NUMBER(4.), Token::MUL, Token::NUMBER(12.)];
-        let mut parser = Parser::new(tokens);
-        let mut symtab = SymbolTable::new();
-        let expression = parser.term();
+    /// Rest of `poly(expr)` or `poly(expr, ID)`, after the opening
+    /// parenthesis.
+    fn poly(&mut self) -> Result<Box<dyn ASTSemanticExpression>, InterpreterError> {
+        let expr = self.expr()?;
 
-        assert!(expression.is_ok());
-        assert_eq!(expression.unwrap().eval(&mut symtab).unwrap(), 48f64);
+        let var = match self.next_token() {
+            Some(Token::RPAREN) => return Ok(Box::new(PolyNode::new(expr, None))),
+            Some(Token::COMMA) => match self.next_token() {
+                Some(Token::ID(var)) => var,
+                Some(_) => {
+                    return Err(InterpreterError::InvalidSyntax(
+                        "poly expects a variable name as its second argument".to_string(),
+                    ))
+                }
+                None => return Err(InterpreterError::EOF),
+            },
+            Some(token) => return Err(InterpreterError::UnexpectedToken(token)),
+            None => return Err(InterpreterError::EOF),
+        };
+
+        match self.next_token() {
+            Some(Token::RPAREN) => Ok(Box::new(PolyNode::new(expr, Some(var)))),
+            Some(token) => Err(InterpreterError::UnexpectedToken(token)),
+            None => Err(InterpreterError::EOF),
+        }
     }
 
-    #[test]
-    fn test_expr() {
-        let tokens = vec![Token::NUMBER(4311.), Token::PLUS, Token::NUMBER(111.)];
-        let mut parser = Parser::new(tokens);
-        let mut symtab = SymbolTable::new();
-        let expression = parser.expr();
+    /// Rest of `simplify(expr)`, after the opening parenthesis.
+    fn simplify(&mut self) -> Result<Box<dyn ASTSemanticExpression>, InterpreterError> {
+        let expr = self.expr()?;
 
-        assert!(expression.is_ok());
-        assert_eq!(expression.unwrap().eval(&mut symtab).unwrap(), 4422f64);
+        match self.next_token() {
+            Some(Token::RPAREN) => Ok(Box::new(SimplifyNode::new(expr))),
+            Some(token) => Err(InterpreterError::UnexpectedToken(token)),
+            None => Err(InterpreterError::EOF),
+        }
     }
 
-    #[test]
-    fn test_factor_parenthesis() {
-        let tokens = vec![
-            Token::LPAREN,
-            Token::NUMBER(12.),
-            Token::PLUS,
-            Token::NUMBER(21.),
-            Token::RPAREN,
-        ];
-        let mut parser = Parser::new(tokens);
-        let mut symtab = SymbolTable::new();
-        let expression = parser.factor();
+    /// Rest of `solve(equation, ID)` with an optional initial guess or the
+    /// bounds of an interval, after the opening parenthesis.
+    fn solve(&mut self) -> Result<Box<dyn ASTSemanticExpression>, InterpreterError> {
+        let (lhs, rhs) = self.equation()?;
 
-        assert!(expression.is_ok());
-        assert_eq!(expression.unwrap().eval(&mut symtab).unwrap(), 33f64);
+        self.expect(Token::COMMA)?;
+
+        let var = match self.next_token() {
+            Some(Token::ID(var)) => var,
+            Some(_) => {
+                return Err(InterpreterError::InvalidSyntax(
+                    "solve expects a variable name as its second argument".to_string(),
+                ))
+            }
+            None => return Err(InterpreterError::EOF),
+        };
+        let mut bounds = vec![];
+
+        loop {
+            match self.next_token() {
+                Some(Token::COMMA) => bounds.push(self.expr()?),
+                Some(Token::RPAREN) => break,
+                Some(token) => return Err(InterpreterError::UnexpectedToken(token)),
+                None => return Err(InterpreterError::EOF),
+            }
+        }
+
+        if bounds.len() > 2 {
+            return Err(InterpreterError::InvalidSyntax(
+                "solve expects an initial guess or the bounds of an interval after the variable"
+                    .to_string(),
+            ));
+        }
+
+        Ok(Box::new(SolveNode::new(lhs, rhs, var, bounds)))
     }
 
-    #[test]
-    fn test_factor_unary_op() {
-        let tokens = vec![Token::PLUS, Token::PLUS, Token::NUMBER(12.)];
-        let mut parser = Parser::new(tokens);
-        let mut symtab = SymbolTable::new();
-        let expression = parser.factor();
+    /// `expr = expr`, whose `=` states an equation rather than assigning.
+    pub fn equation(&mut self) -> Result<Equation, InterpreterError> {
+        let lhs = self.expr()?;
 
-        assert!(expression.is_ok());
-        assert_eq!(expression.unwrap().eval(&mut symtab).unwrap(), 12f64);
+        self.expect(Token::ASSIGN)?;
+
+        Ok((lhs, self.expr()?))
     }
 
-    #[test]
-    fn test_parse() {
-        let tokens = vec![
-            Token::ID("x".to_string()),
+    pub fn implicit_term(&mut self) -> Result<Box<dyn ASTSemanticExpression>, InterpreterError> {
+        let mut left = self.factor()?;
+
+        if !self.implicit_multiplication {
+            return Ok(left);
+        }
+
+        while let Some(Token::ID(_) | Token::LPAREN) = self.peek_token() {
+            left = Box::new(BinaryOpNode::new(left, self.power()?, BinaryOpType::MUL));
+        }
+
+        Ok(left)
+    }
+
+    pub fn term(&mut self) -> Result<Box<dyn ASTSemanticExpression>, InterpreterError> {
+        let mut left = self.implicit_term()?;
+
+        while let Some(token) = self.peek_token() {
+            let op_type = match token {
+                Token::MUL => BinaryOpType::MUL,
+                Token::MATMUL => BinaryOpType::MATMUL,
+                Token::DIV => BinaryOpType::DIV,
+                Token::FLOORDIV => BinaryOpType::FLOORDIV,
+                Token::PERCENT => BinaryOpType::MOD,
+                _ => break,
+            };
+
+            self.next_token();
+            left = Box::new(BinaryOpNode::new(left, self.implicit_term()?, op_type));
+        }
+
+        Ok(left)
+    }
+
+    pub fn expr(&mut self) -> Result<Box<dyn ASTSemanticExpression>, InterpreterError> {
+        if self.lambda_ahead() {
+            return self.lambda();
+        }
+
+        if let Some(Token::IF) = self.peek_token() {
+            return self.conditional();
+        }
+
+        self.comparison()
+    }
+
+    /// Parses `if cond then expr else expr`, whose else branch extends as far
+    /// as possible.
+    pub fn conditional(&mut self) -> Result<Box<dyn ASTSemanticExpression>, InterpreterError> {
+        self.expect(Token::IF)?;
+
+        let condition = self.expr()?;
+
+        self.expect(Token::THEN)?;
+
+        let then_branch = self.expr()?;
+
+        self.expect(Token::ELSE)?;
+
+        Ok(Box::new(ConditionalNode::new(
+            condition,
+            then_branch,
+            self.expr()?,
+        )))
+    }
+
+    /// Parses at most one comparison, `a < b`, as comparisons do not chain.
+    pub fn comparison(&mut self) -> Result<Box<dyn ASTSemanticExpression>, InterpreterError> {
+        let left = self.convert_expr()?;
+        let op_type = match self.peek_token() {
+            Some(Token::LT) => CompareOpType::LT,
+            Some(Token::LE) => CompareOpType::LE,
+            Some(Token::GT) => CompareOpType::GT,
+            Some(Token::GE) => CompareOpType::GE,
+            Some(Token::EQ) => CompareOpType::EQ,
+            Some(Token::NE) => CompareOpType::NE,
+            _ => return Ok(left),
+        };
+
+        self.next_token();
+
+        Ok(Box::new(CompareNode::new(
+            left,
+            self.convert_expr()?,
+            op_type,
+        )))
+    }
+
+    pub fn convert_expr(&mut self) -> Result<Box<dyn ASTSemanticExpression>, InterpreterError> {
+        let mut node = self.bitor_expr()?;
+
+        while let Some(Token::TO) = self.peek_token() {
+            self.next_token();
+            node = Box::new(ConvertNode::new(node, self.unit()?));
+        }
+
+        Ok(node)
+    }
+
+    /// Parses an anonymous function, `x -> expr` or `(a, b) -> expr`, whose
+    /// body extends as far as possible.
+    pub fn lambda(&mut self) -> Result<Box<dyn ASTSemanticExpression>, InterpreterError> {
+        let params = match self.peek_token() {
+            Some(Token::LPAREN) => self.params()?,
+            _ => vec![self.variable()?.name().clone()],
+        };
+
+        self.expect(Token::ARROW)?;
+
+        let body = self.scoped(&params, Self::expr)?;
+
+        Ok(Box::new(LambdaNode::new(params, body)))
+    }
+
+    /// Parses with the parameters as variables, which they are in the body.
+    fn scoped<T>(
+        &mut self,
+        params: &[String],
+        parse: fn(&mut Self) -> Result<T, InterpreterError>,
+    ) -> Result<T, InterpreterError> {
+        let len = self.variables.len();
+
+        self.variables.extend(params.iter().cloned());
+
+        let result = parse(self);
+
+        self.variables.truncate(len);
+        result
+    }
+
+    /// Whether the next tokens are the parameters of a lambda followed by
+    /// `->`, as opposed to a variable or a parenthesized expression.
+    fn lambda_ahead(&self) -> bool {
+        let mut tokens = self.tokens.clone();
+
+        match tokens.next() {
+            Some(Token::ID(_)) => tokens.next() == Some(Token::ARROW),
+            Some(Token::LPAREN) => {
+                let mut param = true;
+
+                loop {
+                    match tokens.next() {
+                        Some(Token::ID(_)) if param => param = false,
+                        Some(Token::COMMA) if !param => param = true,
+                        Some(Token::RPAREN) => break,
+                        _ => return false,
+                    }
+                }
+
+                tokens.next() == Some(Token::ARROW)
+            }
+            _ => false,
+        }
+    }
+
+    pub fn bitor_expr(&mut self) -> Result<Box<dyn ASTSemanticExpression>, InterpreterError> {
+        let mut left = self.xor_expr()?;
+
+        while let Some(Token::BITOR) = self.peek_token() {
+            self.next_token();
+            left = Box::new(BinaryOpNode::new(
+                left,
+                self.xor_expr()?,
+                BinaryOpType::BITOR,
+            ));
+        }
+
+        Ok(left)
+    }
+
+    pub fn xor_expr(&mut self) -> Result<Box<dyn ASTSemanticExpression>, InterpreterError> {
+        let mut left = self.bitand_expr()?;
+
+        while let Some(Token::XOR) = self.peek_token() {
+            self.next_token();
+            left = Box::new(BinaryOpNode::new(
+                left,
+                self.bitand_expr()?,
+                BinaryOpType::XOR,
+            ));
+        }
+
+        Ok(left)
+    }
+
+    pub fn bitand_expr(&mut self) -> Result<Box<dyn ASTSemanticExpression>, InterpreterError> {
+        let mut left = self.shift_expr()?;
+
+        while let Some(Token::BITAND) = self.peek_token() {
+            self.next_token();
+            left = Box::new(BinaryOpNode::new(
+                left,
+                self.shift_expr()?,
+                BinaryOpType::BITAND,
+            ));
+        }
+
+        Ok(left)
+    }
+
+    pub fn shift_expr(&mut self) -> Result<Box<dyn ASTSemanticExpression>, InterpreterError> {
+        let mut left = self.arith_expr()?;
+
+        while let Some(token) = self.peek_token() {
+            let op_type = match token {
+                Token::SHL => BinaryOpType::SHL,
+                Token::SHR => BinaryOpType::SHR,
+                _ => break,
+            };
+
+            self.next_token();
+            left = Box::new(BinaryOpNode::new(left, self.arith_expr()?, op_type));
+        }
+
+        Ok(left)
+    }
+
+    pub fn arith_expr(&mut self) -> Result<Box<dyn ASTSemanticExpression>, InterpreterError> {
+        let mut left = self.term()?;
+
+        while let Some(token) = self.peek_token() {
+            let op_type = match token {
+                Token::PLUS => BinaryOpType::ADD,
+                Token::MINUS => BinaryOpType::SUB,
+                Token::PLUSMINUS => BinaryOpType::PLUSMINUS,
+                _ => break,
+            };
+
+            self.next_token();
+
+            let right = self.term()?;
+
+            // Only a percentage on its own is a change, `x + y * 10%` and
+            // `x + (10%)` add.
+            left = if self.calculator_mode
+                && self.previous_token == Some(Token::PERCENT)
+                && matches!(right.kind(), NodeKind::Unary(_, UnaryOpType::PERCENT))
+            {
+                Box::new(PercentChangeNode::new(
+                    left,
+                    right,
+                    op_type,
+                    self.number_mode.literal(1.),
+                ))
+            } else {
+                Box::new(BinaryOpNode::new(left, right, op_type))
+            };
+        }
+
+        Ok(left)
+    }
+
+    fn peek_token(&mut self) -> Option<&Token> {
+        self.tokens.peek()
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), InterpreterError> {
+        match self.next_token() {
+            Some(token) if token == expected => Ok(()),
+            Some(token) => Err(InterpreterError::UnexpectedToken(token)),
+            None => Err(InterpreterError::EOF),
+        }
+    }
+
+    /// In decimal mode a literal takes its sign before it is rounded, so that
+    /// `-2.25` rounds like `-9/4`. Powers and postfix operators bind tighter
+    /// than the sign, `-2^2` stays `-(2^2)`.
+    fn signed_literal_ahead(&self) -> bool {
+        let mut tokens = self.tokens.clone();
+
+        matches!(self.number_mode, NumberMode::DECIMAL(_))
+            && tokens.next() == Some(Token::MINUS)
+            && matches!(
+                tokens.next(),
+                Some(Token::NUMBER(_) | Token::INTEGER(_) | Token::DECIMAL(_))
+            )
+            && !matches!(
+                tokens.next(),
+                Some(Token::POW | Token::FACTORIAL | Token::PERCENT | Token::LBRACKET)
+            )
+    }
+
+    /// `%` is the modulo operator when an operand follows it and the postfix
+    /// percent operator otherwise. A sign after `%` continues a percentage,
+    /// `50% + 1` is `1.5`, so a signed divisor is written `7 % (-2)`.
+    fn modulo_ahead(&self) -> bool {
+        matches!(
+            self.peek_second_token(),
+            Some(
+                Token::NUMBER(_)
+                    | Token::INTEGER(_)
+                    | Token::DECIMAL(_)
+                    | Token::IMAGINARY(_)
+                    | Token::ID(_)
+                    | Token::LPAREN
+                    | Token::LBRACKET
+                    | Token::BITNOT
+            )
+        )
+    }
+
+    fn peek_second_token(&self) -> Option<Token> {
+        let mut tokens = self.tokens.clone();
+
+        tokens.next();
+        tokens.next()
+    }
+
+    fn next_token(&mut self) -> Option<Token> {
+        let token = self.tokens.next();
+
+        self.previous_token = token.clone();
+        token
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::ASTStatement;
+    use crate::error::InterpreterError;
+    use crate::format;
+    use crate::symbol_table::SymbolTable;
+    use crate::token::Token;
+    use crate::value::{BigInt, DecimalContext, NumberMode, RoundingMode, Value};
+
+    use super::Parser;
+
+    #[test]
+    fn test_factor_integer() {
+        let tokens = vec![Token::NUMBER(123.)];
+        let mut parser = Parser::new(tokens);
+        let mut symtab = SymbolTable::new();
+        let expression = parser.factor();
+
+        assert!(expression.is_ok());
+        assert_eq!(expression.unwrap().eval(&mut symtab).unwrap(), 123f64);
+    }
+
+    #[test]
+    fn test_term() {
+        let tokens = vec![Token::NUMBER(4.), Token::MUL, Token::NUMBER(12.)];
+        let mut parser = Parser::new(tokens);
+        let mut symtab = SymbolTable::new();
+        let expression = parser.term();
+
+        assert!(expression.is_ok());
+        assert_eq!(expression.unwrap().eval(&mut symtab).unwrap(), 48f64);
+    }
+
+    #[test]
+    fn test_expr() {
+        let tokens = vec![Token::NUMBER(4311.), Token::PLUS, Token::NUMBER(111.)];
+        let mut parser = Parser::new(tokens);
+        let mut symtab = SymbolTable::new();
+        let expression = parser.expr();
+
+        assert!(expression.is_ok());
+        assert_eq!(expression.unwrap().eval(&mut symtab).unwrap(), 4422f64);
+    }
+
+    #[test]
+    fn test_factor_parenthesis() {
+        let tokens = vec![
+            Token::LPAREN,
+            Token::NUMBER(12.),
+            Token::PLUS,
+            Token::NUMBER(21.),
+            Token::RPAREN,
+        ];
+        let mut parser = Parser::new(tokens);
+        let mut symtab = SymbolTable::new();
+        let expression = parser.factor();
+
+        assert!(expression.is_ok());
+        assert_eq!(expression.unwrap().eval(&mut symtab).unwrap(), 33f64);
+    }
+
+    #[test]
+    fn test_factor_unary_op() {
+        let tokens = vec![Token::PLUS, Token::PLUS, Token::NUMBER(12.)];
+        let mut parser = Parser::new(tokens);
+        let mut symtab = SymbolTable::new();
+        let expression = parser.factor();
+
+        assert!(expression.is_ok());
+        assert_eq!(expression.unwrap().eval(&mut symtab).unwrap(), 12f64);
+    }
+
+    #[test]
+    fn test_parse() {
+        let tokens = vec![
+            Token::ID("x".to_string()),
             Token::ASSIGN,
             Token::NUMBER(1.),
             Token::PLUS,
@@ -260,6 +1196,1105 @@ mod tests {
 
         assert!(node.is_ok());
         assert_eq!(node.unwrap().execute(&mut symtab).unwrap(), 1f64);
-        assert_eq!(symtab.get(&x), Some(&1f64));
+        assert_eq!(symtab.get(&x), Some(&Value::Number(1.)));
+    }
+
+    #[test]
+    fn test_power() {
+        let tokens = vec![
+            Token::MINUS,
+            Token::NUMBER(2.),
+            Token::POW,
+            Token::NUMBER(3.),
+            Token::POW,
+            Token::NUMBER(2.),
+        ];
+        let mut parser = Parser::new(tokens);
+        let mut symtab = SymbolTable::new();
+        let expression = parser.factor();
+
+        assert!(expression.is_ok());
+        assert_eq!(expression.unwrap().eval(&mut symtab).unwrap(), -512f64);
+    }
+
+    #[test]
+    fn test_parse_compound_assignment() {
+        let tokens = vec![
+            Token::ID("x".to_string()),
+            Token::ASSIGN,
+            Token::NUMBER(3.),
+            Token::SEMI,
+            Token::ID("x".to_string()),
+            Token::ADDASSIGN,
+            Token::NUMBER(1.),
+            Token::SEMI,
+            Token::ID("x".to_string()),
+            Token::POWASSIGN,
+            Token::NUMBER(2.),
+        ];
+        let mut parser = Parser::new(tokens);
+        let mut symtab = SymbolTable::new();
+        let node = parser.parse();
+
+        assert!(node.is_ok());
+        assert_eq!(node.unwrap().execute(&mut symtab).unwrap(), 16f64);
+        assert_eq!(symtab.get(&"x".to_string()), Some(&Value::Number(16.)));
+    }
+
+    #[test]
+    fn test_parse_multiple_assignment() {
+        let tokens = vec![
+            Token::ID("a".to_string()),
+            Token::COMMA,
+            Token::ID("b".to_string()),
+            Token::ASSIGN,
+            Token::NUMBER(1.),
+            Token::COMMA,
+            Token::NUMBER(2.),
+            Token::SEMI,
+            Token::ID("a".to_string()),
+            Token::COMMA,
+            Token::ID("b".to_string()),
+            Token::ASSIGN,
+            Token::ID("b".to_string()),
+            Token::COMMA,
+            Token::ID("a".to_string()),
+            Token::PLUS,
+            Token::ID("b".to_string()),
+        ];
+        let mut parser = Parser::new(tokens);
+        let mut symtab = SymbolTable::new();
+        let node = parser.parse();
+
+        assert!(node.is_ok());
+        assert_eq!(node.unwrap().execute(&mut symtab).unwrap(), 3f64);
+        assert_eq!(symtab.get(&"a".to_string()), Some(&Value::Number(2.)));
+        assert_eq!(symtab.get(&"b".to_string()), Some(&Value::Number(3.)));
+    }
+
+    #[test]
+    fn test_parse_uncertainty() {
+        let tokens = vec![
+            Token::ID("x".to_string()),
+            Token::ASSIGN,
+            Token::NUMBER(12.3),
+            Token::PLUSMINUS,
+            Token::NUMBER(0.2),
+            Token::SEMI,
+            Token::ID("y".to_string()),
+            Token::ASSIGN,
+            Token::ID("x".to_string()),
+            Token::MINUS,
+            Token::ID("x".to_string()),
+        ];
+        let mut parser = Parser::new(tokens);
+        let mut symtab = SymbolTable::new();
+        let node = parser.parse();
+
+        assert!(node.is_ok());
+        assert_eq!(
+            node.unwrap().execute(&mut symtab).unwrap().to_string(),
+            "0 ± 0"
+        );
+        assert_eq!(
+            symtab.get(&"x".to_string()).unwrap().to_string(),
+            "12.3 ± 0.2"
+        );
+    }
+
+    #[test]
+    fn test_parse_multiple_assignment_mismatch() {
+        let tokens = vec![
+            Token::ID("a".to_string()),
+            Token::COMMA,
+            Token::ID("b".to_string()),
+            Token::ASSIGN,
+            Token::NUMBER(1.),
+        ];
+        let mut parser = Parser::new(tokens);
+
+        assert!(parser.parse().is_err());
+    }
+
+    #[test]
+    fn test_parse_compound_assignment_multiple_targets() {
+        let tokens = vec![
+            Token::ID("a".to_string()),
+            Token::COMMA,
+            Token::ID("b".to_string()),
+            Token::ADDASSIGN,
+            Token::NUMBER(1.),
+        ];
+        let mut parser = Parser::new(tokens);
+
+        assert!(parser.parse().is_err());
+    }
+
+    #[test]
+    fn test_call() {
+        let tokens = vec![
+            Token::ID("pow".to_string()),
+            Token::LPAREN,
+            Token::NUMBER(2.),
+            Token::COMMA,
+            Token::ID("sqrt".to_string()),
+            Token::LPAREN,
+            Token::NUMBER(9.),
+            Token::RPAREN,
+            Token::RPAREN,
+        ];
+        let mut parser = Parser::new(tokens);
+        let mut symtab = SymbolTable::new();
+        let expression = parser.expr();
+
+        assert!(expression.is_ok());
+        assert_eq!(expression.unwrap().eval(&mut symtab).unwrap(), 8f64);
+    }
+
+    #[test]
+    fn test_implicit_multiplication() {
+        // 1 / 2x + 2(3 + 1)(2) + 2pi
+        let tokens = vec![
+            Token::NUMBER(1.),
+            Token::DIV,
+            Token::NUMBER(2.),
+            Token::ID("x".to_string()),
+            Token::PLUS,
+            Token::NUMBER(2.),
+            Token::LPAREN,
+            Token::NUMBER(3.),
+            Token::PLUS,
+            Token::NUMBER(1.),
+            Token::RPAREN,
+            Token::LPAREN,
+            Token::NUMBER(2.),
+            Token::RPAREN,
+            Token::PLUS,
+            Token::NUMBER(2.),
+            Token::ID("pi".to_string()),
+        ];
+        let mut parser = Parser::new(tokens).implicit_multiplication(true);
+        let mut symtab = SymbolTable::new();
+        let expression = parser.expr();
+
+        symtab.insert("x".to_string(), 4.);
+
+        assert!(expression.is_ok());
+        assert_eq!(
+            expression.unwrap().eval(&mut symtab).unwrap(),
+            0.125 + 16. + 2. * std::f64::consts::PI
+        );
+    }
+
+    #[test]
+    fn test_implicit_multiplication_power() {
+        let tokens = vec![
+            Token::NUMBER(3.),
+            Token::ID("x".to_string()),
+            Token::POW,
+            Token::NUMBER(2.),
+        ];
+        let mut parser = Parser::new(tokens).implicit_multiplication(true);
+        let mut symtab = SymbolTable::new();
+        let expression = parser.expr();
+
+        symtab.insert("x".to_string(), 2.);
+
+        assert!(expression.is_ok());
+        assert_eq!(expression.unwrap().eval(&mut symtab).unwrap(), 12f64);
+    }
+
+    #[test]
+    fn test_implicit_multiplication_ambiguous_call() {
+        let tokens = vec![
+            Token::NUMBER(2.),
+            Token::ID("x".to_string()),
+            Token::LPAREN,
+            Token::NUMBER(1.),
+            Token::RPAREN,
+        ];
+        let mut parser = Parser::new(tokens).implicit_multiplication(true);
+
+        assert!(matches!(
+            parser.expr(),
+            Err(InterpreterError::AmbiguousSyntax(_))
+        ));
+    }
+
+    #[test]
+    fn test_implicit_multiplication_disabled() {
+        let tokens = vec![
+            Token::ID("x".to_string()),
+            Token::ASSIGN,
+            Token::NUMBER(2.),
+            Token::ID("pi".to_string()),
+        ];
+        let mut parser = Parser::new(tokens);
+
+        assert!(matches!(
+            parser.parse(),
+            Err(InterpreterError::UnexpectedToken(_))
+        ));
+    }
+
+    #[test]
+    fn test_postfix() {
+        let tokens = vec![
+            Token::MINUS,
+            Token::NUMBER(2.),
+            Token::POW,
+            Token::NUMBER(3.),
+            Token::FACTORIAL,
+            Token::PLUS,
+            Token::NUMBER(15.),
+            Token::PERCENT,
+        ];
+        let mut parser = Parser::new(tokens);
+        let mut symtab = SymbolTable::new();
+        let expression = parser.expr();
+
+        assert!(expression.is_ok());
+        assert_eq!(expression.unwrap().eval(&mut symtab).unwrap(), -63.85);
+    }
+
+    #[test]
+    fn test_term_integer_division() {
+        let tokens = vec![
+            Token::NUMBER(17.),
+            Token::FLOORDIV,
+            Token::NUMBER(3.),
+            Token::PERCENT,
+            Token::NUMBER(4.),
+            Token::MUL,
+            Token::INTEGER(BigInt::parse("10000000000000000000").unwrap()),
+        ];
+        let mut parser = Parser::new(tokens);
+        let mut symtab = SymbolTable::new();
+        let expression = parser.expr();
+
+        assert!(expression.is_ok());
+        assert_eq!(
+            expression.unwrap().eval(&mut symtab).unwrap().to_string(),
+            "10000000000000000000"
+        );
+    }
+
+    #[test]
+    fn test_term_modulo_signed_operand() {
+        // 50% + 1 == 1.5
+        let tokens = vec![
+            Token::NUMBER(50.),
+            Token::PERCENT,
+            Token::PLUS,
+            Token::NUMBER(1.),
+        ];
+        let mut parser = Parser::new(tokens);
+        let mut symtab = SymbolTable::new();
+
+        assert_eq!(parser.expr().unwrap().eval(&mut symtab).unwrap(), 1.5);
+
+        // 7 % (-2) == -1
+        let tokens = vec![
+            Token::NUMBER(7.),
+            Token::PERCENT,
+            Token::LPAREN,
+            Token::MINUS,
+            Token::NUMBER(2.),
+            Token::RPAREN,
+        ];
+        let mut parser = Parser::new(tokens);
+
+        assert_eq!(parser.expr().unwrap().eval(&mut symtab).unwrap(), -1.);
+
+        // ~1 == -2
+        let tokens = vec![
+            Token::ID("x".to_string()),
+            Token::PERCENT,
+            Token::BITNOT,
+            Token::ID("y".to_string()),
+        ];
+        let mut parser = Parser::new(tokens);
+
+        symtab.insert("x".to_string(), 7.);
+        symtab.insert("y".to_string(), 1.);
+
+        assert_eq!(parser.expr().unwrap().eval(&mut symtab).unwrap(), -1.);
+    }
+
+    #[test]
+    fn test_list_literal() {
+        let tokens = vec![
+            Token::LBRACKET,
+            Token::NUMBER(1.),
+            Token::COMMA,
+            Token::NUMBER(2.),
+            Token::COMMA,
+            Token::NUMBER(3.),
+            Token::RBRACKET,
+            Token::MUL,
+            Token::NUMBER(2.),
+        ];
+        let mut parser = Parser::new(tokens);
+        let mut symtab = SymbolTable::new();
+        let expression = parser.expr();
+
+        assert!(expression.is_ok());
+        assert_eq!(
+            expression.unwrap().eval(&mut symtab).unwrap().to_string(),
+            "[2, 4, 6]"
+        );
+    }
+
+    #[test]
+    fn test_list_index() {
+        let tokens = vec![
+            Token::LBRACKET,
+            Token::NUMBER(4.),
+            Token::COMMA,
+            Token::NUMBER(5.),
+            Token::RBRACKET,
+            Token::LBRACKET,
+            Token::MINUS,
+            Token::NUMBER(1.),
+            Token::RBRACKET,
+            Token::FACTORIAL,
+        ];
+        let mut parser = Parser::new(tokens);
+        let mut symtab = SymbolTable::new();
+        let expression = parser.expr();
+
+        assert!(expression.is_ok());
+        assert_eq!(expression.unwrap().eval(&mut symtab).unwrap(), 120.);
+    }
+
+    #[test]
+    fn test_matrix_literal() {
+        let tokens = vec![
+            Token::LBRACKET,
+            Token::NUMBER(1.),
+            Token::COMMA,
+            Token::NUMBER(2.),
+            Token::SEMI,
+            Token::NUMBER(3.),
+            Token::COMMA,
+            Token::NUMBER(4.),
+            Token::RBRACKET,
+            Token::MATMUL,
+            Token::LBRACKET,
+            Token::NUMBER(1.),
+            Token::COMMA,
+            Token::NUMBER(1.),
+            Token::RBRACKET,
+        ];
+        let mut parser = Parser::new(tokens);
+        let mut symtab = SymbolTable::new();
+        let expression = parser.expr();
+
+        assert!(expression.is_ok());
+        assert_eq!(
+            expression.unwrap().eval(&mut symtab).unwrap().to_string(),
+            "[3, 7]"
+        );
+    }
+
+    #[test]
+    fn test_matrix_literal_ragged() {
+        let tokens = vec![
+            Token::LBRACKET,
+            Token::NUMBER(1.),
+            Token::COMMA,
+            Token::NUMBER(2.),
+            Token::SEMI,
+            Token::NUMBER(3.),
+            Token::RBRACKET,
+        ];
+        let mut parser = Parser::new(tokens);
+
+        assert!(matches!(
+            parser.expr(),
+            Err(InterpreterError::ShapeMismatch(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_interval_literal() {
+        let tokens = vec![
+            Token::LBRACKET,
+            Token::NUMBER(9.5),
+            Token::RANGE,
+            Token::NUMBER(10.5),
+            Token::RBRACKET,
+            Token::MUL,
+            Token::NUMBER(2.),
+        ];
+        let mut parser = Parser::new(tokens);
+        let mut symtab = SymbolTable::new();
+        let expression = parser.expr();
+
+        assert!(expression.is_ok());
+        assert_eq!(
+            expression.unwrap().eval(&mut symtab).unwrap().to_string(),
+            "[19, 21] (mid 20, width 2)"
+        );
+    }
+
+    #[test]
+    fn test_list_unclosed() {
+        let tokens = vec![Token::LBRACKET, Token::NUMBER(1.), Token::COMMA];
+        let mut parser = Parser::new(tokens);
+
+        assert!(matches!(parser.expr(), Err(InterpreterError::EOF)));
+    }
+
+    #[test]
+    fn test_unit_literal() {
+        let tokens = vec![
+            Token::NUMBER(60.),
+            Token::ID("mph".to_string()),
+            Token::MUL,
+            Token::NUMBER(2.),
+            Token::TO,
+            Token::ID("km".to_string()),
+            Token::DIV,
+            Token::ID("h".to_string()),
+        ];
+        let mut parser = Parser::new(tokens);
+        let mut symtab = SymbolTable::new();
+        let expression = parser.expr();
+
+        assert!(expression.is_ok());
+        assert_eq!(
+            expression.unwrap().eval(&mut symtab).unwrap().to_string(),
+            "193.12128 km/h"
+        );
+    }
+
+    #[test]
+    fn test_unit_power() {
+        let tokens = vec![
+            Token::NUMBER(9.81),
+            Token::ID("m".to_string()),
+            Token::DIV,
+            Token::ID("s".to_string()),
+            Token::POW,
+            Token::NUMBER(2.),
+        ];
+        let mut parser = Parser::new(tokens);
+        let mut symtab = SymbolTable::new();
+        let expression = parser.expr();
+
+        assert!(expression.is_ok());
+        assert_eq!(
+            expression.unwrap().eval(&mut symtab).unwrap().to_string(),
+            "9.81 m/s^2"
+        );
+    }
+
+    #[test]
+    fn test_unit_before_variable() {
+        // h = 2; x = 5 km * h
+        let tokens = vec![
+            Token::ID("h".to_string()),
+            Token::ASSIGN,
+            Token::NUMBER(2.),
+            Token::SEMI,
+            Token::ID("x".to_string()),
+            Token::ASSIGN,
+            Token::NUMBER(5.),
+            Token::ID("km".to_string()),
+            Token::MUL,
+            Token::ID("h".to_string()),
+        ];
+        let mut parser = Parser::new(tokens);
+        let mut symtab = SymbolTable::new();
+
+        parser.parse().unwrap().execute(&mut symtab).unwrap();
+
+        assert_eq!(symtab.get(&"x".to_string()).unwrap().to_string(), "10 km");
+        assert_eq!(parser.variables(), ["h".to_string(), "x".to_string()]);
+
+        // A parameter is a variable in the body, f(s) = 5 km * s
+        let tokens = vec![
+            Token::ID("f".to_string()),
+            Token::LPAREN,
+            Token::ID("s".to_string()),
+            Token::RPAREN,
+            Token::ASSIGN,
+            Token::NUMBER(5.),
+            Token::ID("km".to_string()),
+            Token::MUL,
+            Token::ID("s".to_string()),
+        ];
+        let node = Parser::new(tokens).parse().unwrap();
+
+        assert_eq!(format::statement(node.as_ref()), "f(s) = (5 km) * s");
+    }
+
+    #[test]
+    fn test_convert_undefined_unit() {
+        let tokens = vec![
+            Token::NUMBER(1.),
+            Token::ID("m".to_string()),
+            Token::TO,
+            Token::ID("parsec".to_string()),
+        ];
+        let mut parser = Parser::new(tokens);
+
+        assert!(matches!(
+            parser.expr(),
+            Err(InterpreterError::UndefinedUnit(_))
+        ));
+    }
+
+    #[test]
+    fn test_decimal_mode_signed_literal() {
+        let context = DecimalContext::new(2, RoundingMode::CEILING);
+        let mut symtab = SymbolTable::new();
+
+        // -2.25 + 0 rounds like -9/4, not like -(2.25 rounded up)
+        let tokens = vec![
+            Token::MINUS,
+            Token::NUMBER(2.25),
+            Token::PLUS,
+            Token::NUMBER(0.),
+        ];
+        let mut parser = Parser::new(tokens).number_mode(NumberMode::DECIMAL(context));
+
+        assert_eq!(
+            parser
+                .expr()
+                .unwrap()
+                .eval(&mut symtab)
+                .unwrap()
+                .to_string(),
+            "-2.2"
+        );
+
+        // -2^2 == -(2^2)
+        let tokens = vec![
+            Token::MINUS,
+            Token::NUMBER(2.),
+            Token::POW,
+            Token::NUMBER(2.),
+        ];
+        let mut parser = Parser::new(tokens).number_mode(NumberMode::DECIMAL(context));
+
+        assert_eq!(
+            parser
+                .expr()
+                .unwrap()
+                .eval(&mut symtab)
+                .unwrap()
+                .to_string(),
+            "-4"
+        );
+    }
+
+    #[test]
+    fn test_calculator_mode_percent() {
+        let tokens = vec![
+            Token::NUMBER(80.),
+            Token::PLUS,
+            Token::NUMBER(25.),
+            Token::PERCENT,
+            Token::MINUS,
+            Token::NUMBER(50.),
+            Token::PERCENT,
+        ];
+        let mut parser = Parser::new(tokens).calculator_mode(true);
+        let mut symtab = SymbolTable::new();
+        let expression = parser.expr();
+
+        assert!(expression.is_ok());
+        assert_eq!(expression.unwrap().eval(&mut symtab).unwrap(), 50f64);
+    }
+
+    #[test]
+    fn test_bitwise_precedence() {
+        // 1 | 6 xor 3 & ~0 << 1 + 1 == 1 | (6 xor (3 & (~0 << 2)))
+        let tokens = vec![
+            Token::NUMBER(1.),
+            Token::BITOR,
+            Token::NUMBER(6.),
+            Token::XOR,
+            Token::NUMBER(3.),
+            Token::BITAND,
+            Token::BITNOT,
+            Token::NUMBER(0.),
+            Token::SHL,
+            Token::NUMBER(1.),
+            Token::PLUS,
+            Token::NUMBER(1.),
+        ];
+        let mut parser = Parser::new(tokens);
+        let mut symtab = SymbolTable::new();
+        let expression = parser.expr();
+
+        assert!(expression.is_ok());
+        assert_eq!(expression.unwrap().eval(&mut symtab).unwrap(), 7f64);
+    }
+
+    #[test]
+    fn test_parse_const() {
+        let tokens = vec![
+            Token::CONST,
+            Token::ID("g".to_string()),
+            Token::ASSIGN,
+            Token::NUMBER(9.81),
+        ];
+        let mut parser = Parser::new(tokens);
+        let mut symtab = SymbolTable::new();
+        let node = parser.parse();
+
+        assert!(node.is_ok());
+        assert_eq!(node.unwrap().execute(&mut symtab).unwrap(), 9.81);
+        assert!(symtab.is_constant(&"g".to_string()));
+    }
+
+    #[test]
+    fn test_parse_function_definition() {
+        // f(x, y) = x y; n = f(3, 4)
+        let tokens = vec![
+            Token::ID("f".to_string()),
+            Token::LPAREN,
+            Token::ID("x".to_string()),
+            Token::COMMA,
+            Token::ID("y".to_string()),
+            Token::RPAREN,
+            Token::ASSIGN,
+            Token::ID("x".to_string()),
+            Token::ID("y".to_string()),
+            Token::SEMI,
+            Token::ID("n".to_string()),
+            Token::ASSIGN,
+            Token::ID("f".to_string()),
+            Token::LPAREN,
+            Token::NUMBER(3.),
+            Token::COMMA,
+            Token::NUMBER(4.),
+            Token::RPAREN,
+        ];
+        let mut parser = Parser::new(tokens).implicit_multiplication(true);
+        let mut symtab = SymbolTable::new();
+        let node = parser.parse();
+
+        assert!(node.is_ok());
+        assert_eq!(node.unwrap().execute(&mut symtab).unwrap(), 12.);
+    }
+
+    #[test]
+    fn test_parse_function_repeated_parameter() {
+        let tokens = vec![
+            Token::ID("f".to_string()),
+            Token::LPAREN,
+            Token::ID("x".to_string()),
+            Token::COMMA,
+            Token::ID("x".to_string()),
+            Token::RPAREN,
+            Token::ASSIGN,
+            Token::NUMBER(1.),
+        ];
+        let mut parser = Parser::new(tokens);
+
+        assert!(matches!(
+            parser.parse(),
+            Err(InterpreterError::InvalidSyntax(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_diff() {
+        let tokens = vec![
+            Token::ID("d".to_string()),
+            Token::ASSIGN,
+            Token::ID("diff".to_string()),
+            Token::LPAREN,
+            Token::ID("x".to_string()),
+            Token::POW,
+            Token::NUMBER(2.),
+            Token::COMMA,
+            Token::ID("x".to_string()),
+            Token::RPAREN,
+        ];
+        let mut parser = Parser::new(tokens).implicit_multiplication(true);
+
+        assert!(parser.parse().is_ok());
+
+        let tokens = vec![
+            Token::ID("d".to_string()),
+            Token::ASSIGN,
+            Token::ID("diff".to_string()),
+            Token::LPAREN,
+            Token::ID("x".to_string()),
+            Token::COMMA,
+            Token::NUMBER(2.),
+            Token::RPAREN,
+        ];
+        let mut parser = Parser::new(tokens);
+
+        assert!(matches!(
+            parser.parse(),
+            Err(InterpreterError::InvalidSyntax(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_solve() {
+        // r = solve(x^2 = 2, x, 0, 2)
+        let tokens = vec![
+            Token::ID("r".to_string()),
+            Token::ASSIGN,
+            Token::ID("solve".to_string()),
+            Token::LPAREN,
+            Token::ID("x".to_string()),
+            Token::POW,
+            Token::NUMBER(2.),
+            Token::ASSIGN,
+            Token::NUMBER(2.),
+            Token::COMMA,
+            Token::ID("x".to_string()),
+            Token::COMMA,
+            Token::NUMBER(0.),
+            Token::COMMA,
+            Token::NUMBER(2.),
+            Token::RPAREN,
+        ];
+        let mut parser = Parser::new(tokens).implicit_multiplication(true);
+
+        assert!(parser.parse().is_ok());
+
+        // An expression is not an equation
+        let tokens = vec![
+            Token::ID("r".to_string()),
+            Token::ASSIGN,
+            Token::ID("solve".to_string()),
+            Token::LPAREN,
+            Token::ID("x".to_string()),
+            Token::COMMA,
+            Token::ID("x".to_string()),
+            Token::RPAREN,
+        ];
+        let mut parser = Parser::new(tokens);
+
+        assert!(matches!(
+            parser.parse(),
+            Err(InterpreterError::UnexpectedToken(Token::COMMA))
+        ));
+
+        let tokens = vec![
+            Token::ID("r".to_string()),
+            Token::ASSIGN,
+            Token::ID("solve".to_string()),
+            Token::LPAREN,
+            Token::ID("x".to_string()),
+            Token::ASSIGN,
+            Token::NUMBER(1.),
+            Token::COMMA,
+            Token::NUMBER(2.),
+            Token::RPAREN,
+        ];
+        let mut parser = Parser::new(tokens);
+
+        assert!(matches!(
+            parser.parse(),
+            Err(InterpreterError::InvalidSyntax(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_poly() {
+        let tokens = vec![
+            Token::ID("p".to_string()),
+            Token::ASSIGN,
+            Token::ID("poly".to_string()),
+            Token::LPAREN,
+            Token::ID("x".to_string()),
+            Token::POW,
+            Token::NUMBER(2.),
+            Token::COMMA,
+            Token::ID("x".to_string()),
+            Token::RPAREN,
+        ];
+        let mut parser = Parser::new(tokens).implicit_multiplication(true);
+
+        assert!(parser.parse().is_ok());
+
+        let tokens = vec![
+            Token::ID("p".to_string()),
+            Token::ASSIGN,
+            Token::ID("poly".to_string()),
+            Token::LPAREN,
+            Token::LBRACKET,
+            Token::NUMBER(1.),
+            Token::RBRACKET,
+            Token::RPAREN,
+        ];
+        let mut parser = Parser::new(tokens);
+
+        assert!(parser.parse().is_ok());
+
+        let tokens = vec![
+            Token::ID("p".to_string()),
+            Token::ASSIGN,
+            Token::ID("poly".to_string()),
+            Token::LPAREN,
+            Token::ID("x".to_string()),
+            Token::COMMA,
+            Token::NUMBER(1.),
+            Token::RPAREN,
+        ];
+        let mut parser = Parser::new(tokens);
+
+        assert!(matches!(
+            parser.parse(),
+            Err(InterpreterError::InvalidSyntax(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_series() {
+        // sum(k, 1, n, k^2)
+        let tokens = vec![
+            Token::ID("s".to_string()),
+            Token::ASSIGN,
+            Token::ID("sum".to_string()),
+            Token::LPAREN,
+            Token::ID("k".to_string()),
+            Token::COMMA,
+            Token::NUMBER(1.),
+            Token::COMMA,
+            Token::ID("n".to_string()),
+            Token::COMMA,
+            Token::ID("k".to_string()),
+            Token::POW,
+            Token::NUMBER(2.),
+            Token::RPAREN,
+        ];
+        let mut parser = Parser::new(tokens).implicit_multiplication(true);
+
+        assert!(parser.parse().is_ok());
+
+        let tokens = vec![
+            Token::ID("s".to_string()),
+            Token::ASSIGN,
+            Token::ID("prod".to_string()),
+            Token::LPAREN,
+            Token::NUMBER(2.),
+            Token::COMMA,
+            Token::NUMBER(1.),
+            Token::COMMA,
+            Token::NUMBER(3.),
+            Token::COMMA,
+            Token::NUMBER(4.),
+            Token::RPAREN,
+        ];
+        let mut parser = Parser::new(tokens);
+
+        assert!(matches!(
+            parser.parse(),
+            Err(InterpreterError::InvalidSyntax(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_lambda() {
+        // f = (a, b) -> a * b
+        let tokens = vec![
+            Token::ID("f".to_string()),
+            Token::ASSIGN,
+            Token::LPAREN,
+            Token::ID("a".to_string()),
+            Token::COMMA,
+            Token::ID("b".to_string()),
+            Token::RPAREN,
+            Token::ARROW,
+            Token::ID("a".to_string()),
+            Token::MUL,
+            Token::ID("b".to_string()),
+        ];
+        let mut parser = Parser::new(tokens);
+
+        assert!(parser.parse().is_ok());
+
+        // A parenthesized variable is not a parameter list
+        let tokens = vec![
+            Token::ID("f".to_string()),
+            Token::ASSIGN,
+            Token::LPAREN,
+            Token::ID("a".to_string()),
+            Token::RPAREN,
+            Token::MUL,
+            Token::NUMBER(2.),
+        ];
+        let mut parser = Parser::new(tokens);
+
+        assert!(parser.parse().is_ok());
+
+        let tokens = vec![
+            Token::ID("f".to_string()),
+            Token::ASSIGN,
+            Token::LPAREN,
+            Token::ID("a".to_string()),
+            Token::COMMA,
+            Token::ID("a".to_string()),
+            Token::RPAREN,
+            Token::ARROW,
+            Token::NUMBER(1.),
+        ];
+        let mut parser = Parser::new(tokens);
+
+        assert!(matches!(
+            parser.parse(),
+            Err(InterpreterError::InvalidSyntax(_))
+        ));
+
+        // The body is not an operand
+        let tokens = vec![
+            Token::ID("f".to_string()),
+            Token::ASSIGN,
+            Token::NUMBER(1.),
+            Token::PLUS,
+            Token::ID("x".to_string()),
+            Token::ARROW,
+            Token::ID("x".to_string()),
+        ];
+        let mut parser = Parser::new(tokens);
+
+        assert!(matches!(
+            parser.parse(),
+            Err(InterpreterError::UnexpectedToken(Token::ARROW))
+        ));
+    }
+
+    #[test]
+    fn test_parse_conditional() {
+        // r = if n < 2 then n else 2
+        let tokens = vec![
+            Token::ID("r".to_string()),
+            Token::ASSIGN,
+            Token::IF,
+            Token::ID("n".to_string()),
+            Token::LT,
+            Token::NUMBER(2.),
+            Token::THEN,
+            Token::ID("n".to_string()),
+            Token::ELSE,
+            Token::NUMBER(2.),
+        ];
+        let mut parser = Parser::new(tokens);
+
+        assert!(parser.parse().is_ok());
+
+        let tokens = vec![
+            Token::ID("r".to_string()),
+            Token::ASSIGN,
+            Token::IF,
+            Token::ID("n".to_string()),
+            Token::THEN,
+            Token::ID("n".to_string()),
+        ];
+        let mut parser = Parser::new(tokens);
+
+        assert!(matches!(parser.parse(), Err(InterpreterError::EOF)));
+
+        // Comparisons do not chain
+        let tokens = vec![
+            Token::ID("r".to_string()),
+            Token::ASSIGN,
+            Token::NUMBER(1.),
+            Token::LT,
+            Token::NUMBER(2.),
+            Token::LE,
+            Token::NUMBER(3.),
+        ];
+        let mut parser = Parser::new(tokens);
+
+        assert!(matches!(
+            parser.parse(),
+            Err(InterpreterError::UnexpectedToken(Token::LE))
+        ));
+    }
+
+    #[test]
+    fn test_parse_memo() {
+        // memo f(n) = f(n - 1)
+        let tokens = vec![
+            Token::MEMO,
+            Token::ID("f".to_string()),
+            Token::LPAREN,
+            Token::ID("n".to_string()),
+            Token::RPAREN,
+            Token::ASSIGN,
+            Token::ID("f".to_string()),
+            Token::LPAREN,
+            Token::ID("n".to_string()),
+            Token::MINUS,
+            Token::NUMBER(1.),
+            Token::RPAREN,
+        ];
+        let mut parser = Parser::new(tokens).implicit_multiplication(true);
+
+        assert!(parser.parse().is_ok());
+        assert_eq!(parser.functions(), ["f".to_string()]);
+
+        let tokens = vec![
+            Token::MEMO,
+            Token::ID("x".to_string()),
+            Token::ASSIGN,
+            Token::NUMBER(1.),
+        ];
+        let mut parser = Parser::new(tokens);
+
+        assert!(matches!(
+            parser.parse(),
+            Err(InterpreterError::InvalidSyntax(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_simplify() {
+        let tokens = vec![
+            Token::ID("s".to_string()),
+            Token::ASSIGN,
+            Token::ID("simplify".to_string()),
+            Token::LPAREN,
+            Token::ID("x".to_string()),
+            Token::PLUS,
+            Token::ID("x".to_string()),
+            Token::RPAREN,
+        ];
+        let mut parser = Parser::new(tokens).implicit_multiplication(true);
+
+        assert!(parser.parse().is_ok());
+
+        let tokens = vec![
+            Token::ID("s".to_string()),
+            Token::ASSIGN,
+            Token::ID("simplify".to_string()),
+            Token::LPAREN,
+            Token::ID("x".to_string()),
+            Token::COMMA,
+            Token::ID("x".to_string()),
+            Token::RPAREN,
+        ];
+        let mut parser = Parser::new(tokens);
+
+        assert!(matches!(
+            parser.parse(),
+            Err(InterpreterError::UnexpectedToken(Token::COMMA))
+        ));
+    }
+
+    #[test]
+    fn test_parse_const_compound() {
+        let tokens = vec![
+            Token::CONST,
+            Token::ID("g".to_string()),
+            Token::ADDASSIGN,
+            Token::NUMBER(9.81),
+        ];
+        let mut parser = Parser::new(tokens);
+
+        assert!(parser.parse().is_err());
     }
 }
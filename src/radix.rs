@@ -0,0 +1,62 @@
+use crate::math;
+use crate::value::{BigInt, Value};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Radix {
+    DEC,
+    HEX,
+    BIN,
+}
+
+/// Formats `value` in the given radix. Values that are not integral are
+/// always formatted in decimal.
+pub fn format_value(value: &Value, radix: Radix) -> String {
+    let integer = match value {
+        Value::Number(value) => BigInt::from_f64(*value),
+        Value::Integer(value) => Some(value.clone()),
+        _ => math::to_integer(value.to_f64()).map(BigInt::from_i64),
+    };
+
+    let integer = match (radix, integer) {
+        (Radix::DEC, _) | (_, None) => return value.to_string(),
+        (_, Some(integer)) => integer,
+    };
+
+    let sign = if integer.is_negative() { "-" } else { "" };
+
+    match radix {
+        Radix::HEX => format!("{}0x{}", sign, integer.to_radix(16)),
+        Radix::BIN => format!("{}0b{}", sign, integer.to_radix(2)),
+        Radix::DEC => unreachable!(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::value::{BigInt, Value};
+
+    use super::{format_value, Radix};
+
+    #[test]
+    fn test_format_number() {
+        assert_eq!(format_value(&Value::Number(255.), Radix::DEC), "255");
+        assert_eq!(format_value(&Value::Number(255.), Radix::HEX), "0xff");
+        assert_eq!(format_value(&Value::Number(-255.), Radix::HEX), "-0xff");
+        assert_eq!(format_value(&Value::Number(5.), Radix::BIN), "0b101");
+        assert_eq!(format_value(&Value::Number(0.5), Radix::HEX), "0.5");
+    }
+
+    #[test]
+    fn test_format_integer() {
+        let value = Value::Integer(BigInt::parse("1152921504606846977").unwrap());
+
+        assert_eq!(format_value(&value, Radix::HEX), "0x1000000000000001");
+        assert_eq!(
+            format_value(
+                &Value::Integer(BigInt::parse("-36893488147419103232").unwrap()),
+                Radix::BIN
+            ),
+            format!("-0b1{}", "0".repeat(65))
+        );
+    }
+}
@@ -1,60 +1,226 @@
 use std::collections::HashMap;
 use std::f64::consts;
 
+use crate::error::InterpreterError;
+use crate::units;
+use crate::value::{Unit, Value};
+
+/// Nested user function calls deeper than this are an error rather than an
+/// overflow of the native stack, unless the limit is changed with
+/// `set_max_call_depth`.
+pub const MAX_CALL_DEPTH: usize = 256;
+
+#[derive(Clone)]
 pub struct SymbolTable {
-    global: HashMap<String, f64>,
-    local: HashMap<String, f64>,
+    global: HashMap<String, Value>,
+    units: HashMap<String, Unit>,
+    constant: HashMap<String, Value>,
+    local: HashMap<String, Value>,
+    scopes: Vec<HashMap<String, Value>>,
+    max_call_depth: usize,
+    generation: usize,
 }
 
 impl SymbolTable {
     pub fn new() -> Self {
-        let mut global: HashMap<String, f64> = HashMap::new();
+        let mut global: HashMap<String, Value> = HashMap::new();
+
+        global.insert("e".to_string(), Value::Number(consts::E));
+        global.insert("pi".to_string(), Value::Number(consts::PI));
 
-        global.insert("e".to_string(), consts::E);
-        global.insert("pi".to_string(), consts::PI);
+        let units = units::UNITS
+            .iter()
+            .map(|unit| (unit.name.to_string(), unit.unit()))
+            .collect();
 
         Self {
             global,
+            units,
+            constant: HashMap::new(),
             local: HashMap::new(),
+            scopes: vec![],
+            max_call_depth: MAX_CALL_DEPTH,
+            generation: 0,
         }
     }
 
     pub fn clear(&mut self) {
+        self.constant.clear();
         self.local.clear();
+        self.scopes.clear();
+        self.generation += 1;
+    }
+
+    pub fn set_max_call_depth(&mut self, depth: usize) {
+        self.max_call_depth = depth;
+    }
+
+    /// Counts the changes to the functions in the table, a memoized function
+    /// forgets its results when one of the functions it may call changes.
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Looks up a symbol, parameters of the innermost function call shadow
+    /// user variables and constants.
+    pub fn get(&self, symbol: &String) -> Option<&Value> {
+        self.global
+            .get(symbol)
+            .or(self.scopes.last().and_then(|scope| scope.get(symbol)))
+            .or(self.constant.get(symbol))
+            .or(self.local.get(symbol))
+    }
+
+    /// Enters a function call with its parameters bound to `bindings`. Only
+    /// the innermost scope is visible, so a function never sees the
+    /// parameters of its caller.
+    pub fn push_scope(&mut self, bindings: HashMap<String, Value>) -> Result<(), InterpreterError> {
+        if self.scopes.len() >= self.max_call_depth {
+            return Err(InterpreterError::StackOverflow(self.max_call_depth));
+        }
+
+        self.scopes.push(bindings);
+        Ok(())
+    }
+
+    /// Enters a scope that adds `symbol` to the bindings of the innermost
+    /// one, so that an expression can be evaluated at many values of a
+    /// variable, e.g. to solve an equation inside a function body.
+    pub fn push_binding(&mut self, symbol: String, value: Value) -> Result<(), InterpreterError> {
+        self.push_bindings(HashMap::from([(symbol, value)]))
+    }
+
+    /// Enters a scope that adds all of `bindings` to the innermost one, e.g.
+    /// the parameters of a lambda defined inside a function body.
+    pub fn push_bindings(
+        &mut self,
+        bindings: HashMap<String, Value>,
+    ) -> Result<(), InterpreterError> {
+        let mut scope = self.scopes.last().cloned().unwrap_or_default();
+
+        scope.extend(bindings);
+        self.push_scope(scope)
+    }
+
+    /// Changes the value of `symbol` in the innermost scope, as entered with
+    /// `push_binding`, e.g. for each value of a summation index.
+    pub fn rebind(&mut self, symbol: &str, value: Value) {
+        if let Some(binding) = self
+            .scopes
+            .last_mut()
+            .and_then(|scope| scope.get_mut(symbol))
+        {
+            *binding = value;
+        }
+    }
+
+    pub fn pop_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    /// Names of the user functions defined so far.
+    pub fn functions(&self) -> Vec<String> {
+        self.constant
+            .iter()
+            .chain(self.local.iter())
+            .filter(|(_, value)| matches!(value, Value::Function(_)))
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    /// Names of the user variables and constants defined so far.
+    pub fn variables(&self) -> Vec<String> {
+        self.constant
+            .iter()
+            .chain(self.local.iter())
+            .filter(|(_, value)| !matches!(value, Value::Function(_)))
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    pub fn get_unit(&self, name: &str) -> Option<&Unit> {
+        self.units.get(name)
+    }
+
+    pub fn insert<T: Into<Value>>(&mut self, symbol: String, value: T) {
+        let value = value.into();
+
+        self.count_function(&value);
+
+        if let Some(previous) = self.local.insert(symbol, value) {
+            self.count_function(&previous);
+        }
     }
 
-    pub fn get(&self, symbol: &String) -> Option<&f64> {
-        self.global.get(symbol).or(self.local.get(symbol))
+    /// Inserts a user declared constant, which shadows and replaces any
+    /// variable with the same name.
+    pub fn insert_constant<T: Into<Value>>(&mut self, symbol: String, value: T) {
+        let value = value.into();
+
+        self.count_function(&value);
+
+        if let Some(previous) = self.local.remove(&symbol) {
+            self.count_function(&previous);
+        }
+
+        self.constant.insert(symbol, value);
     }
 
-    pub fn insert(&mut self, symbol: String, value: f64) {
-        self.local.insert(symbol, value);
+    /// Removes a variable, e.g. a function whose definition turned out to be
+    /// invalid.
+    pub fn remove(&mut self, symbol: &str) -> Option<Value> {
+        let value = self.local.remove(symbol)?;
+
+        self.count_function(&value);
+        Some(value)
+    }
+
+    fn count_function(&mut self, value: &Value) {
+        if matches!(value, Value::Function(_)) {
+            self.generation += 1;
+        }
     }
 
     pub fn is_global(&self, symbol: &String) -> bool {
-        self.global.get(symbol).is_some()
+        self.global.contains_key(symbol)
+    }
+
+    pub fn is_constant(&self, symbol: &String) -> bool {
+        self.constant.contains_key(symbol)
+    }
+}
+
+impl Default for SymbolTable {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
     use std::f64::consts;
+    use std::rc::Rc;
 
-    use super::SymbolTable;
+    use crate::ast::NumberNode;
+    use crate::error::InterpreterError;
+    use crate::value::{Function, Value};
+
+    use super::{SymbolTable, MAX_CALL_DEPTH};
 
     #[test]
     fn test_get() {
         let symtab = SymbolTable::new();
 
-        let kv_pairs = vec![
-            ("e".to_string(), Some(&consts::E)),
-            ("pi".to_string(), Some(&consts::PI)),
+        let kv_pairs = [
+            ("e".to_string(), Some(Value::Number(consts::E))),
+            ("pi".to_string(), Some(Value::Number(consts::PI))),
             ("x".to_string(), None),
             ("test_key".to_string(), None),
         ];
 
         for kv in kv_pairs.iter() {
-            assert_eq!(symtab.get(&kv.0), kv.1);
+            assert_eq!(symtab.get(&kv.0), kv.1.as_ref());
         }
     }
 
@@ -64,7 +230,7 @@ mod tests {
 
         symtab.insert("x".to_string(), 123.45);
 
-        assert_eq!(symtab.get(&"x".to_string()), Some(&123.45));
+        assert_eq!(symtab.get(&"x".to_string()), Some(&Value::Number(123.45)));
     }
 
     #[test]
@@ -77,18 +243,127 @@ mod tests {
         assert!(!symtab.is_global(&"test_var".to_string()));
     }
 
+    #[test]
+    fn test_insert_constant() {
+        let mut symtab = SymbolTable::new();
+
+        symtab.insert("g".to_string(), 10.);
+        symtab.insert_constant("g".to_string(), 9.81);
+
+        assert_eq!(symtab.get(&"g".to_string()), Some(&Value::Number(9.81)));
+        assert!(symtab.is_constant(&"g".to_string()));
+        assert!(!symtab.is_constant(&"x".to_string()));
+        assert!(!symtab.is_global(&"g".to_string()));
+    }
+
+    #[test]
+    fn test_scope() {
+        let mut symtab = SymbolTable::new();
+
+        symtab.insert("x".to_string(), 1.);
+        symtab
+            .push_scope([("x".to_string(), Value::Number(2.))].into())
+            .unwrap();
+
+        assert_eq!(symtab.get(&"x".to_string()), Some(&Value::Number(2.)));
+
+        symtab.pop_scope();
+
+        assert_eq!(symtab.get(&"x".to_string()), Some(&Value::Number(1.)));
+
+        for _ in 0..MAX_CALL_DEPTH {
+            symtab.push_scope(HashMap::new()).unwrap();
+        }
+
+        assert!(matches!(
+            symtab.push_scope(HashMap::new()),
+            Err(InterpreterError::StackOverflow(MAX_CALL_DEPTH))
+        ));
+
+        symtab.set_max_call_depth(MAX_CALL_DEPTH + 1);
+
+        assert!(symtab.push_scope(HashMap::new()).is_ok());
+        assert!(matches!(
+            symtab.push_scope(HashMap::new()),
+            Err(InterpreterError::StackOverflow(depth)) if depth == MAX_CALL_DEPTH + 1
+        ));
+    }
+
+    #[test]
+    fn test_generation() {
+        let mut symtab = SymbolTable::new();
+        let function = Value::Function(Function::new(
+            "f".to_string(),
+            vec![],
+            Rc::new(NumberNode::new(1.)),
+        ));
+        let generation = symtab.generation();
+
+        symtab.insert("x".to_string(), 1.);
+        symtab.insert("x".to_string(), 2.);
+
+        assert_eq!(symtab.generation(), generation);
+
+        // Defining, replacing and removing a function each count
+        symtab.insert("f".to_string(), function.clone());
+        symtab.insert("f".to_string(), 1.);
+        symtab.remove("f");
+
+        assert_eq!(symtab.generation(), generation + 2);
+
+        symtab.insert("f".to_string(), function);
+
+        assert_eq!(
+            symtab.remove("f").map(|value| value.to_string()),
+            Some("f()".to_string())
+        );
+        assert_eq!(symtab.generation(), generation + 4);
+        assert_eq!(symtab.remove("f"), None);
+    }
+
+    #[test]
+    fn test_push_binding() {
+        let mut symtab = SymbolTable::new();
+
+        symtab
+            .push_scope([("a".to_string(), Value::Number(2.))].into())
+            .unwrap();
+        symtab
+            .push_binding("x".to_string(), Value::Number(3.))
+            .unwrap();
+
+        assert_eq!(symtab.get(&"a".to_string()), Some(&Value::Number(2.)));
+        assert_eq!(symtab.get(&"x".to_string()), Some(&Value::Number(3.)));
+
+        symtab.rebind("x", Value::Number(4.));
+
+        assert_eq!(symtab.get(&"x".to_string()), Some(&Value::Number(4.)));
+
+        symtab.pop_scope();
+
+        assert_eq!(symtab.get(&"x".to_string()), None);
+    }
+
     #[test]
     fn test_clear() {
         let mut symtab = SymbolTable::new();
 
         symtab.insert("x".to_string(), 123.45);
+        symtab.insert_constant("g".to_string(), 9.81);
 
-        assert_eq!(symtab.get(&"x".to_string()), Some(&123.45));
+        assert_eq!(symtab.get(&"x".to_string()), Some(&Value::Number(123.45)));
 
         symtab.clear();
 
         assert_eq!(symtab.get(&"x".to_string()), None);
-        assert_eq!(symtab.get(&"e".to_string()), Some(&consts::E));
-        assert_eq!(symtab.get(&"pi".to_string()), Some(&consts::PI));
+        assert_eq!(symtab.get(&"g".to_string()), None);
+        assert_eq!(
+            symtab.get(&"e".to_string()),
+            Some(&Value::Number(consts::E))
+        );
+        assert_eq!(
+            symtab.get(&"pi".to_string()),
+            Some(&Value::Number(consts::PI))
+        );
     }
 }
@@ -0,0 +1,366 @@
+use std::collections::{BTreeSet, HashMap};
+
+use crate::ast::{
+    ASTSemanticExpression, BinaryOpNode, BinaryOpType, CallNode, NodeKind, NumberNode, UnaryOpNode,
+    UnaryOpType, VarNode,
+};
+use crate::error::InterpreterError;
+use crate::symbol_table::SymbolTable;
+use crate::value::Value;
+
+mod simplify;
+
+pub use simplify::simplify;
+
+type Node = Box<dyn ASTSemanticExpression>;
+
+fn number<T: Into<Value>>(value: T) -> Node {
+    Box::new(NumberNode::new(value))
+}
+
+fn unary(node: Node, op_type: UnaryOpType) -> Node {
+    Box::new(UnaryOpNode::new(node, op_type))
+}
+
+fn binary(left: Node, right: Node, op_type: BinaryOpType) -> Node {
+    Box::new(BinaryOpNode::new(left, right, op_type))
+}
+
+fn call(name: &str, args: Vec<Node>) -> Node {
+    Box::new(CallNode::new(name.to_string(), args))
+}
+
+/// Copies a tree, replacing the variables in `bindings` with copies of
+/// their trees.
+pub fn substitute(
+    node: &dyn ASTSemanticExpression,
+    bindings: &HashMap<&str, &dyn ASTSemanticExpression>,
+) -> Result<Node, InterpreterError> {
+    match node.kind() {
+        NodeKind::Number(value) => Ok(number(value.clone())),
+        NodeKind::Var(name) => match bindings.get(name.as_str()) {
+            Some(binding) => copy(*binding),
+            None => Ok(Box::new(VarNode::new(name.clone()))),
+        },
+        NodeKind::Unary(operand, op_type) => Ok(unary(substitute(operand, bindings)?, op_type)),
+        NodeKind::Binary(left, right, op_type) => Ok(binary(
+            substitute(left, bindings)?,
+            substitute(right, bindings)?,
+            op_type,
+        )),
+        NodeKind::PercentChange(base, percent, op_type) => Ok(binary(
+            substitute(base, bindings)?,
+            binary(number(1.), substitute(percent, bindings)?, op_type),
+            BinaryOpType::MUL,
+        )),
+        NodeKind::Call(name, args) => Ok(call(
+            name,
+            args.iter()
+                .map(|arg| substitute(arg.as_ref(), bindings))
+                .collect::<Result<Vec<Node>, InterpreterError>>()?,
+        )),
+        _ => Err(unsupported()),
+    }
+}
+
+fn unsupported() -> InterpreterError {
+    InterpreterError::TypeError(
+        "only numbers, variables, operators and function calls can be transformed symbolically"
+            .to_string(),
+    )
+}
+
+pub fn copy(node: &dyn ASTSemanticExpression) -> Result<Node, InterpreterError> {
+    substitute(node, &HashMap::new())
+}
+
+/// Names of the variables the tree refers to.
+pub fn variables(node: &dyn ASTSemanticExpression) -> BTreeSet<String> {
+    let mut names = BTreeSet::new();
+
+    collect_variables(node, &mut names);
+
+    names
+}
+
+fn collect_variables(node: &dyn ASTSemanticExpression, names: &mut BTreeSet<String>) {
+    match node.kind() {
+        NodeKind::Var(name) => {
+            names.insert(name.clone());
+        }
+        NodeKind::Unary(operand, _) => collect_variables(operand, names),
+        NodeKind::Binary(left, right, _) | NodeKind::PercentChange(left, right, _) => {
+            collect_variables(left, names);
+            collect_variables(right, names);
+        }
+        NodeKind::Call(_, args) => {
+            for arg in args {
+                collect_variables(arg.as_ref(), names);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Whether the tree refers to the variable `var`.
+fn depends(node: &dyn ASTSemanticExpression, var: &str) -> bool {
+    match node.kind() {
+        NodeKind::Number(_) => false,
+        NodeKind::Var(name) => name == var,
+        NodeKind::Unary(operand, _) => depends(operand, var),
+        NodeKind::Binary(left, right, _) | NodeKind::PercentChange(left, right, _) => {
+            depends(left, var) || depends(right, var)
+        }
+        NodeKind::Call(_, args) => args.iter().any(|arg| depends(arg.as_ref(), var)),
+        _ => true,
+    }
+}
+
+/// Derivative of an expression with respect to `var`, by the rules of
+/// differentiation applied to each node. Other variables are constants,
+/// and calls to user functions are expanded with their body.
+pub fn diff(
+    node: &dyn ASTSemanticExpression,
+    var: &str,
+    symtab: &SymbolTable,
+) -> Result<Node, InterpreterError> {
+    let d = |node: &dyn ASTSemanticExpression| diff(node, var, symtab);
+    let error = |name: &str| {
+        InterpreterError::TypeError(format!("{} cannot be differentiated symbolically", name))
+    };
+
+    match node.kind() {
+        NodeKind::Number(_) => Ok(number(0.)),
+        NodeKind::Var(name) if name == var => Ok(number(1.)),
+        NodeKind::Var(name) => match symtab.get(name) {
+            Some(Value::Function(_)) => Err(InterpreterError::TypeError(format!(
+                "{} is a function, differentiate {}({}) instead",
+                name, name, var
+            ))),
+            _ => Ok(number(0.)),
+        },
+        NodeKind::Unary(operand, op_type) => match op_type {
+            UnaryOpType::PLUS => d(operand),
+            UnaryOpType::MINUS | UnaryOpType::PERCENT => Ok(unary(d(operand)?, op_type)),
+            _ => Err(error(&format!("{:?}", op_type))),
+        },
+        NodeKind::Binary(u, v, op_type) => match op_type {
+            BinaryOpType::ADD | BinaryOpType::SUB => Ok(binary(d(u)?, d(v)?, op_type)),
+            BinaryOpType::MUL | BinaryOpType::MATMUL => Ok(binary(
+                binary(d(u)?, copy(v)?, op_type),
+                binary(copy(u)?, d(v)?, op_type),
+                BinaryOpType::ADD,
+            )),
+            BinaryOpType::DIV => Ok(binary(
+                binary(
+                    binary(d(u)?, copy(v)?, BinaryOpType::MUL),
+                    binary(copy(u)?, d(v)?, BinaryOpType::MUL),
+                    BinaryOpType::SUB,
+                ),
+                binary(copy(v)?, number(2.), BinaryOpType::POW),
+                BinaryOpType::DIV,
+            )),
+            // n u^(n - 1) u'
+            BinaryOpType::POW if !depends(v, var) => Ok(binary(
+                binary(
+                    copy(v)?,
+                    binary(
+                        copy(u)?,
+                        binary(copy(v)?, number(1.), BinaryOpType::SUB),
+                        BinaryOpType::POW,
+                    ),
+                    BinaryOpType::MUL,
+                ),
+                d(u)?,
+                BinaryOpType::MUL,
+            )),
+            // a^v ln(a) v'
+            BinaryOpType::POW if !depends(u, var) => Ok(binary(
+                binary(
+                    binary(copy(u)?, copy(v)?, BinaryOpType::POW),
+                    call("ln", vec![copy(u)?]),
+                    BinaryOpType::MUL,
+                ),
+                d(v)?,
+                BinaryOpType::MUL,
+            )),
+            // u^v (v' ln(u) + v u' / u)
+            BinaryOpType::POW => Ok(binary(
+                binary(copy(u)?, copy(v)?, BinaryOpType::POW),
+                binary(
+                    binary(d(v)?, call("ln", vec![copy(u)?]), BinaryOpType::MUL),
+                    binary(
+                        binary(copy(v)?, d(u)?, BinaryOpType::MUL),
+                        copy(u)?,
+                        BinaryOpType::DIV,
+                    ),
+                    BinaryOpType::ADD,
+                ),
+                BinaryOpType::MUL,
+            )),
+            BinaryOpType::FLOORDIV => Ok(number(0.)),
+            // u - v (u // v), where the quotient is piecewise constant
+            BinaryOpType::MOD => Ok(binary(
+                d(u)?,
+                binary(
+                    binary(copy(u)?, copy(v)?, BinaryOpType::FLOORDIV),
+                    d(v)?,
+                    BinaryOpType::MUL,
+                ),
+                BinaryOpType::SUB,
+            )),
+            _ => Err(error(&format!("{:?}", op_type))),
+        },
+        NodeKind::PercentChange(..) => d(copy(node)?.as_ref()),
+        NodeKind::Call(name, args) => diff_call(name, args, var, symtab),
+        _ => Err(error("expression")),
+    }
+}
+
+/// Chain rule for calls, `f(u)' = f'(u) u'`.
+fn diff_call(
+    name: &str,
+    args: &[Node],
+    var: &str,
+    symtab: &SymbolTable,
+) -> Result<Node, InterpreterError> {
+    let d = |node: &dyn ASTSemanticExpression| diff(node, var, symtab);
+    let u = || copy(args[0].as_ref());
+    let chain = |outer: Node| -> Result<Node, InterpreterError> {
+        Ok(binary(outer, d(args[0].as_ref())?, BinaryOpType::MUL))
+    };
+
+    match name {
+        "ln" => chain(binary(number(1.), u()?, BinaryOpType::DIV)),
+        "log2" | "log10" => {
+            let base = if name == "log2" { 2. } else { 10. };
+
+            chain(binary(
+                number(1.),
+                binary(u()?, call("ln", vec![number(base)]), BinaryOpType::MUL),
+                BinaryOpType::DIV,
+            ))
+        }
+        "log" => d(binary(
+            call("ln", vec![u()?]),
+            call("ln", vec![copy(args[1].as_ref())?]),
+            BinaryOpType::DIV,
+        )
+        .as_ref()),
+        "pow" => d(binary(u()?, copy(args[1].as_ref())?, BinaryOpType::POW).as_ref()),
+        "sqrt" => chain(binary(
+            number(1.),
+            binary(number(2.), call("sqrt", vec![u()?]), BinaryOpType::MUL),
+            BinaryOpType::DIV,
+        )),
+        "pow2" | "pow10" => {
+            let base = if name == "pow2" { 2. } else { 10. };
+
+            chain(binary(
+                call(name, vec![u()?]),
+                call("ln", vec![number(base)]),
+                BinaryOpType::MUL,
+            ))
+        }
+        "exp" => chain(call("exp", vec![u()?])),
+        "sin" => chain(call("cos", vec![u()?])),
+        "cos" => chain(unary(call("sin", vec![u()?]), UnaryOpType::MINUS)),
+        "tan" => chain(binary(
+            number(1.),
+            binary(call("cos", vec![u()?]), number(2.), BinaryOpType::POW),
+            BinaryOpType::DIV,
+        )),
+        "abs" => chain(binary(u()?, call("abs", vec![u()?]), BinaryOpType::DIV)),
+        "ceil" | "floor" | "round" => Ok(number(0.)),
+        _ => match symtab.get(&name.to_string()) {
+            Some(Value::Function(function)) if function.params().len() == args.len() => {
+                let bindings = function
+                    .params()
+                    .iter()
+                    .map(|param| param.as_str())
+                    .zip(args.iter().map(|arg| arg.as_ref()))
+                    .collect();
+
+                d(substitute(function.body(), &bindings)?.as_ref())
+            }
+            _ => Err(InterpreterError::TypeError(format!(
+                "{} cannot be differentiated symbolically",
+                name
+            ))),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::tests::var;
+    use crate::ast::{ASTSemanticExpression, BinaryOpType, UnaryOpType};
+    use crate::format;
+    use crate::symbol_table::SymbolTable;
+
+    use super::{binary, call, diff, number, simplify, unary, variables};
+
+    fn derivative(node: Box<dyn ASTSemanticExpression>) -> String {
+        let symtab = SymbolTable::new();
+        let node = diff(node.as_ref(), "x", &symtab).unwrap();
+
+        format::expression(simplify(node.as_ref()).unwrap().as_ref())
+    }
+
+    #[test]
+    fn test_diff() {
+        // x^2 * y
+        let node = binary(
+            binary(var("x"), number(2.), BinaryOpType::POW),
+            var("y"),
+            BinaryOpType::MUL,
+        );
+
+        assert_eq!(derivative(node), "2 * x * y");
+
+        // 2^x
+        let node = binary(number(2.), var("x"), BinaryOpType::POW);
+
+        assert_eq!(derivative(node), "2^x * ln(2)");
+
+        // ln(-x)
+        let node = call("ln", vec![unary(var("x"), UnaryOpType::MINUS)]);
+
+        assert_eq!(derivative(node), "1 / x");
+
+        // x^2 * sin(x)
+        let node = binary(
+            binary(var("x"), number(2.), BinaryOpType::POW),
+            call("sin", vec![var("x")]),
+            BinaryOpType::MUL,
+        );
+
+        assert_eq!(derivative(node), "x^2 * cos(x) + 2 * x * sin(x)");
+    }
+
+    #[test]
+    fn test_variables() {
+        // ln(x) * y^x
+        let node = binary(
+            call("ln", vec![var("x")]),
+            binary(var("y"), var("x"), BinaryOpType::POW),
+            BinaryOpType::MUL,
+        );
+
+        assert_eq!(
+            variables(node.as_ref())
+                .into_iter()
+                .collect::<Vec<String>>(),
+            ["x", "y"]
+        );
+    }
+
+    #[test]
+    fn test_diff_err() {
+        let symtab = SymbolTable::new();
+        let node = binary(var("x"), number(3.), BinaryOpType::BITAND);
+
+        assert!(diff(node.as_ref(), "x", &symtab).is_err());
+        assert!(diff(call("foo", vec![var("x")]).as_ref(), "x", &symtab).is_err());
+    }
+}
@@ -0,0 +1,830 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::ast::{ASTSemanticExpression, BinaryOpType, NodeKind, UnaryOpType};
+use crate::builtins;
+use crate::error::InterpreterError;
+use crate::format;
+use crate::value::{BigInt, Rational, Value};
+
+use super::{binary, call, copy, number, unary, unsupported, Node};
+
+/// Largest power of a sum that is expanded, as in `(x + 1)^8`.
+const MAX_EXPANSION: i64 = 8;
+
+/// Largest power of a number that is folded into a coefficient.
+const MAX_EXPONENT: i64 = 1024;
+
+/// Factor that is not a number, identified by its simplified source text.
+/// Variables come before calls and other expressions in canonical order.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Atom {
+    rank: u8,
+    text: String,
+}
+
+/// Product of atoms raised to exact powers.
+type Monomial = BTreeMap<Atom, Rational>;
+
+/// Sum of distinct monomials with their nonzero coefficients.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+struct Sum {
+    terms: BTreeMap<Monomial, Rational>,
+}
+
+fn integer(value: i64) -> Rational {
+    Rational::from_bigint(BigInt::from_i64(value))
+}
+
+fn is_negative(value: &Rational) -> bool {
+    value.numerator().is_negative()
+}
+
+fn monomial_mul(left: &Monomial, right: &Monomial) -> Monomial {
+    let mut product = left.clone();
+
+    for (atom, exponent) in right {
+        let sum = match product.remove(atom) {
+            Some(other) => other.add(exponent),
+            None => exponent.clone(),
+        };
+
+        if !sum.is_zero() {
+            product.insert(atom.clone(), sum);
+        }
+    }
+
+    product
+}
+
+fn monomial_pow(monomial: &Monomial, exponent: &Rational) -> Monomial {
+    monomial
+        .iter()
+        .map(|(atom, power)| (atom.clone(), power.mul(exponent)))
+        .filter(|(_, power)| !power.is_zero())
+        .collect()
+}
+
+fn degree(monomial: &Monomial) -> Rational {
+    monomial
+        .values()
+        .fold(integer(0), |degree, exponent| degree.add(exponent))
+}
+
+/// `n^(1/q)` when it is an integer.
+fn exact_root(n: &BigInt, q: u32) -> Option<BigInt> {
+    if n.is_negative() && q.is_multiple_of(2) {
+        return None;
+    }
+
+    let root = n.abs().to_f64().powf(1. / q as f64).round();
+    let root = BigInt::from_f64(if n.is_negative() { -root } else { root })?;
+
+    (root.pow(q) == *n).then_some(root)
+}
+
+/// `value^exponent` when it is rational, e.g. `(8/27)^(2/3) = 4/9`.
+fn rational_pow(value: &Rational, exponent: &Rational) -> Option<Rational> {
+    let power = exponent.numerator().to_i64()?;
+
+    if power.abs() > MAX_EXPONENT {
+        return None;
+    }
+
+    let value = value.powi(power)?;
+    let q = u32::try_from(exponent.denominator().to_i64()?).ok()?;
+
+    Rational::new(
+        exact_root(value.numerator(), q)?,
+        exact_root(value.denominator(), q)?,
+    )
+}
+
+impl Sum {
+    fn term(monomial: Monomial, coefficient: Rational) -> Self {
+        let mut sum = Self::default();
+
+        sum.add_term(monomial, coefficient);
+        sum
+    }
+
+    fn constant(value: Rational) -> Self {
+        Self::term(Monomial::new(), value)
+    }
+
+    fn atom(atom: Atom, exponent: Rational) -> Self {
+        match exponent.is_zero() {
+            true => Self::constant(integer(1)),
+            false => Self::term([(atom, exponent)].into(), integer(1)),
+        }
+    }
+
+    fn add_term(&mut self, monomial: Monomial, coefficient: Rational) {
+        let sum = match self.terms.remove(&monomial) {
+            Some(other) => other.add(&coefficient),
+            None => coefficient,
+        };
+
+        if !sum.is_zero() {
+            self.terms.insert(monomial, sum);
+        }
+    }
+
+    fn add(mut self, other: Self) -> Self {
+        for (monomial, coefficient) in other.terms {
+            self.add_term(monomial, coefficient);
+        }
+
+        self
+    }
+
+    fn neg(self) -> Self {
+        self.mul(&Self::constant(integer(-1)))
+    }
+
+    /// Product with every term multiplied out.
+    fn mul(&self, other: &Self) -> Self {
+        let mut product = Self::default();
+
+        for (left, a) in self.terms.iter() {
+            for (right, b) in other.terms.iter() {
+                product.add_term(monomial_mul(left, right), a.mul(b));
+            }
+        }
+
+        product
+    }
+
+    fn as_term(&self) -> Option<(&Monomial, &Rational)> {
+        match self.terms.len() {
+            1 => self.terms.iter().next(),
+            _ => None,
+        }
+    }
+
+    fn as_constant(&self) -> Option<Rational> {
+        match self.as_term() {
+            Some((monomial, coefficient)) if monomial.is_empty() => Some(coefficient.clone()),
+            None if self.terms.is_empty() => Some(integer(0)),
+            _ => None,
+        }
+    }
+
+    /// Terms by decreasing degree, then by their atoms, e.g.
+    /// `x^2 * y + x * y^2 + x + 1`.
+    fn ordered(&self) -> Vec<(&Monomial, &Rational)> {
+        let mut terms: Vec<(&Monomial, &Rational)> = self.terms.iter().collect();
+
+        terms.sort_by_cached_key(|(monomial, _)| {
+            let atoms: Vec<(&Atom, Rational)> = monomial
+                .iter()
+                .map(|(atom, exponent)| (atom, exponent.neg()))
+                .collect();
+
+            (Reverse(degree(monomial)), atoms)
+        });
+        terms
+    }
+
+    /// Power with an exact exponent, when it has a canonical form. Powers
+    /// of a single atom multiply its exponent, small natural powers of sums
+    /// are expanded.
+    fn pow(&self, exponent: &Rational) -> Option<Self> {
+        if let Some((monomial, coefficient)) = self.as_term() {
+            let single = monomial.len() == 1 && monomial.values().all(|e| *e == integer(1));
+
+            if !exponent.is_integer() && !monomial.is_empty() && !single {
+                return None;
+            }
+
+            return Some(Self::term(
+                monomial_pow(monomial, exponent),
+                rational_pow(coefficient, exponent)?,
+            ));
+        }
+
+        if self.terms.is_empty() {
+            return (!is_negative(exponent) && !exponent.is_zero()).then(Self::default);
+        }
+
+        let n = exponent
+            .is_integer()
+            .then(|| exponent.numerator().to_i64())??;
+
+        if !(0..=MAX_EXPANSION).contains(&n) {
+            return None;
+        }
+
+        Some((0..n).fold(Self::constant(integer(1)), |power, _| power.mul(self)))
+    }
+
+    /// Splits off the greatest common factor of the terms, signed like the
+    /// leading term, so that `-2 * x^2 - 4 * x` is `-2 * x * (x + 2)`.
+    fn primitive(&self) -> (Self, Self) {
+        let mut terms = self.ordered().into_iter();
+        let Some((first, leading)) = terms.next() else {
+            return (Self::constant(integer(1)), self.clone());
+        };
+        let (mut numerator, mut denominator) =
+            (leading.numerator().abs(), leading.denominator().clone());
+        let mut common = first.clone();
+
+        for (monomial, coefficient) in terms {
+            numerator = numerator.gcd(coefficient.numerator());
+            denominator = denominator
+                .mul(coefficient.denominator())
+                .div_rem(&denominator.gcd(coefficient.denominator()))
+                .map_or(denominator.clone(), |(lcm, _)| lcm);
+            common = common
+                .into_iter()
+                .filter_map(|(atom, exponent)| {
+                    let other = monomial.get(&atom)?;
+
+                    Some((
+                        atom,
+                        if *other < exponent {
+                            other.clone()
+                        } else {
+                            exponent
+                        },
+                    ))
+                })
+                .collect();
+        }
+
+        let mut coefficient = Rational::new(numerator, denominator).unwrap_or(integer(1));
+
+        if is_negative(leading) {
+            coefficient = coefficient.neg();
+        }
+
+        let inverse = Sum::term(
+            monomial_pow(&common, &integer(-1)),
+            integer(1).div(&coefficient).unwrap_or(integer(1)),
+        );
+
+        (Self::term(common, coefficient), self.mul(&inverse))
+    }
+
+    /// Coefficients of the sum as a polynomial in `atom`, lowest power
+    /// first, each a sum of the other atoms. Only natural powers of the
+    /// atom make a polynomial.
+    fn coefficients(&self, atom: &Atom) -> Option<Vec<Sum>> {
+        let mut coefficients: Vec<Sum> = vec![];
+
+        for (monomial, coefficient) in self.terms.iter() {
+            let mut rest = monomial.clone();
+            let power = match rest.remove(atom) {
+                Some(exponent) => natural(&exponent)?,
+                None => 0,
+            };
+
+            if coefficients.len() <= power {
+                coefficients.resize(power + 1, Sum::default());
+            }
+
+            coefficients[power].add_term(rest, coefficient.clone());
+        }
+
+        Some(coefficients)
+    }
+
+    /// Exact quotient of polynomials, `(x^2 - y^2) / (x - y)`, by long
+    /// division in their first atom with the coefficients divided in turn.
+    fn divide(&self, divisor: &Self) -> Option<Self> {
+        if let Some(value) = divisor.as_constant() {
+            let inverse = integer(1).div(&value)?;
+
+            return Some(self.mul(&Self::constant(inverse)));
+        }
+
+        let atom = first_atom(self, divisor)?;
+        let divisor = divisor.coefficients(&atom)?;
+        let mut remainder = self.coefficients(&atom)?;
+        let last = divisor.len() - 1;
+
+        if remainder.len() < divisor.len() {
+            return self.terms.is_empty().then(Self::default);
+        }
+
+        let mut quotient = vec![Sum::default(); remainder.len() - last];
+
+        for i in (0..quotient.len()).rev() {
+            let coefficient = remainder[i + last].divide(&divisor[last])?;
+
+            for (j, d) in divisor.iter().enumerate() {
+                remainder[i + j] = remainder[i + j].clone().add(coefficient.mul(d).neg());
+            }
+
+            quotient[i] = coefficient;
+        }
+
+        remainder
+            .iter()
+            .all(|coefficient| coefficient.terms.is_empty())
+            .then(|| polynomial(&atom, quotient))
+    }
+
+    /// Greatest common divisor of polynomials up to a constant factor, by
+    /// the Euclidean algorithm in their first atom on pseudo-remainders, and
+    /// recursively on the coefficients for the content.
+    fn gcd(&self, other: &Self) -> Option<Self> {
+        if self.terms.is_empty() {
+            return Some(other.clone());
+        }
+
+        if other.terms.is_empty() {
+            return Some(self.clone());
+        }
+
+        let Some(atom) = first_atom(self, other) else {
+            return Some(Self::constant(integer(1)));
+        };
+        let (a, b) = (self.coefficients(&atom)?, other.coefficients(&atom)?);
+        let (content_a, content_b) = (content(&a)?, content(&b)?);
+        let common = content_a.gcd(&content_b)?;
+
+        if a.len() == 1 || b.len() == 1 {
+            return Some(common);
+        }
+
+        let (mut a, mut b) = (
+            primitive_part(a, &content_a)?,
+            primitive_part(b, &content_b)?,
+        );
+
+        if a.len() < b.len() {
+            (a, b) = (b, a);
+        }
+
+        while b.len() > 1 {
+            let remainder = pseudo_remainder(a, &b);
+
+            if remainder.is_empty() {
+                break;
+            }
+
+            let content = content(&remainder)?;
+
+            (a, b) = (b, primitive_part(remainder, &content)?);
+        }
+
+        match b.len() {
+            1 => Some(common),
+            _ => Some(polynomial(&atom, b).mul(&common)),
+        }
+    }
+}
+
+/// A natural exponent, small enough to divide by.
+fn natural(exponent: &Rational) -> Option<usize> {
+    let power = exponent
+        .is_integer()
+        .then(|| exponent.numerator().to_i64())??;
+
+    (0..=MAX_EXPONENT)
+        .contains(&power)
+        .then_some(power as usize)
+}
+
+/// The atom polynomials are divided in, the first of either.
+fn first_atom(a: &Sum, b: &Sum) -> Option<Atom> {
+    a.terms
+        .keys()
+        .chain(b.terms.keys())
+        .filter_map(|monomial| monomial.keys().next())
+        .min()
+        .cloned()
+}
+
+/// `Σ coefficients[i] * atom^i`.
+fn polynomial(atom: &Atom, coefficients: Vec<Sum>) -> Sum {
+    coefficients
+        .into_iter()
+        .enumerate()
+        .fold(Sum::default(), |sum, (i, coefficient)| {
+            sum.add(Sum::atom(atom.clone(), integer(i as i64)).mul(&coefficient))
+        })
+}
+
+fn content(coefficients: &[Sum]) -> Option<Sum> {
+    coefficients
+        .iter()
+        .try_fold(Sum::default(), |content, coefficient| {
+            content.gcd(coefficient)
+        })
+}
+
+fn primitive_part(coefficients: Vec<Sum>, content: &Sum) -> Option<Vec<Sum>> {
+    coefficients
+        .iter()
+        .map(|coefficient| coefficient.divide(content))
+        .collect()
+}
+
+/// Remainder of `a` by `b` after multiplying `a` by powers of the leading
+/// coefficient of `b`, so that no coefficient is divided. Trailing zero
+/// coefficients are dropped, an exact division leaves none.
+fn pseudo_remainder(mut a: Vec<Sum>, b: &[Sum]) -> Vec<Sum> {
+    let leading = &b[b.len() - 1];
+
+    while a.len() >= b.len() {
+        let shift = a.len() - b.len();
+        let factor = a[a.len() - 1].clone();
+
+        a = a
+            .iter()
+            .map(|coefficient| coefficient.mul(leading))
+            .collect();
+
+        for (j, d) in b.iter().enumerate() {
+            a[shift + j] = a[shift + j].clone().add(factor.mul(d).neg());
+        }
+
+        while a
+            .last()
+            .is_some_and(|coefficient| coefficient.terms.is_empty())
+        {
+            a.pop();
+        }
+    }
+
+    a
+}
+
+/// Exact value of a literal, e.g. `0.1` is `1/10`.
+fn literal(value: &Value) -> Option<Rational> {
+    match value {
+        Value::Number(value) => Rational::from_f64(*value),
+        Value::Integer(value) => Some(Rational::from_bigint(value.clone())),
+        Value::Rational(value) => Some(value.clone()),
+        _ => None,
+    }
+}
+
+/// Exact value of a computed result, rejecting rounded floating point.
+fn exact(value: &Value) -> Option<Rational> {
+    match value {
+        Value::Number(value) if value.fract() != 0. => None,
+        value => literal(value),
+    }
+}
+
+/// Atoms of an expression in canonical form, with the tree each stands for.
+#[derive(Default)]
+struct Canonical {
+    atoms: HashMap<String, Node>,
+}
+
+impl Canonical {
+    fn register(&mut self, node: Node) -> Atom {
+        let text = format::expression(node.as_ref());
+        let rank = match node.kind() {
+            NodeKind::Var(_) => 0,
+            _ => 1,
+        };
+
+        self.atoms.entry(text.clone()).or_insert(node);
+
+        Atom { rank, text }
+    }
+
+    fn atom(&mut self, node: Node) -> Sum {
+        Sum::atom(self.register(node), integer(1))
+    }
+
+    fn sum(&mut self, node: &dyn ASTSemanticExpression) -> Result<Sum, InterpreterError> {
+        match node.kind() {
+            NodeKind::Number(value) => match literal(value) {
+                Some(value) => Ok(Sum::constant(value)),
+                None => Ok(self.atom(copy(node)?)),
+            },
+            NodeKind::Var(_) => Ok(self.atom(copy(node)?)),
+            NodeKind::Unary(operand, op_type) => {
+                let operand = self.sum(operand)?;
+
+                match op_type {
+                    UnaryOpType::PLUS => Ok(operand),
+                    UnaryOpType::MINUS => Ok(operand.neg()),
+                    UnaryOpType::PERCENT => Ok(operand.mul(&Sum::constant(
+                        integer(1).div(&integer(100)).unwrap_or(integer(0)),
+                    ))),
+                    _ => {
+                        if let Some(value) = operand.as_constant() {
+                            if let Some(value) = op_type
+                                .apply(&Value::Rational(value))
+                                .ok()
+                                .and_then(|value| exact(&value))
+                            {
+                                return Ok(Sum::constant(value));
+                            }
+                        }
+
+                        let operand = self.build(&operand)?;
+
+                        Ok(self.atom(unary(operand, op_type)))
+                    }
+                }
+            }
+            NodeKind::Binary(left, right, op_type) => {
+                let (left, right) = (self.sum(left)?, self.sum(right)?);
+
+                match op_type {
+                    BinaryOpType::ADD => Ok(left.add(right)),
+                    BinaryOpType::SUB => Ok(left.add(right.neg())),
+                    BinaryOpType::MUL => Ok(left.mul(&right)),
+                    BinaryOpType::DIV => self.div(left, right),
+                    BinaryOpType::POW => self.pow(left, right),
+                    _ => {
+                        if let (Some(a), Some(b)) = (left.as_constant(), right.as_constant()) {
+                            if let Some(value) = op_type
+                                .apply(&Value::Rational(a), &Value::Rational(b))
+                                .ok()
+                                .and_then(|value| exact(&value))
+                            {
+                                return Ok(Sum::constant(value));
+                            }
+                        }
+
+                        let node = binary(self.build(&left)?, self.build(&right)?, op_type);
+
+                        Ok(self.atom(node))
+                    }
+                }
+            }
+            NodeKind::PercentChange(..) => self.sum(copy(node)?.as_ref()),
+            NodeKind::Call(name, args) => {
+                let args = args
+                    .iter()
+                    .map(|arg| self.sum(arg.as_ref()))
+                    .collect::<Result<Vec<Sum>, InterpreterError>>()?;
+                let values: Option<Vec<Value>> = args
+                    .iter()
+                    .map(|arg| arg.as_constant().map(Value::Rational))
+                    .collect();
+
+                if let (Some(builtin), Some(values)) = (builtins::lookup(name), values) {
+                    if let Some(value) = builtin.call(&values).ok().and_then(|v| exact(&v)) {
+                        return Ok(Sum::constant(value));
+                    }
+                }
+
+                let args = args
+                    .iter()
+                    .map(|arg| self.build(arg))
+                    .collect::<Result<Vec<Node>, InterpreterError>>()?;
+
+                Ok(self.atom(call(name, args)))
+            }
+            _ => Err(unsupported()),
+        }
+    }
+
+    /// Quotient with common factors cancelled. Sums that do not divide
+    /// evenly are kept as a single fraction.
+    fn div(&mut self, numerator: Sum, denominator: Sum) -> Result<Sum, InterpreterError> {
+        if let Some((monomial, coefficient)) = denominator.as_term() {
+            if let Some(inverse) = integer(1).div(coefficient) {
+                let inverse = Sum::term(monomial_pow(monomial, &integer(-1)), inverse);
+
+                return Ok(numerator.mul(&inverse));
+            }
+        }
+
+        if numerator.terms.is_empty() {
+            return Ok(numerator);
+        }
+
+        if denominator.terms.is_empty() {
+            let node = binary(
+                self.build(&numerator)?,
+                self.build(&denominator)?,
+                BinaryOpType::DIV,
+            );
+
+            return Ok(self.atom(node));
+        }
+
+        let (a, numerator) = numerator.primitive();
+        let (b, denominator) = denominator.primitive();
+        let content = self.div(a, b)?;
+
+        if numerator == denominator {
+            return Ok(content);
+        }
+
+        if numerator == denominator.clone().neg() {
+            return Ok(content.neg());
+        }
+
+        // Common factors that are sums, `(x^2 - 1) / (x^2 + 2 * x + 1)` is
+        // `(x - 1) / (x + 1)`.
+        if let Some(common) = numerator
+            .gcd(&denominator)
+            .filter(|common| common.as_constant().is_none())
+        {
+            if let (Some(numerator), Some(denominator)) =
+                (numerator.divide(&common), denominator.divide(&common))
+            {
+                return Ok(self.div(numerator, denominator)?.mul(&content));
+            }
+        }
+
+        let denominator = self.build(&denominator)?;
+
+        if numerator.as_constant() == Some(integer(1)) {
+            return Ok(Sum::atom(self.register(denominator), integer(-1)).mul(&content));
+        }
+
+        let node = binary(self.build(&numerator)?, denominator, BinaryOpType::DIV);
+
+        Ok(self.atom(node).mul(&content))
+    }
+
+    fn pow(&mut self, base: Sum, exponent: Sum) -> Result<Sum, InterpreterError> {
+        let Some(exponent) = exponent.as_constant() else {
+            let node = binary(
+                self.build(&base)?,
+                self.build(&exponent)?,
+                BinaryOpType::POW,
+            );
+
+            return Ok(self.atom(node));
+        };
+
+        if let Some(power) = base.pow(&exponent) {
+            return Ok(power);
+        }
+
+        let base = self.build(&base)?;
+
+        Ok(Sum::atom(self.register(base), exponent))
+    }
+
+    fn build(&self, sum: &Sum) -> Result<Node, InterpreterError> {
+        let mut result: Option<Node> = None;
+
+        for (monomial, coefficient) in sum.ordered() {
+            result = Some(match result {
+                None => self.term(monomial, coefficient)?,
+                Some(left) if is_negative(coefficient) => binary(
+                    left,
+                    self.term(monomial, &coefficient.neg())?,
+                    BinaryOpType::SUB,
+                ),
+                Some(left) => binary(left, self.term(monomial, coefficient)?, BinaryOpType::ADD),
+            });
+        }
+
+        Ok(result.unwrap_or_else(|| number(0.)))
+    }
+
+    /// A term as `coefficient * factors / factors`, e.g. `-3 * x / (2 * y)`.
+    fn term(&self, monomial: &Monomial, coefficient: &Rational) -> Result<Node, InterpreterError> {
+        let mut numerator: Vec<Node> = vec![];
+        let mut denominator: Vec<Node> = vec![];
+        let unit = coefficient.numerator().abs() == BigInt::from_i64(1);
+
+        if !unit || monomial.values().all(is_negative) {
+            numerator.push(self.integer(coefficient.numerator()));
+        }
+
+        if !coefficient.is_integer() {
+            denominator.push(self.integer(coefficient.denominator()));
+        }
+
+        for (atom, exponent) in monomial {
+            let node = copy(self.atoms[&atom.text].as_ref())?;
+            let node = match exponent.numerator().abs() == *exponent.denominator() {
+                true => node,
+                false => binary(node, self.exponent(exponent), BinaryOpType::POW),
+            };
+
+            match is_negative(exponent) {
+                true => denominator.push(node),
+                false if unit && is_negative(coefficient) && numerator.is_empty() => {
+                    numerator.push(unary(node, UnaryOpType::MINUS))
+                }
+                false => numerator.push(node),
+            }
+        }
+
+        let product = |nodes: Vec<Node>| {
+            nodes
+                .into_iter()
+                .reduce(|left, right| binary(left, right, BinaryOpType::MUL))
+        };
+
+        Ok(match (product(numerator), product(denominator)) {
+            (Some(numerator), Some(denominator)) => {
+                binary(numerator, denominator, BinaryOpType::DIV)
+            }
+            (Some(numerator), None) => numerator,
+            (None, _) => number(0.),
+        })
+    }
+
+    /// Absolute value of an exponent, `2` or `(1 / 2)`.
+    fn exponent(&self, exponent: &Rational) -> Node {
+        let numerator = self.integer(&exponent.numerator().abs());
+
+        match exponent.is_integer() {
+            true => numerator,
+            false => binary(
+                numerator,
+                self.integer(exponent.denominator()),
+                BinaryOpType::DIV,
+            ),
+        }
+    }
+
+    fn integer(&self, value: &BigInt) -> Node {
+        match value.to_i64() {
+            Some(value) if value.unsigned_abs() < 1 << 53 => number(value as f64),
+            _ => number(Value::Integer(value.clone())),
+        }
+    }
+}
+
+/// Canonical form of an expression: like terms are collected, constants
+/// combined exactly, products and small powers of sums expanded, common
+/// factors cancelled and terms ordered by decreasing degree, so that
+/// `(x + 1)^2 - x * (x + 2)` is `1`.
+pub fn simplify(node: &dyn ASTSemanticExpression) -> Result<Node, InterpreterError> {
+    let mut canonical = Canonical::default();
+    let sum = canonical.sum(node)?;
+
+    canonical.build(&sum)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::format;
+    use crate::parser::Parser;
+    use crate::tokenizer::Tokenizer;
+
+    use super::simplify;
+
+    fn canonical(content: &str) -> String {
+        let tokens = Tokenizer::new(content).try_collect().unwrap();
+        let node = Parser::new(tokens).expr().unwrap();
+
+        format::expression(simplify(node.as_ref()).unwrap().as_ref())
+    }
+
+    #[test]
+    fn test_like_terms() {
+        assert_eq!(canonical("2 * x + 3 * y - x + y * 2"), "x + 5 * y");
+        assert_eq!(canonical("(x + 1)^2 - x * (x + 2)"), "1");
+        assert_eq!(canonical("y * x^2 + 3 - x * x"), "x^2 * y - x^2 + 3");
+        assert_eq!(canonical("-(x - y)"), "-x + y");
+    }
+
+    #[test]
+    fn test_constants() {
+        assert_eq!(canonical("0.1 * x + x / 10"), "x / 5");
+        assert_eq!(canonical("sqrt(16) + 2^10"), "1028");
+        assert_eq!(canonical("8^(2 / 3) / 3"), "4 / 3");
+        assert_eq!(canonical("ln(2) * 2"), "2 * ln(2)");
+    }
+
+    #[test]
+    fn test_cancel() {
+        assert_eq!(canonical("x * y / (x * z)"), "y / z");
+        assert_eq!(canonical("(2 * x + 2) / (4 * x + 4)"), "1 / 2");
+        assert_eq!(canonical("(x^2 - 1) / (x - 1)"), "x + 1");
+        assert_eq!(canonical("(y - x) / (x - y)"), "-1");
+        assert_eq!(canonical("(x + y) / (x - y)"), "(x + y) / (x - y)");
+        assert_eq!(canonical("3 / (x^2 + x)"), "3 / (x * (x + 1))");
+        assert_eq!(
+            canonical("(x^3 - 1) / (x^2 - 1)"),
+            "(x^2 + x + 1) / (x + 1)"
+        );
+        assert_eq!(canonical("(x^2 - y^2) / (x - y)"), "x + y");
+        assert_eq!(
+            canonical("(x^2 - 1) / (x^2 + 2 * x + 1)"),
+            "(x - 1) / (x + 1)"
+        );
+        assert_eq!(
+            canonical("(x * y + y) / (x^2 * y + 2 * x * y + y)"),
+            "1 / (x + 1)"
+        );
+    }
+
+    #[test]
+    fn test_canonical_order() {
+        assert_eq!(canonical("ln(1 + x) - ln(x + 1)"), "0");
+        assert_eq!(canonical("1 + y * x + x^2"), "x^2 + x * y + 1");
+        assert_eq!(canonical("x^(1 / 2) * x^(1 / 2)"), "x");
+        assert_eq!(canonical("(x^2)^(1 / 2)"), "(x^2)^(1 / 2)");
+        assert_eq!(canonical("x^0 + (x * y)^0"), "2");
+    }
+
+    #[test]
+    fn test_unsupported() {
+        let tokens = Tokenizer::new("[1 .. 2] * x").try_collect().unwrap();
+        let node = Parser::new(tokens).expr().unwrap();
+
+        assert!(simplify(node.as_ref()).is_err());
+    }
+}
@@ -1,15 +1,53 @@
+use crate::value::BigInt;
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum Token {
     NUMBER(f64),
+    INTEGER(BigInt),
+    DECIMAL(String),
+    IMAGINARY(f64),
     ID(String),
+    CONST,
+    MEMO,
+    TO,
+    IF,
+    THEN,
+    ELSE,
     PLUS,
     MINUS,
+    PLUSMINUS,
     MUL,
+    MATMUL,
     DIV,
+    FLOORDIV,
+    POW,
+    FACTORIAL,
+    PERCENT,
+    BITAND,
+    BITOR,
+    BITNOT,
+    XOR,
+    SHL,
+    SHR,
+    LT,
+    LE,
+    GT,
+    GE,
+    EQ,
+    NE,
     LPAREN,
     RPAREN,
+    LBRACKET,
+    RBRACKET,
     COMMA,
     DOT,
+    RANGE,
     ASSIGN,
+    ADDASSIGN,
+    SUBASSIGN,
+    MULASSIGN,
+    DIVASSIGN,
+    POWASSIGN,
+    ARROW,
     SEMI,
 }
@@ -1,5 +1,6 @@
 use crate::error::InterpreterError;
 use crate::token::Token;
+use crate::value::{BigInt, Value};
 
 pub struct Tokenizer {
     charvec: Vec<char>,
@@ -34,6 +35,7 @@ impl Tokenizer {
         Ok(tokens)
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn next(&mut self) -> Result<Token, InterpreterError> {
         self.skip_char();
         self.skip_comment();
@@ -41,7 +43,7 @@ impl Tokenizer {
         self.peek_char()
             .ok_or(InterpreterError::EOF)
             .and_then(|ch| {
-                if ch.is_ascii_digit() {
+                if ch.is_ascii_digit() || (ch == '.' && self.peek_digit_after()) {
                     self.next_number()
                 } else if ch.is_ascii_alphabetic() || ch == '_' {
                     self.next_identity()
@@ -49,15 +51,38 @@ impl Tokenizer {
                     self.next_char();
 
                     match ch {
-                        '+' => Ok(Token::PLUS),
-                        '-' => Ok(Token::MINUS),
-                        '*' => Ok(Token::MUL),
-                        '/' => Ok(Token::DIV),
+                        '+' => Ok(self.next_assign_or(Token::PLUS, Token::ADDASSIGN)),
+                        '-' if self.peek_char() == Some('>') => {
+                            self.next_char();
+                            Ok(Token::ARROW)
+                        }
+                        '-' => Ok(self.next_assign_or(Token::MINUS, Token::SUBASSIGN)),
+                        '±' => Ok(Token::PLUSMINUS),
+                        '*' => Ok(self.next_assign_or(Token::MUL, Token::MULASSIGN)),
+                        '/' if self.peek_char() == Some('/') => {
+                            self.next_char();
+                            Ok(Token::FLOORDIV)
+                        }
+                        '/' => Ok(self.next_assign_or(Token::DIV, Token::DIVASSIGN)),
+                        '^' => Ok(self.next_assign_or(Token::POW, Token::POWASSIGN)),
+                        '@' => Ok(Token::MATMUL),
+                        '!' => Ok(self.next_assign_or(Token::FACTORIAL, Token::NE)),
+                        '%' => Ok(Token::PERCENT),
+                        '&' => Ok(Token::BITAND),
+                        '|' => Ok(Token::BITOR),
+                        '~' => Ok(Token::BITNOT),
+                        '<' | '>' => Ok(self.next_angle(ch)),
                         '(' => Ok(Token::LPAREN),
                         ')' => Ok(Token::RPAREN),
+                        '[' => Ok(Token::LBRACKET),
+                        ']' => Ok(Token::RBRACKET),
                         ',' => Ok(Token::COMMA),
+                        '.' if self.peek_char() == Some('.') => {
+                            self.next_char();
+                            Ok(Token::RANGE)
+                        }
                         '.' => Ok(Token::DOT),
-                        '=' => Ok(Token::ASSIGN),
+                        '=' => Ok(self.next_assign_or(Token::ASSIGN, Token::EQ)),
                         ';' => Ok(Token::SEMI),
                         _ => Err(InterpreterError::InvalidSyntax(ch.to_string())),
                     }
@@ -65,7 +90,58 @@ impl Tokenizer {
             })
     }
 
+    /// `<` and `>` start a shift when doubled and a comparison otherwise.
+    fn next_angle(&mut self, ch: char) -> Token {
+        match (ch, self.peek_char()) {
+            ('<', Some('<')) => {
+                self.next_char();
+                Token::SHL
+            }
+            ('>', Some('>')) => {
+                self.next_char();
+                Token::SHR
+            }
+            ('<', _) => self.next_assign_or(Token::LT, Token::LE),
+            _ => self.next_assign_or(Token::GT, Token::GE),
+        }
+    }
+
+    /// A number directly followed by `i` or `j` is an imaginary literal.
     fn next_number(&mut self) -> Result<Token, InterpreterError> {
+        let token = self.next_real_number()?;
+        let imaginary = matches!(self.peek_char(), Some('i' | 'j'))
+            && !self
+                .charvec
+                .get(self.ptr + 1)
+                .is_some_and(|ch| ch.is_ascii_alphanumeric() || *ch == '_');
+
+        if !imaginary {
+            return Ok(token);
+        }
+
+        self.next_char();
+
+        match token {
+            Token::NUMBER(value) => Ok(Token::IMAGINARY(value)),
+            Token::INTEGER(value) => Ok(Token::IMAGINARY(value.to_f64())),
+            Token::DECIMAL(text) => Ok(Token::IMAGINARY(text.parse().unwrap())),
+            _ => Ok(token),
+        }
+    }
+
+    fn next_real_number(&mut self) -> Result<Token, InterpreterError> {
+        if let Some('0') = self.peek_char() {
+            let radix = match self.charvec.get(self.ptr + 1) {
+                Some('x') | Some('X') => Some(16),
+                Some('b') | Some('B') => Some(2),
+                _ => None,
+            };
+
+            if let Some(radix) = radix {
+                return self.next_radix_number(radix);
+            }
+        }
+
         let mut cur = String::new();
 
         while let Some(ch) = self.peek_char() {
@@ -77,11 +153,9 @@ impl Tokenizer {
         }
 
         if let Some(ch) = self.peek_char() {
-            if ch != '.' {
-                return cur
-                    .parse::<f64>()
-                    .map(|value| Token::NUMBER(value))
-                    .map_err(|_| InterpreterError::InvalidSyntax(cur));
+            // `1..2` is a range, not the number `1.` followed by `.2`.
+            if ch != '.' || self.charvec.get(self.ptr + 1) == Some(&'.') {
+                return integer_token(BigInt::parse(&cur), cur);
             }
 
             cur.push(self.next_char().unwrap());
@@ -95,9 +169,39 @@ impl Tokenizer {
             }
         }
 
-        cur.parse::<f64>()
-            .map(|value| Token::NUMBER(value))
-            .map_err(|_| InterpreterError::InvalidSyntax(cur))
+        if !cur.contains('.') {
+            return integer_token(BigInt::parse(&cur), cur);
+        }
+
+        let value = cur
+            .parse::<f64>()
+            .map_err(|_| InterpreterError::InvalidSyntax(cur.clone()))?;
+
+        // Decimal literals that are not exact as an `f64` keep their digits.
+        if fraction_digits(&cur) != value.to_string() {
+            return Ok(Token::DECIMAL(cur));
+        }
+
+        Ok(Token::NUMBER(value))
+    }
+
+    fn next_radix_number(&mut self, radix: u32) -> Result<Token, InterpreterError> {
+        let mut cur = String::new();
+
+        cur.push(self.next_char().unwrap());
+        cur.push(self.next_char().unwrap());
+
+        let prefix_len = cur.len();
+
+        while let Some(ch) = self.peek_char() {
+            if !ch.is_ascii_alphanumeric() {
+                break;
+            }
+
+            cur.push(self.next_char().unwrap());
+        }
+
+        integer_token(BigInt::parse_radix(&cur[prefix_len..], radix), cur)
     }
 
     fn next_identity(&mut self) -> Result<Token, InterpreterError> {
@@ -111,7 +215,32 @@ impl Tokenizer {
             cur.push(self.next_char().unwrap());
         }
 
-        Ok(Token::ID(cur))
+        match cur.as_str() {
+            "xor" => Ok(Token::XOR),
+            "const" => Ok(Token::CONST),
+            "memo" => Ok(Token::MEMO),
+            "if" => Ok(Token::IF),
+            "then" => Ok(Token::THEN),
+            "else" => Ok(Token::ELSE),
+            "to" => Ok(Token::TO),
+            _ => Ok(Token::ID(cur)),
+        }
+    }
+
+    /// Whether a digit follows the current character, as in `.5`.
+    fn peek_digit_after(&self) -> bool {
+        self.charvec
+            .get(self.ptr + 1)
+            .is_some_and(|ch| ch.is_ascii_digit())
+    }
+
+    fn next_assign_or(&mut self, token: Token, assign_token: Token) -> Token {
+        if let Some('=') = self.peek_char() {
+            self.next_char();
+            assign_token
+        } else {
+            token
+        }
     }
 
     fn skip_char(&mut self) {
@@ -157,9 +286,33 @@ impl Tokenizer {
     }
 }
 
+/// A literal such as `007.50` in the shortest form, `7.5`, the way an `f64`
+/// prints it.
+fn fraction_digits(cur: &str) -> String {
+    let (integer, fraction) = cur.split_once('.').unwrap_or((cur, ""));
+    let integer = match integer.trim_start_matches('0') {
+        "" => "0",
+        integer => integer,
+    };
+
+    match fraction.trim_end_matches('0') {
+        "" => integer.to_string(),
+        fraction => format!("{}.{}", integer, fraction),
+    }
+}
+
+/// Integer literals that are not exact as an `f64` keep all their digits.
+fn integer_token(value: Option<BigInt>, cur: String) -> Result<Token, InterpreterError> {
+    match value.map(Value::from_bigint) {
+        Some(Value::Number(value)) => Ok(Token::NUMBER(value)),
+        Some(Value::Integer(value)) => Ok(Token::INTEGER(value)),
+        _ => Err(InterpreterError::InvalidSyntax(cur)),
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{Token, Tokenizer};
+    use super::{BigInt, Token, Tokenizer};
 
     #[test]
     fn test_try_collect() {
@@ -197,4 +350,355 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_try_collect_postfix() {
+        let tokens = Tokenizer::new("(e * my_var)! % # Some comment")
+            .try_collect()
+            .unwrap();
+
+        assert_eq!(
+            tokens,
+            vec![
+                Token::LPAREN,
+                Token::ID("e".to_string()),
+                Token::MUL,
+                Token::ID("my_var".to_string()),
+                Token::RPAREN,
+                Token::FACTORIAL,
+                Token::PERCENT,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_try_collect_compound_assign() {
+        let mut tokenizer =
+            Tokenizer::new("x += 1; x -= 2; x *= 3; x /= 4; x ^= 2 ^ 3; a, b = b, a");
+
+        let token_result = tokenizer.try_collect();
+
+        assert!(token_result.is_ok());
+
+        let tokens = token_result.unwrap();
+
+        assert_eq!(
+            tokens,
+            vec![
+                Token::ID("x".to_string()),
+                Token::ADDASSIGN,
+                Token::NUMBER(1.),
+                Token::SEMI,
+                Token::ID("x".to_string()),
+                Token::SUBASSIGN,
+                Token::NUMBER(2.),
+                Token::SEMI,
+                Token::ID("x".to_string()),
+                Token::MULASSIGN,
+                Token::NUMBER(3.),
+                Token::SEMI,
+                Token::ID("x".to_string()),
+                Token::DIVASSIGN,
+                Token::NUMBER(4.),
+                Token::SEMI,
+                Token::ID("x".to_string()),
+                Token::POWASSIGN,
+                Token::NUMBER(2.),
+                Token::POW,
+                Token::NUMBER(3.),
+                Token::SEMI,
+                Token::ID("a".to_string()),
+                Token::COMMA,
+                Token::ID("b".to_string()),
+                Token::ASSIGN,
+                Token::ID("b".to_string()),
+                Token::COMMA,
+                Token::ID("a".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_try_collect_bitwise() {
+        let mut tokenizer = Tokenizer::new("0xff & ~0b101 | 1 << 4 xor 256 >> 2");
+
+        let token_result = tokenizer.try_collect();
+
+        assert!(token_result.is_ok());
+
+        let tokens = token_result.unwrap();
+
+        assert_eq!(
+            tokens,
+            vec![
+                Token::NUMBER(255.),
+                Token::BITAND,
+                Token::BITNOT,
+                Token::NUMBER(5.),
+                Token::BITOR,
+                Token::NUMBER(1.),
+                Token::SHL,
+                Token::NUMBER(4.),
+                Token::XOR,
+                Token::NUMBER(256.),
+                Token::SHR,
+                Token::NUMBER(2.),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_try_collect_invalid_radix_number() {
+        assert!(Tokenizer::new("0x").try_collect().is_err());
+        assert!(Tokenizer::new("0b102").try_collect().is_err());
+    }
+
+    #[test]
+    fn test_try_collect_long_radix_number() {
+        let tokens = Tokenizer::new("0xFFFFFFFFFFFFFFFFFF")
+            .try_collect()
+            .unwrap();
+
+        assert_eq!(
+            tokens,
+            vec![Token::INTEGER(
+                BigInt::parse("4722366482869645213695").unwrap()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_try_collect_integer_division() {
+        let tokens = Tokenizer::new("7 // 2 % (3) + 50%").try_collect().unwrap();
+
+        assert_eq!(
+            tokens,
+            vec![
+                Token::NUMBER(7.),
+                Token::FLOORDIV,
+                Token::NUMBER(2.),
+                Token::PERCENT,
+                Token::LPAREN,
+                Token::NUMBER(3.),
+                Token::RPAREN,
+                Token::PLUS,
+                Token::NUMBER(50.),
+                Token::PERCENT,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_try_collect_leading_dot() {
+        let tokens = Tokenizer::new("7 % .5 + x[1..2]").try_collect().unwrap();
+
+        assert_eq!(
+            tokens,
+            vec![
+                Token::NUMBER(7.),
+                Token::PERCENT,
+                Token::NUMBER(0.5),
+                Token::PLUS,
+                Token::ID("x".to_string()),
+                Token::LBRACKET,
+                Token::NUMBER(1.),
+                Token::RANGE,
+                Token::NUMBER(2.),
+                Token::RBRACKET,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_try_collect_long_decimal() {
+        let tokens = Tokenizer::new("1.00000000000000000001 0.10 2.5i")
+            .try_collect()
+            .unwrap();
+
+        assert_eq!(
+            tokens,
+            vec![
+                Token::DECIMAL("1.00000000000000000001".to_string()),
+                Token::NUMBER(0.1),
+                Token::IMAGINARY(2.5),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_try_collect_long_integer() {
+        let tokens = Tokenizer::new("9007199254740993 9007199254740992")
+            .try_collect()
+            .unwrap();
+
+        assert_eq!(
+            tokens,
+            vec![
+                Token::INTEGER(BigInt::parse("9007199254740993").unwrap()),
+                Token::NUMBER(9007199254740992.),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_try_collect_imaginary() {
+        let tokens = Tokenizer::new("3 + 4i - 2.5j * 2in").try_collect().unwrap();
+
+        assert_eq!(
+            tokens,
+            vec![
+                Token::NUMBER(3.),
+                Token::PLUS,
+                Token::IMAGINARY(4.),
+                Token::MINUS,
+                Token::IMAGINARY(2.5),
+                Token::MUL,
+                Token::NUMBER(2.),
+                Token::ID("in".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_try_collect_list() {
+        let tokens = Tokenizer::new("[1; 2] @ [1, 2][0]").try_collect().unwrap();
+
+        assert_eq!(
+            tokens,
+            vec![
+                Token::LBRACKET,
+                Token::NUMBER(1.),
+                Token::SEMI,
+                Token::NUMBER(2.),
+                Token::RBRACKET,
+                Token::MATMUL,
+                Token::LBRACKET,
+                Token::NUMBER(1.),
+                Token::COMMA,
+                Token::NUMBER(2.),
+                Token::RBRACKET,
+                Token::LBRACKET,
+                Token::NUMBER(0.),
+                Token::RBRACKET,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_try_collect_interval() {
+        let tokens = Tokenizer::new("[1..2.5]").try_collect().unwrap();
+
+        assert_eq!(
+            tokens,
+            vec![
+                Token::LBRACKET,
+                Token::NUMBER(1.),
+                Token::RANGE,
+                Token::NUMBER(2.5),
+                Token::RBRACKET,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_try_collect_uncertainty() {
+        let tokens = Tokenizer::new("12.3 ± 0.2").try_collect().unwrap();
+
+        assert_eq!(
+            tokens,
+            vec![Token::NUMBER(12.3), Token::PLUSMINUS, Token::NUMBER(0.2)]
+        );
+    }
+
+    #[test]
+    fn test_try_collect_lambda() {
+        let tokens = Tokenizer::new("(a, b) -> a * -b").try_collect().unwrap();
+
+        assert_eq!(
+            tokens,
+            vec![
+                Token::LPAREN,
+                Token::ID("a".to_string()),
+                Token::COMMA,
+                Token::ID("b".to_string()),
+                Token::RPAREN,
+                Token::ARROW,
+                Token::ID("a".to_string()),
+                Token::MUL,
+                Token::MINUS,
+                Token::ID("b".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_try_collect_const() {
+        let tokens = Tokenizer::new("const g = 9.81").try_collect().unwrap();
+
+        assert_eq!(
+            tokens,
+            vec![
+                Token::CONST,
+                Token::ID("g".to_string()),
+                Token::ASSIGN,
+                Token::NUMBER(9.81),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_try_collect_comparison() {
+        let tokens = Tokenizer::new("a < b <= c > d >= e == f != g << 1 > 2!")
+            .try_collect()
+            .unwrap();
+
+        assert_eq!(
+            tokens,
+            vec![
+                Token::ID("a".to_string()),
+                Token::LT,
+                Token::ID("b".to_string()),
+                Token::LE,
+                Token::ID("c".to_string()),
+                Token::GT,
+                Token::ID("d".to_string()),
+                Token::GE,
+                Token::ID("e".to_string()),
+                Token::EQ,
+                Token::ID("f".to_string()),
+                Token::NE,
+                Token::ID("g".to_string()),
+                Token::SHL,
+                Token::NUMBER(1.),
+                Token::GT,
+                Token::NUMBER(2.),
+                Token::FACTORIAL,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_try_collect_conditional() {
+        let tokens = Tokenizer::new("memo f(n) = if n then 1 else 2")
+            .try_collect()
+            .unwrap();
+
+        assert_eq!(
+            tokens,
+            vec![
+                Token::MEMO,
+                Token::ID("f".to_string()),
+                Token::LPAREN,
+                Token::ID("n".to_string()),
+                Token::RPAREN,
+                Token::ASSIGN,
+                Token::IF,
+                Token::ID("n".to_string()),
+                Token::THEN,
+                Token::NUMBER(1.),
+                Token::ELSE,
+                Token::NUMBER(2.),
+            ]
+        );
+    }
 }
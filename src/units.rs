@@ -0,0 +1,115 @@
+use crate::value::{Dimension, Unit};
+
+const LENGTH: [i32; 7] = [1, 0, 0, 0, 0, 0, 0];
+const MASS: [i32; 7] = [0, 1, 0, 0, 0, 0, 0];
+const TIME: [i32; 7] = [0, 0, 1, 0, 0, 0, 0];
+const CURRENT: [i32; 7] = [0, 0, 0, 1, 0, 0, 0];
+const TEMPERATURE: [i32; 7] = [0, 0, 0, 0, 1, 0, 0];
+const AMOUNT: [i32; 7] = [0, 0, 0, 0, 0, 1, 0];
+const LUMINOSITY: [i32; 7] = [0, 0, 0, 0, 0, 0, 1];
+const AREA: [i32; 7] = [2, 0, 0, 0, 0, 0, 0];
+const VOLUME: [i32; 7] = [3, 0, 0, 0, 0, 0, 0];
+const SPEED: [i32; 7] = [1, 0, -1, 0, 0, 0, 0];
+const FREQUENCY: [i32; 7] = [0, 0, -1, 0, 0, 0, 0];
+const FORCE: [i32; 7] = [1, 1, -2, 0, 0, 0, 0];
+const PRESSURE: [i32; 7] = [-1, 1, -2, 0, 0, 0, 0];
+const ENERGY: [i32; 7] = [2, 1, -2, 0, 0, 0, 0];
+const POWER: [i32; 7] = [2, 1, -3, 0, 0, 0, 0];
+const CHARGE: [i32; 7] = [0, 0, 1, 1, 0, 0, 0];
+const VOLTAGE: [i32; 7] = [2, 1, -3, -1, 0, 0, 0];
+const RESISTANCE: [i32; 7] = [2, 1, -3, -2, 0, 0, 0];
+const CAPACITANCE: [i32; 7] = [-2, -1, 4, 2, 0, 0, 0];
+
+pub struct UnitDef {
+    pub name: &'static str,
+    pub factor: f64,
+    pub dimension: [i32; 7],
+}
+
+impl UnitDef {
+    const fn new(name: &'static str, factor: f64, dimension: [i32; 7]) -> Self {
+        Self {
+            name,
+            factor,
+            dimension,
+        }
+    }
+
+    pub fn unit(&self) -> Unit {
+        Unit::new(self.name, self.factor, Dimension(self.dimension))
+    }
+}
+
+pub static UNITS: [UnitDef; 67] = [
+    UnitDef::new("m", 1., LENGTH),
+    UnitDef::new("km", 1e3, LENGTH),
+    UnitDef::new("cm", 1e-2, LENGTH),
+    UnitDef::new("mm", 1e-3, LENGTH),
+    UnitDef::new("um", 1e-6, LENGTH),
+    UnitDef::new("nm", 1e-9, LENGTH),
+    UnitDef::new("in", 0.0254, LENGTH),
+    UnitDef::new("ft", 0.3048, LENGTH),
+    UnitDef::new("yd", 0.9144, LENGTH),
+    UnitDef::new("mi", 1609.344, LENGTH),
+    UnitDef::new("kg", 1., MASS),
+    UnitDef::new("g", 1e-3, MASS),
+    UnitDef::new("mg", 1e-6, MASS),
+    UnitDef::new("t", 1e3, MASS),
+    UnitDef::new("lb", 0.45359237, MASS),
+    UnitDef::new("oz", 0.028349523125, MASS),
+    UnitDef::new("s", 1., TIME),
+    UnitDef::new("ms", 1e-3, TIME),
+    UnitDef::new("us", 1e-6, TIME),
+    UnitDef::new("ns", 1e-9, TIME),
+    UnitDef::new("min", 60., TIME),
+    UnitDef::new("h", 3600., TIME),
+    UnitDef::new("day", 86400., TIME),
+    UnitDef::new("week", 604800., TIME),
+    UnitDef::new("yr", 31557600., TIME),
+    UnitDef::new("A", 1., CURRENT),
+    UnitDef::new("mA", 1e-3, CURRENT),
+    UnitDef::new("K", 1., TEMPERATURE),
+    UnitDef::new("mol", 1., AMOUNT),
+    UnitDef::new("cd", 1., LUMINOSITY),
+    UnitDef::new("ha", 1e4, AREA),
+    UnitDef::new("acre", 4046.8564224, AREA),
+    UnitDef::new("L", 1e-3, VOLUME),
+    UnitDef::new("mL", 1e-6, VOLUME),
+    UnitDef::new("gal", 3.785411784e-3, VOLUME),
+    UnitDef::new("mph", 0.44704, SPEED),
+    UnitDef::new("kn", 1852. / 3600., SPEED),
+    UnitDef::new("Hz", 1., FREQUENCY),
+    UnitDef::new("kHz", 1e3, FREQUENCY),
+    UnitDef::new("MHz", 1e6, FREQUENCY),
+    UnitDef::new("GHz", 1e9, FREQUENCY),
+    UnitDef::new("N", 1., FORCE),
+    UnitDef::new("kN", 1e3, FORCE),
+    UnitDef::new("lbf", 4.4482216152605, FORCE),
+    UnitDef::new("Pa", 1., PRESSURE),
+    UnitDef::new("kPa", 1e3, PRESSURE),
+    UnitDef::new("bar", 1e5, PRESSURE),
+    UnitDef::new("atm", 101325., PRESSURE),
+    UnitDef::new("psi", 6894.757293168361, PRESSURE),
+    UnitDef::new("J", 1., ENERGY),
+    UnitDef::new("kJ", 1e3, ENERGY),
+    UnitDef::new("cal", 4.184, ENERGY),
+    UnitDef::new("kcal", 4184., ENERGY),
+    UnitDef::new("kWh", 3.6e6, ENERGY),
+    UnitDef::new("eV", 1.602176634e-19, ENERGY),
+    UnitDef::new("W", 1., POWER),
+    UnitDef::new("kW", 1e3, POWER),
+    UnitDef::new("hp", 745.6998715822702, POWER),
+    UnitDef::new("C", 1., CHARGE),
+    UnitDef::new("V", 1., VOLTAGE),
+    UnitDef::new("mV", 1e-3, VOLTAGE),
+    UnitDef::new("kV", 1e3, VOLTAGE),
+    UnitDef::new("ohm", 1., RESISTANCE),
+    UnitDef::new("kohm", 1e3, RESISTANCE),
+    UnitDef::new("F", 1., CAPACITANCE),
+    UnitDef::new("uF", 1e-6, CAPACITANCE),
+    UnitDef::new("nF", 1e-9, CAPACITANCE),
+];
+
+pub fn lookup(name: &str) -> Option<&'static UnitDef> {
+    UNITS.iter().find(|unit| unit.name == name)
+}
@@ -0,0 +1,624 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+const BASE: u64 = 1_000_000_000;
+const BASE_DIGITS: usize = 9;
+
+/// Arbitrary-precision signed integer stored as little-endian limbs in base
+/// 10^9, which keeps decimal parsing and printing straightforward.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    negative: bool,
+    limbs: Vec<u32>,
+}
+
+impl BigInt {
+    pub fn zero() -> Self {
+        Self {
+            negative: false,
+            limbs: vec![],
+        }
+    }
+
+    pub fn from_i64(value: i64) -> Self {
+        let mut magnitude = value.unsigned_abs();
+        let mut limbs = vec![];
+
+        while magnitude > 0 {
+            limbs.push((magnitude % BASE) as u32);
+            magnitude /= BASE;
+        }
+
+        Self::from_parts(value < 0, limbs)
+    }
+
+    /// Converts an integral, finite `f64` exactly.
+    pub fn from_f64(value: f64) -> Option<Self> {
+        if !value.is_finite() || value.fract() != 0. {
+            return None;
+        }
+
+        // `f64` formats integral values with every digit and no exponent.
+        Self::parse(&format!("{}", value.abs())).map(|magnitude| {
+            if value < 0. {
+                magnitude.neg()
+            } else {
+                magnitude
+            }
+        })
+    }
+
+    /// Parses an optionally signed string of decimal digits.
+    pub fn parse(text: &str) -> Option<Self> {
+        let (negative, digits) = match text.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, text),
+        };
+
+        if digits.is_empty() || !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+            return None;
+        }
+
+        let mut limbs = vec![];
+        let mut end = digits.len();
+
+        while end > 0 {
+            let start = end.saturating_sub(BASE_DIGITS);
+
+            limbs.push(digits[start..end].parse::<u32>().ok()?);
+            end = start;
+        }
+
+        Some(Self::from_parts(negative, limbs))
+    }
+
+    /// Parses a string of digits in the given radix, without sign or prefix.
+    pub fn parse_radix(digits: &str, radix: u32) -> Option<Self> {
+        if digits.is_empty() {
+            return None;
+        }
+
+        digits.chars().try_fold(Self::zero(), |value, ch| {
+            let digit = ch.to_digit(radix)?;
+
+            Some(value.mul_small(radix).add(&Self::from_i64(digit as i64)))
+        })
+    }
+
+    /// Ten raised to `exponent`.
+    pub fn pow10(exponent: usize) -> Self {
+        let mut limbs = vec![0; exponent / BASE_DIGITS];
+
+        limbs.push(10u32.pow((exponent % BASE_DIGITS) as u32));
+
+        Self::from_parts(false, limbs)
+    }
+
+    fn from_parts(negative: bool, mut limbs: Vec<u32>) -> Self {
+        while let Some(0) = limbs.last() {
+            limbs.pop();
+        }
+
+        Self {
+            negative: negative && !limbs.is_empty(),
+            limbs,
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    pub fn is_even(&self) -> bool {
+        self.limbs.first().is_none_or(|limb| limb % 2 == 0)
+    }
+
+    pub fn abs(&self) -> Self {
+        Self::from_parts(false, self.limbs.clone())
+    }
+
+    pub fn neg(&self) -> Self {
+        Self::from_parts(!self.negative, self.limbs.clone())
+    }
+
+    /// Number of decimal digits of the magnitude, zero has no digits.
+    pub fn digit_count(&self) -> usize {
+        match self.limbs.last() {
+            Some(top) => (self.limbs.len() - 1) * BASE_DIGITS + top.to_string().len(),
+            None => 0,
+        }
+    }
+
+    pub fn to_i64(&self) -> Option<i64> {
+        let mut magnitude: i128 = 0;
+
+        for limb in self.limbs.iter().rev() {
+            magnitude = magnitude.checked_mul(BASE as i128)? + *limb as i128;
+
+            if magnitude > i64::MAX as i128 + 1 {
+                return None;
+            }
+        }
+
+        let value = if self.negative { -magnitude } else { magnitude };
+
+        i64::try_from(value).ok()
+    }
+
+    /// Digits of the magnitude in a radix of 2, 4 or 16, whose digits fill
+    /// the 32-bit words evenly.
+    pub fn to_radix(&self, radix: u32) -> String {
+        let bits = radix.trailing_zeros();
+        let mut digits = vec![];
+
+        for word in self.words() {
+            for i in 0..32 / bits {
+                digits.push(char::from_digit((word >> (i * bits)) & (radix - 1), radix).unwrap());
+            }
+        }
+
+        while digits.len() > 1 && digits.last() == Some(&'0') {
+            digits.pop();
+        }
+
+        if digits.is_empty() {
+            return "0".to_string();
+        }
+
+        digits.iter().rev().collect()
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.to_string().parse::<f64>().unwrap_or(f64::NAN)
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        if self.negative == other.negative {
+            return Self::from_parts(self.negative, add_magnitude(&self.limbs, &other.limbs));
+        }
+
+        match cmp_magnitude(&self.limbs, &other.limbs) {
+            Ordering::Less => {
+                Self::from_parts(other.negative, sub_magnitude(&other.limbs, &self.limbs))
+            }
+            _ => Self::from_parts(self.negative, sub_magnitude(&self.limbs, &other.limbs)),
+        }
+    }
+
+    pub fn sub(&self, other: &Self) -> Self {
+        self.add(&other.neg())
+    }
+
+    pub fn mul(&self, other: &Self) -> Self {
+        if self.is_zero() || other.is_zero() {
+            return Self::zero();
+        }
+
+        let mut result = vec![0u64; self.limbs.len() + other.limbs.len() + 1];
+
+        for (i, a) in self.limbs.iter().enumerate() {
+            let mut carry = 0u64;
+
+            for (j, b) in other.limbs.iter().enumerate() {
+                let cur = result[i + j] + *a as u64 * *b as u64 + carry;
+
+                result[i + j] = cur % BASE;
+                carry = cur / BASE;
+            }
+
+            let mut k = i + other.limbs.len();
+
+            while carry > 0 {
+                let cur = result[k] + carry;
+
+                result[k] = cur % BASE;
+                carry = cur / BASE;
+                k += 1;
+            }
+        }
+
+        Self::from_parts(
+            self.negative != other.negative,
+            result.into_iter().map(|limb| limb as u32).collect(),
+        )
+    }
+
+    /// Truncating division, the remainder has the sign of the dividend.
+    /// Returns `None` when dividing by zero.
+    pub fn div_rem(&self, other: &Self) -> Option<(Self, Self)> {
+        if other.is_zero() {
+            return None;
+        }
+
+        let divisor = other.abs();
+        let mut quotient = vec![0u32; self.limbs.len()];
+        let mut remainder = Self::zero();
+
+        for i in (0..self.limbs.len()).rev() {
+            let mut limbs = vec![self.limbs[i]];
+
+            limbs.extend_from_slice(&remainder.limbs);
+            remainder = Self::from_parts(false, limbs);
+
+            let (mut low, mut high) = (0u64, BASE - 1);
+
+            while low < high {
+                let mid = (low + high).div_ceil(2);
+
+                if divisor.mul_small(mid as u32) <= remainder {
+                    low = mid;
+                } else {
+                    high = mid - 1;
+                }
+            }
+
+            quotient[i] = low as u32;
+            remainder = remainder.sub(&divisor.mul_small(low as u32));
+        }
+
+        Some((
+            Self::from_parts(self.negative != other.negative, quotient),
+            Self::from_parts(self.negative, remainder.limbs),
+        ))
+    }
+
+    /// Floor division and the matching non-negative (for positive divisors)
+    /// modulus.
+    pub fn div_floor(&self, other: &Self) -> Option<(Self, Self)> {
+        let (quotient, remainder) = self.div_rem(other)?;
+
+        if !remainder.is_zero() && remainder.negative != other.negative {
+            Some((quotient.sub(&Self::from_i64(1)), remainder.add(other)))
+        } else {
+            Some((quotient, remainder))
+        }
+    }
+
+    pub fn pow(&self, mut exponent: u32) -> Self {
+        let mut base = self.clone();
+        let mut result = Self::from_i64(1);
+
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = result.mul(&base);
+            }
+
+            exponent >>= 1;
+
+            if exponent > 0 {
+                base = base.mul(&base);
+            }
+        }
+
+        result
+    }
+
+    pub fn gcd(&self, other: &Self) -> Self {
+        let mut a = self.abs();
+        let mut b = other.abs();
+
+        while !b.is_zero() {
+            let (_, remainder) = a.div_rem(&b).unwrap();
+
+            a = b;
+            b = remainder;
+        }
+
+        a
+    }
+
+    /// Bitwise operation on the two's complement representations, which
+    /// extend the sign indefinitely so that `-1 & x == x`.
+    pub fn bitwise(&self, other: &Self, op: impl Fn(u32, u32) -> u32) -> Self {
+        let len = self.words().len().max(other.words().len()) + 1;
+        let words: Vec<u32> = self
+            .twos_complement(len)
+            .into_iter()
+            .zip(other.twos_complement(len))
+            .map(|(left, right)| op(left, right))
+            .collect();
+
+        if words[len - 1] >> 31 == 1 {
+            Self::from_words(true, negate_words(words))
+        } else {
+            Self::from_words(false, words)
+        }
+    }
+
+    /// Bitwise complement, `-x - 1`.
+    pub fn not(&self) -> Self {
+        self.neg().sub(&Self::from_i64(1))
+    }
+
+    pub fn shl(&self, shift: u32) -> Self {
+        self.mul(&Self::from_i64(2).pow(shift))
+    }
+
+    /// Arithmetic shift to the right, which rounds towards negative infinity.
+    pub fn shr(&self, shift: u32) -> Self {
+        // Every bit is shifted out, 2^shift would only waste time.
+        if shift as usize > self.digit_count() * 4 {
+            return if self.negative {
+                Self::from_i64(-1)
+            } else {
+                Self::zero()
+            };
+        }
+
+        self.div_floor(&Self::from_i64(2).pow(shift)).unwrap().0
+    }
+
+    /// Magnitude as little-endian 32-bit words.
+    fn words(&self) -> Vec<u32> {
+        let mut limbs = self.limbs.clone();
+        let mut words = vec![];
+
+        while !limbs.is_empty() {
+            let mut remainder = 0u64;
+
+            for limb in limbs.iter_mut().rev() {
+                let cur = remainder * BASE + *limb as u64;
+
+                *limb = (cur >> 32) as u32;
+                remainder = cur & 0xFFFF_FFFF;
+            }
+
+            words.push(remainder as u32);
+
+            while let Some(0) = limbs.last() {
+                limbs.pop();
+            }
+        }
+
+        words
+    }
+
+    fn from_words(negative: bool, words: Vec<u32>) -> Self {
+        let magnitude = words.iter().rev().fold(Self::zero(), |value, word| {
+            value
+                .mul_small(1 << 16)
+                .mul_small(1 << 16)
+                .add(&Self::from_i64(*word as i64))
+        });
+
+        Self::from_parts(negative, magnitude.limbs)
+    }
+
+    fn twos_complement(&self, len: usize) -> Vec<u32> {
+        let mut words = self.words();
+
+        words.resize(len, 0);
+
+        if self.negative {
+            negate_words(words)
+        } else {
+            words
+        }
+    }
+
+    fn mul_small(&self, factor: u32) -> Self {
+        let mut limbs = Vec::with_capacity(self.limbs.len() + 1);
+        let mut carry = 0u64;
+
+        for limb in self.limbs.iter() {
+            let cur = *limb as u64 * factor as u64 + carry;
+
+            limbs.push((cur % BASE) as u32);
+            carry = cur / BASE;
+        }
+
+        if carry > 0 {
+            limbs.push(carry as u32);
+        }
+
+        Self::from_parts(self.negative, limbs)
+    }
+}
+
+/// Two's complement negation of little-endian words, `!x + 1`.
+fn negate_words(mut words: Vec<u32>) -> Vec<u32> {
+    let mut carry = true;
+
+    for word in words.iter_mut() {
+        (*word, carry) = (!*word).overflowing_add(carry as u32);
+    }
+
+    words
+}
+
+fn cmp_magnitude(a: &[u32], b: &[u32]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = Vec::with_capacity(a.len().max(b.len()) + 1);
+    let mut carry = 0u64;
+
+    for i in 0..a.len().max(b.len()) {
+        let cur = *a.get(i).unwrap_or(&0) as u64 + *b.get(i).unwrap_or(&0) as u64 + carry;
+
+        result.push((cur % BASE) as u32);
+        carry = cur / BASE;
+    }
+
+    if carry > 0 {
+        result.push(carry as u32);
+    }
+
+    result
+}
+
+/// Subtracts magnitudes, `a` must not be smaller than `b`.
+fn sub_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = Vec::with_capacity(a.len());
+    let mut borrow = 0i64;
+
+    for (i, limb) in a.iter().enumerate() {
+        let mut cur = *limb as i64 - *b.get(i).unwrap_or(&0) as i64 - borrow;
+
+        borrow = if cur < 0 {
+            cur += BASE as i64;
+            1
+        } else {
+            0
+        };
+
+        result.push(cur as u32);
+    }
+
+    result
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => cmp_magnitude(&self.limbs, &other.limbs),
+            (true, true) => cmp_magnitude(&other.limbs, &self.limbs),
+        }
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut limbs = self.limbs.iter().rev();
+
+        match limbs.next() {
+            Some(top) => {
+                if self.negative {
+                    write!(f, "-")?;
+                }
+
+                write!(f, "{}", top)?;
+
+                for limb in limbs {
+                    write!(f, "{:09}", limb)?;
+                }
+
+                Ok(())
+            }
+            None => write!(f, "0"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BigInt;
+
+    fn big(text: &str) -> BigInt {
+        BigInt::parse(text).unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        assert_eq!(big("0").to_string(), "0");
+        assert_eq!(big("-0").to_string(), "0");
+        assert_eq!(big("000123").to_string(), "123");
+        assert_eq!(
+            big("-1234567890123456789012345").to_string(),
+            "-1234567890123456789012345"
+        );
+        assert!(BigInt::parse("12a").is_none());
+        assert!(BigInt::parse("").is_none());
+    }
+
+    #[test]
+    fn test_add_sub() {
+        assert_eq!(big("999999999").add(&big("1")), big("1000000000"));
+        assert_eq!(big("1000000000").sub(&big("1")), big("999999999"));
+        assert_eq!(big("5").sub(&big("12")), big("-7"));
+        assert_eq!(big("-5").add(&big("5")), BigInt::zero());
+    }
+
+    #[test]
+    fn test_mul() {
+        assert_eq!(
+            big("123456789123456789").mul(&big("-987654321987654321")),
+            big("-121932631356500531347203169112635269")
+        );
+        assert_eq!(big("2").pow(100), big("1267650600228229401496703205376"));
+    }
+
+    #[test]
+    fn test_div_rem() {
+        let (quotient, remainder) = big("1267650600228229401496703205376")
+            .div_rem(&big("12345678901"))
+            .unwrap();
+
+        assert_eq!(quotient, big("102679699544554791713"));
+        assert_eq!(remainder, big("7069457963"));
+
+        let (quotient, remainder) = big("-7").div_rem(&big("2")).unwrap();
+
+        assert_eq!(quotient, big("-3"));
+        assert_eq!(remainder, big("-1"));
+
+        let (quotient, remainder) = big("-7").div_floor(&big("2")).unwrap();
+
+        assert_eq!(quotient, big("-4"));
+        assert_eq!(remainder, big("1"));
+
+        assert!(big("1").div_rem(&BigInt::zero()).is_none());
+    }
+
+    #[test]
+    fn test_helpers() {
+        assert_eq!(BigInt::pow10(12), big("1000000000000"));
+        assert_eq!(big("-1000000000000").digit_count(), 13);
+        assert_eq!(big("12").gcd(&big("-18")), big("6"));
+        assert_eq!(big("-9223372036854775808").to_i64(), Some(i64::MIN));
+        assert_eq!(big("9223372036854775808").to_i64(), None);
+        assert!(big("-3") < big("2"));
+        assert_eq!(
+            BigInt::from_f64(-1e20).unwrap(),
+            big("-100000000000000000000")
+        );
+        assert!(BigInt::from_f64(1.5).is_none());
+    }
+
+    #[test]
+    fn test_radix() {
+        let value = big("4722366482869645213695");
+
+        assert_eq!(
+            BigInt::parse_radix("FFFFFFFFFFFFFFFFFF", 16).unwrap(),
+            value
+        );
+        assert_eq!(value.to_radix(16), "ffffffffffffffffff");
+        assert_eq!(big("-5").to_radix(2), "101");
+        assert_eq!(BigInt::zero().to_radix(16), "0");
+        assert!(BigInt::parse_radix("12", 2).is_none());
+    }
+
+    #[test]
+    fn test_bitwise() {
+        let odd = big("9007199254740993");
+
+        assert_eq!(odd.bitwise(&odd, |a, b| a & b), odd);
+        assert_eq!(
+            big("1").shl(100).bitwise(&big("1"), |a, b| a | b),
+            big("1267650600228229401496703205377")
+        );
+        assert_eq!(big("-1").bitwise(&big("12"), |a, b| a & b), big("12"));
+        assert_eq!(big("-6").bitwise(&big("3"), |a, b| a ^ b), big("-7"));
+        assert_eq!(big("-6").bitwise(&big("-3"), |a, b| a | b), big("-1"));
+        assert_eq!(big("5").not(), big("-6"));
+        assert_eq!(big("-7").shr(1), big("-4"));
+        assert_eq!(big("-7").shr(1000), big("-1"));
+        assert_eq!(big("7").shr(1000), BigInt::zero());
+    }
+}
@@ -0,0 +1,264 @@
+use std::f64::consts;
+use std::fmt;
+
+/// Complex number in rectangular form.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Complex {
+    pub re: f64,
+    pub im: f64,
+}
+
+impl Complex {
+    pub fn new(re: f64, im: f64) -> Self {
+        Self { re, im }
+    }
+
+    pub fn from_polar(r: f64, theta: f64) -> Self {
+        Self::new(r * theta.cos(), r * theta.sin())
+    }
+
+    /// `e^(i * pi * t)`, exact when `t` is a multiple of `1/2`.
+    fn cis_pi(t: f64) -> Self {
+        let quarter = t * 2.;
+
+        if quarter.fract() == 0. {
+            match quarter.rem_euclid(4.) as u8 {
+                0 => Self::new(1., 0.),
+                1 => Self::new(0., 1.),
+                2 => Self::new(-1., 0.),
+                _ => Self::new(0., -1.),
+            }
+        } else {
+            Self::from_polar(1., t * consts::PI)
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.re == 0. && self.im == 0.
+    }
+
+    pub fn norm(&self) -> f64 {
+        self.re.hypot(self.im)
+    }
+
+    pub fn arg(&self) -> f64 {
+        self.im.atan2(self.re)
+    }
+
+    pub fn conj(&self) -> Self {
+        Self::new(self.re, -self.im)
+    }
+
+    pub fn neg(&self) -> Self {
+        Self::new(-self.re, -self.im)
+    }
+
+    pub fn scale(&self, factor: f64) -> Self {
+        Self::new(self.re * factor, self.im * factor)
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        Self::new(self.re + other.re, self.im + other.im)
+    }
+
+    pub fn sub(&self, other: &Self) -> Self {
+        Self::new(self.re - other.re, self.im - other.im)
+    }
+
+    pub fn mul(&self, other: &Self) -> Self {
+        Self::new(
+            self.re * other.re - self.im * other.im,
+            self.re * other.im + self.im * other.re,
+        )
+    }
+
+    /// Returns `None` when dividing by zero.
+    pub fn div(&self, other: &Self) -> Option<Self> {
+        if other.is_zero() {
+            return None;
+        }
+
+        let denominator = other.re * other.re + other.im * other.im;
+
+        Some(Self::new(
+            (self.re * other.re + self.im * other.im) / denominator,
+            (self.im * other.re - self.re * other.im) / denominator,
+        ))
+    }
+
+    pub fn exp(&self) -> Self {
+        Self::from_polar(self.re.exp(), self.im)
+    }
+
+    pub fn sin(&self) -> Self {
+        Self::new(
+            self.re.sin() * self.im.cosh(),
+            self.re.cos() * self.im.sinh(),
+        )
+    }
+
+    pub fn cos(&self) -> Self {
+        Self::new(
+            self.re.cos() * self.im.cosh(),
+            -self.re.sin() * self.im.sinh(),
+        )
+    }
+
+    /// `(sin 2a + i sinh 2b) / (cos 2a + cosh 2b)`, whose denominator only
+    /// vanishes at the real poles.
+    pub fn tan(&self) -> Self {
+        let denominator = (2. * self.re).cos() + (2. * self.im).cosh();
+
+        Self::new(
+            (2. * self.re).sin() / denominator,
+            (2. * self.im).sinh() / denominator,
+        )
+    }
+
+    /// Principal natural logarithm.
+    pub fn ln(&self) -> Self {
+        Self::new(self.norm().ln(), self.arg())
+    }
+
+    /// Principal square root.
+    pub fn sqrt(&self) -> Self {
+        let r = self.norm();
+        let re = ((r + self.re) / 2.).sqrt();
+        let im = ((r - self.re) / 2.).sqrt();
+
+        Self::new(re, if self.im < 0. { -im } else { im })
+    }
+
+    /// Principal power. Integer exponents use repeated multiplication and
+    /// real powers of negative reals use an exact angle where possible, so
+    /// `(-1)^0.5` is exactly `i`. Like `0^-1` for reals, negative powers of
+    /// zero are infinite.
+    pub fn pow(&self, exponent: &Self) -> Self {
+        if exponent.is_zero() {
+            return Self::new(1., 0.);
+        }
+
+        if self.is_zero() {
+            return if exponent.re < 0. {
+                Self::new(f64::INFINITY, 0.)
+            } else {
+                Self::new(0., 0.)
+            };
+        }
+
+        if exponent.im == 0. && exponent.re.fract() == 0. && exponent.re.abs() <= 64. {
+            let mut result = Self::new(1., 0.);
+
+            for _ in 0..exponent.re.abs() as u32 {
+                result = result.mul(self);
+            }
+
+            // A power that underflows to zero has an infinite reciprocal.
+            return if exponent.re < 0. {
+                Self::new(1., 0.)
+                    .div(&result)
+                    .unwrap_or(Self::new(f64::INFINITY, 0.))
+            } else {
+                result
+            };
+        }
+
+        if exponent.im == 0. && self.im == 0. && self.re < 0. {
+            return Self::cis_pi(exponent.re).scale((-self.re).powf(exponent.re));
+        }
+
+        exponent.mul(&self.ln()).exp()
+    }
+}
+
+impl fmt::Display for Complex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.re == 0. {
+            write!(f, "{}i", self.im)
+        } else if self.im.is_sign_negative() {
+            write!(f, "{} - {}i", self.re, -self.im)
+        } else {
+            write!(f, "{} + {}i", self.re, self.im)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Complex;
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Complex::new(3., 4.);
+        let b = Complex::new(1., -2.);
+
+        assert_eq!(a.add(&b), Complex::new(4., 2.));
+        assert_eq!(a.sub(&b), Complex::new(2., 6.));
+        assert_eq!(a.mul(&b), Complex::new(11., -2.));
+        assert_eq!(a.div(&b).unwrap(), Complex::new(-1., 2.));
+        assert!(a.div(&Complex::new(0., 0.)).is_none());
+    }
+
+    #[test]
+    fn test_polar() {
+        let z = Complex::new(3., 4.);
+
+        assert_eq!(z.norm(), 5.);
+        assert_eq!(z.conj(), Complex::new(3., -4.));
+        assert_eq!(Complex::new(-1., 0.).arg(), std::f64::consts::PI);
+
+        let w = Complex::from_polar(z.norm(), z.arg());
+
+        assert!((w.re - 3.).abs() < 1e-12 && (w.im - 4.).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_trigonometric() {
+        let z = Complex::new(1., 2.);
+        let close = |a: Complex, re: f64, im: f64| {
+            assert!(
+                (a.re - re).abs() < 1e-12 && (a.im - im).abs() < 1e-12,
+                "{}",
+                a
+            );
+        };
+
+        close(z.sin(), 3.165778513216168, 1.959601041421606);
+        close(z.cos(), 2.0327230070196656, -3.0518977991518);
+        close(z.tan(), 0.0338128260798967, 1.0147936161466335);
+        assert_eq!(Complex::new(0.5, 0.).sin(), Complex::new(0.5f64.sin(), 0.));
+    }
+
+    #[test]
+    fn test_sqrt_and_pow() {
+        assert_eq!(Complex::new(-1., 0.).sqrt(), Complex::new(0., 1.));
+        assert_eq!(Complex::new(-4., 0.).sqrt(), Complex::new(0., 2.));
+        assert_eq!(
+            Complex::new(0., 1.).pow(&Complex::new(2., 0.)),
+            Complex::new(-1., 0.)
+        );
+        assert_eq!(
+            Complex::new(-1., 0.).pow(&Complex::new(0.5, 0.)),
+            Complex::new(0., 1.)
+        );
+        assert_eq!(
+            Complex::new(-8., 0.).pow(&Complex::new(-1., 0.)),
+            Complex::new(-0.125, 0.)
+        );
+
+        let infinity = Complex::new(f64::INFINITY, 0.);
+
+        assert_eq!(Complex::new(0., 0.).pow(&Complex::new(-1., 0.)), infinity);
+        assert_eq!(
+            Complex::new(0., 1e-200).pow(&Complex::new(-2., 0.)),
+            infinity
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Complex::new(3., 4.).to_string(), "3 + 4i");
+        assert_eq!(Complex::new(3., -4.).to_string(), "3 - 4i");
+        assert_eq!(Complex::new(0., 1.).to_string(), "1i");
+    }
+}
@@ -0,0 +1,462 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use super::BigInt;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum RoundingMode {
+    HALFEVEN,
+    HALFUP,
+    HALFDOWN,
+    UP,
+    DOWN,
+    CEILING,
+    FLOOR,
+}
+
+impl RoundingMode {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "half-even" => Some(RoundingMode::HALFEVEN),
+            "half-up" => Some(RoundingMode::HALFUP),
+            "half-down" => Some(RoundingMode::HALFDOWN),
+            "up" => Some(RoundingMode::UP),
+            "down" => Some(RoundingMode::DOWN),
+            "ceiling" => Some(RoundingMode::CEILING),
+            "floor" => Some(RoundingMode::FLOOR),
+            _ => None,
+        }
+    }
+}
+
+/// Precision in significant digits and the rounding mode applied whenever a
+/// result has more digits than the precision.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct DecimalContext {
+    pub precision: usize,
+    pub rounding: RoundingMode,
+}
+
+impl DecimalContext {
+    pub fn new(precision: usize, rounding: RoundingMode) -> Self {
+        Self {
+            precision: precision.max(1),
+            rounding,
+        }
+    }
+}
+
+impl Default for DecimalContext {
+    fn default() -> Self {
+        Self::new(28, RoundingMode::HALFEVEN)
+    }
+}
+
+/// Decimal floating point number with value `coefficient * 10^exponent`.
+#[derive(Debug, Clone)]
+pub struct Decimal {
+    coefficient: BigInt,
+    exponent: i64,
+    context: DecimalContext,
+}
+
+impl Decimal {
+    /// Parses a plain decimal literal such as `-12.034`.
+    pub fn parse(text: &str, context: DecimalContext) -> Option<Self> {
+        let (integer, fraction) = text.split_once('.').unwrap_or((text, ""));
+        let coefficient = BigInt::parse(&format!("{}{}", integer, fraction))?;
+
+        Some(Self::round(
+            coefficient,
+            -(fraction.len() as i64),
+            context,
+            false,
+        ))
+    }
+
+    /// Converts a finite `f64` using its shortest round-trip representation,
+    /// so `0.1` becomes exactly `0.1`.
+    pub fn from_f64(value: f64, context: DecimalContext) -> Option<Self> {
+        if value.is_finite() {
+            Self::parse(&value.to_string(), context)
+        } else {
+            None
+        }
+    }
+
+    pub fn from_bigint(value: BigInt, context: DecimalContext) -> Self {
+        Self::round(value, 0, context, false)
+    }
+
+    pub fn context(&self) -> DecimalContext {
+        self.context
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.to_string().parse::<f64>().unwrap_or(f64::NAN)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coefficient.is_zero()
+    }
+
+    /// Returns the value as an integer if it has no fractional part.
+    pub fn to_bigint(&self) -> Option<BigInt> {
+        if self.exponent >= 0 {
+            return Some(self.coefficient.mul(&BigInt::pow10(self.exponent as usize)));
+        }
+
+        let (quotient, remainder) = self
+            .coefficient
+            .div_rem(&BigInt::pow10(self.exponent.unsigned_abs() as usize))?;
+
+        if remainder.is_zero() {
+            Some(quotient)
+        } else {
+            None
+        }
+    }
+
+    pub fn neg(&self) -> Self {
+        Self {
+            coefficient: self.coefficient.neg(),
+            exponent: self.exponent,
+            context: self.context,
+        }
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        let exponent = self.exponent.min(other.exponent);
+        let left = self.aligned_coefficient(exponent);
+        let right = other.aligned_coefficient(exponent);
+
+        Self::round(left.add(&right), exponent, self.context, false)
+    }
+
+    pub fn sub(&self, other: &Self) -> Self {
+        self.add(&other.neg())
+    }
+
+    pub fn mul(&self, other: &Self) -> Self {
+        Self::round(
+            self.coefficient.mul(&other.coefficient),
+            self.exponent + other.exponent,
+            self.context,
+            false,
+        )
+    }
+
+    /// Returns `None` when dividing by zero.
+    pub fn div(&self, other: &Self) -> Option<Self> {
+        if other.is_zero() {
+            return None;
+        }
+
+        // Scale the dividend so the quotient has at least two digits more than
+        // the precision, the remainder only matters as a sticky digit.
+        let shift = (self.context.precision + other.coefficient.digit_count() + 2)
+            .saturating_sub(self.coefficient.digit_count());
+        let dividend = self.coefficient.mul(&BigInt::pow10(shift));
+        let (quotient, remainder) = dividend.div_rem(&other.coefficient)?;
+
+        Some(Self::round(
+            quotient,
+            self.exponent - other.exponent - shift as i64,
+            self.context,
+            !remainder.is_zero(),
+        ))
+    }
+
+    /// Raises to an integer power, returns `None` for `0` to a negative power.
+    pub fn powi(&self, exponent: i64) -> Option<Self> {
+        let mut base = self.clone();
+        let mut result = Self::from_bigint(BigInt::from_i64(1), self.context);
+        let mut n = exponent.unsigned_abs();
+
+        while n > 0 {
+            if n & 1 == 1 {
+                result = result.mul(&base);
+            }
+
+            n >>= 1;
+
+            if n > 0 {
+                base = base.mul(&base);
+            }
+        }
+
+        if exponent < 0 {
+            Self::from_bigint(BigInt::from_i64(1), self.context).div(&result)
+        } else {
+            Some(result)
+        }
+    }
+
+    /// Rounds to `places` digits after the decimal point.
+    pub fn round_to(&self, places: i64, rounding: RoundingMode) -> Self {
+        let drop = -places - self.exponent;
+
+        if drop <= 0 {
+            return self.clone();
+        }
+
+        let (coefficient, exponent) = Self::round_digits(
+            &self.coefficient,
+            self.exponent,
+            drop as usize,
+            rounding,
+            false,
+        );
+
+        Self {
+            coefficient,
+            exponent,
+            context: self.context,
+        }
+    }
+
+    fn aligned_coefficient(&self, exponent: i64) -> BigInt {
+        self.coefficient
+            .mul(&BigInt::pow10((self.exponent - exponent) as usize))
+    }
+
+    fn round(coefficient: BigInt, exponent: i64, context: DecimalContext, sticky: bool) -> Self {
+        let digits = coefficient.digit_count();
+
+        let (coefficient, exponent) = if digits > context.precision {
+            Self::round_digits(
+                &coefficient,
+                exponent,
+                digits - context.precision,
+                context.rounding,
+                sticky,
+            )
+        } else {
+            (coefficient, exponent)
+        };
+
+        Self {
+            coefficient,
+            exponent,
+            context,
+        }
+    }
+
+    /// Drops the lowest `drop` digits of `coefficient`, `sticky` tells whether
+    /// non-zero digits were already discarded below them.
+    fn round_digits(
+        coefficient: &BigInt,
+        exponent: i64,
+        drop: usize,
+        rounding: RoundingMode,
+        sticky: bool,
+    ) -> (BigInt, i64) {
+        let divisor = BigInt::pow10(drop);
+        let (quotient, remainder) = coefficient.div_rem(&divisor).unwrap();
+        let remainder = remainder.abs();
+        let negative = coefficient.is_negative();
+        let inexact = sticky || !remainder.is_zero();
+        let half = remainder
+            .mul(&BigInt::from_i64(2))
+            .cmp(&divisor)
+            .then(if sticky {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
+            });
+
+        let increment = match rounding {
+            RoundingMode::HALFEVEN => {
+                half == Ordering::Greater || (half == Ordering::Equal && !quotient.is_even())
+            }
+            RoundingMode::HALFUP => half != Ordering::Less,
+            RoundingMode::HALFDOWN => half == Ordering::Greater,
+            RoundingMode::UP => inexact,
+            RoundingMode::DOWN => false,
+            RoundingMode::CEILING => inexact && !negative,
+            RoundingMode::FLOOR => inexact && negative,
+        };
+
+        let quotient = if increment {
+            let one = if negative {
+                BigInt::from_i64(-1)
+            } else {
+                BigInt::from_i64(1)
+            };
+
+            quotient.add(&one)
+        } else {
+            quotient
+        };
+
+        (quotient, exponent + drop as i64)
+    }
+}
+
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp_value(other) == Ordering::Equal
+    }
+}
+
+impl Decimal {
+    pub fn cmp_value(&self, other: &Self) -> Ordering {
+        let exponent = self.exponent.min(other.exponent);
+
+        self.aligned_coefficient(exponent)
+            .cmp(&other.aligned_coefficient(exponent))
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut coefficient = self.coefficient.clone();
+        let mut exponent = self.exponent;
+        let ten = BigInt::from_i64(10);
+
+        while exponent < 0 && !coefficient.is_zero() {
+            let (quotient, remainder) = coefficient.div_rem(&ten).unwrap();
+
+            if !remainder.is_zero() {
+                break;
+            }
+
+            coefficient = quotient;
+            exponent += 1;
+        }
+
+        if coefficient.is_zero() {
+            return write!(f, "0");
+        }
+
+        let sign = if coefficient.is_negative() { "-" } else { "" };
+        let digits = coefficient.abs().to_string();
+
+        if exponent >= 0 {
+            return write!(f, "{}{}{}", sign, digits, "0".repeat(exponent as usize));
+        }
+
+        let places = exponent.unsigned_abs() as usize;
+
+        if digits.len() > places {
+            let (integer, fraction) = digits.split_at(digits.len() - places);
+
+            write!(f, "{}{}.{}", sign, integer, fraction)
+        } else {
+            write!(
+                f,
+                "{}0.{}{}",
+                sign,
+                "0".repeat(places - digits.len()),
+                digits
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Decimal, DecimalContext, RoundingMode};
+
+    fn dec(text: &str) -> Decimal {
+        Decimal::parse(text, DecimalContext::default()).unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        assert_eq!(dec("0.1").to_string(), "0.1");
+        assert_eq!(dec("-12.0340").to_string(), "-12.034");
+        assert_eq!(dec("0.000").to_string(), "0");
+        assert_eq!(dec("1200").to_string(), "1200");
+        assert_eq!(
+            Decimal::from_f64(0.001, DecimalContext::default())
+                .unwrap()
+                .to_string(),
+            "0.001"
+        );
+        assert!(Decimal::from_f64(f64::NAN, DecimalContext::default()).is_none());
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(dec("0.1").add(&dec("0.2")), dec("0.3"));
+        assert_eq!(dec("1.10").sub(&dec("2.2")).to_string(), "-1.1");
+        assert_eq!(dec("1.5").mul(&dec("-0.02")).to_string(), "-0.03");
+        assert_eq!(
+            dec("1").div(&dec("3")).unwrap().to_string(),
+            "0.3333333333333333333333333333"
+        );
+        assert_eq!(
+            dec("2").div(&dec("3")).unwrap().to_string(),
+            "0.6666666666666666666666666667"
+        );
+        assert!(dec("1").div(&dec("0")).is_none());
+        assert_eq!(dec("1.1").powi(2).unwrap().to_string(), "1.21");
+        assert_eq!(dec("2").powi(-2).unwrap().to_string(), "0.25");
+    }
+
+    #[test]
+    fn test_precision_and_rounding() {
+        let context = DecimalContext::new(3, RoundingMode::HALFEVEN);
+        let value = |text| Decimal::parse(text, context).unwrap();
+
+        assert_eq!(value("1.235").to_string(), "1.24");
+        assert_eq!(value("1.245").to_string(), "1.24");
+        assert_eq!(value("-1.245").to_string(), "-1.24");
+
+        let context = DecimalContext::new(3, RoundingMode::HALFUP);
+
+        assert_eq!(
+            Decimal::parse("1.245", context).unwrap().to_string(),
+            "1.25"
+        );
+
+        let context = DecimalContext::new(3, RoundingMode::FLOOR);
+
+        assert_eq!(
+            Decimal::parse("-1.241", context).unwrap().to_string(),
+            "-1.25"
+        );
+        assert_eq!(
+            Decimal::parse("1.249", context).unwrap().to_string(),
+            "1.24"
+        );
+
+        let context = DecimalContext::new(2, RoundingMode::HALFEVEN);
+
+        assert_eq!(Decimal::parse("9.99", context).unwrap().to_string(), "10");
+    }
+
+    #[test]
+    fn test_rounding_mode_parse() {
+        assert_eq!(RoundingMode::parse("half-up"), Some(RoundingMode::HALFUP));
+        assert_eq!(RoundingMode::parse("floor"), Some(RoundingMode::FLOOR));
+        assert_eq!(RoundingMode::parse("nearest"), None);
+    }
+
+    #[test]
+    fn test_round_to() {
+        assert_eq!(
+            dec("2.675").round_to(2, RoundingMode::HALFEVEN).to_string(),
+            "2.68"
+        );
+        assert_eq!(
+            dec("2.665").round_to(2, RoundingMode::HALFEVEN).to_string(),
+            "2.66"
+        );
+        assert_eq!(
+            dec("2.6").round_to(2, RoundingMode::HALFEVEN).to_string(),
+            "2.6"
+        );
+        assert_eq!(
+            dec("-2.5").round_to(0, RoundingMode::FLOOR).to_string(),
+            "-3"
+        );
+    }
+
+    #[test]
+    fn test_to_bigint() {
+        assert_eq!(dec("12.000").to_bigint().unwrap().to_string(), "12");
+        assert!(dec("12.5").to_bigint().is_none());
+    }
+}
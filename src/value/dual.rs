@@ -0,0 +1,176 @@
+use std::fmt;
+
+/// Dual number `value + Σ grad[i] εᵢ` with `εᵢ εⱼ = 0`. Evaluating a
+/// function on dual numbers carries its partial derivatives with respect to
+/// each variable exactly, forward through every operation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dual {
+    value: f64,
+    grad: Vec<f64>,
+}
+
+impl Dual {
+    /// The `index`-th of `count` independent variables, at `value`.
+    pub fn variable(value: f64, index: usize, count: usize) -> Self {
+        let mut grad = vec![0.; count];
+
+        grad[index] = 1.;
+
+        Self { value, grad }
+    }
+
+    pub fn constant(value: f64) -> Self {
+        Self {
+            value,
+            grad: vec![],
+        }
+    }
+
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// Partial derivative with respect to the `index`-th variable.
+    pub fn derivative(&self, index: usize) -> f64 {
+        self.grad.get(index).copied().unwrap_or(0.)
+    }
+
+    fn is_constant(&self) -> bool {
+        self.grad.iter().all(|d| *d == 0.)
+    }
+
+    /// Result `value` of a function of `self` and `other`, given its partial
+    /// derivatives with respect to each. Constants contribute nothing, even
+    /// where the partial derivative is not finite.
+    fn combine(&self, other: &Self, value: f64, dself: f64, dother: f64) -> Self {
+        let len = self.grad.len().max(other.grad.len());
+        let mut grad = vec![0.; len];
+
+        for (i, d) in grad.iter_mut().enumerate() {
+            if !self.is_constant() {
+                *d += dself * self.derivative(i);
+            }
+
+            if !other.is_constant() {
+                *d += dother * other.derivative(i);
+            }
+        }
+
+        Self { value, grad }
+    }
+
+    /// Applies a function with the given `value` and `derivative` at `self`.
+    pub fn chain(&self, value: f64, derivative: f64) -> Self {
+        self.combine(&Self::constant(0.), value, derivative, 0.)
+    }
+
+    pub fn scale(&self, factor: f64) -> Self {
+        self.chain(self.value * factor, factor)
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        self.combine(other, self.value + other.value, 1., 1.)
+    }
+
+    pub fn sub(&self, other: &Self) -> Self {
+        self.combine(other, self.value - other.value, 1., -1.)
+    }
+
+    pub fn mul(&self, other: &Self) -> Self {
+        self.combine(other, self.value * other.value, other.value, self.value)
+    }
+
+    /// Returns `None` when dividing by zero.
+    pub fn div(&self, other: &Self) -> Option<Self> {
+        if other.value == 0. {
+            return None;
+        }
+
+        Some(self.combine(
+            other,
+            self.value / other.value,
+            1. / other.value,
+            -self.value / (other.value * other.value),
+        ))
+    }
+
+    /// Returns `None` when the result is not real.
+    pub fn powf(&self, exponent: &Self) -> Option<Self> {
+        let value = self.value.powf(exponent.value);
+
+        if value.is_nan() {
+            return None;
+        }
+
+        Some(self.combine(
+            exponent,
+            value,
+            exponent.value * self.value.powf(exponent.value - 1.),
+            value * self.value.ln(),
+        ))
+    }
+}
+
+impl fmt::Display for Dual {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.grad.as_slice() {
+            [] => write!(f, "{}", self.value),
+            [d] => write!(f, "{} + {}ε", self.value, d),
+            grad => {
+                let grad: Vec<String> = grad.iter().map(|d| d.to_string()).collect();
+
+                write!(f, "{} + [{}]ε", self.value, grad.join(", "))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Dual;
+
+    #[test]
+    fn test_arithmetic() {
+        let x = Dual::variable(3., 0, 2);
+        let y = Dual::variable(4., 1, 2);
+        let xy = x.mul(&y).add(&x);
+
+        assert_eq!(xy.value(), 15.);
+        assert_eq!(xy.derivative(0), 5.);
+        assert_eq!(xy.derivative(1), 3.);
+
+        let quotient = x.div(&y).unwrap();
+
+        assert_eq!(quotient.derivative(0), 0.25);
+        assert_eq!(quotient.derivative(1), -3. / 16.);
+        assert!(x.div(&Dual::constant(0.)).is_none());
+    }
+
+    #[test]
+    fn test_powf() {
+        let x = Dual::variable(2., 0, 1);
+
+        assert_eq!(x.powf(&Dual::constant(3.)).unwrap().derivative(0), 12.);
+        assert_eq!(x.powf(&x).unwrap().derivative(0), 4. * (1. + 2f64.ln()));
+        assert_eq!(
+            Dual::variable(-2., 0, 1)
+                .powf(&Dual::constant(2.))
+                .unwrap()
+                .derivative(0),
+            -4.
+        );
+        assert!(Dual::variable(-2., 0, 1)
+            .powf(&Dual::constant(0.5))
+            .is_none());
+    }
+
+    #[test]
+    fn test_chain() {
+        let x = Dual::variable(0., 0, 1);
+        let sqrt = Dual::constant(0.).chain(0., f64::INFINITY);
+
+        assert_eq!(x.chain(x.value().sin(), x.value().cos()).derivative(0), 1.);
+        assert_eq!(sqrt.derivative(0), 0.);
+        assert_eq!(Dual::variable(2., 0, 1).scale(3.).to_string(), "6 + 3ε");
+    }
+}
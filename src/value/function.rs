@@ -0,0 +1,179 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+use crate::ast::ASTSemanticExpression;
+use crate::error::InterpreterError;
+use crate::format;
+use crate::symbol_table::SymbolTable;
+
+use super::Value;
+
+/// Results of a memoized function by its arguments, valid while the
+/// functions in the symbol table stay the same.
+#[derive(Default)]
+struct Memo {
+    generation: usize,
+    results: HashMap<String, Value>,
+}
+
+/// User function, `f(x, y) = x * y`, or an anonymous function such as a
+/// derivative or a lambda. Calls evaluate the body with the parameters bound
+/// in a scope of their own, next to the variables a lambda captured.
+#[derive(Clone)]
+pub struct Function {
+    name: Option<String>,
+    params: Vec<String>,
+    body: Rc<dyn ASTSemanticExpression>,
+    captures: HashMap<String, Value>,
+    memo: Option<Rc<RefCell<Memo>>>,
+}
+
+impl Function {
+    pub fn new(name: String, params: Vec<String>, body: Rc<dyn ASTSemanticExpression>) -> Self {
+        Self {
+            name: Some(name),
+            params,
+            body,
+            captures: HashMap::new(),
+            memo: None,
+        }
+    }
+
+    pub fn anonymous(params: Vec<String>, body: Rc<dyn ASTSemanticExpression>) -> Self {
+        Self::closure(params, body, HashMap::new())
+    }
+
+    /// Lambda with the values of the variables it refers to at its
+    /// definition, later assignments to them do not change it.
+    pub fn closure(
+        params: Vec<String>,
+        body: Rc<dyn ASTSemanticExpression>,
+        captures: HashMap<String, Value>,
+    ) -> Self {
+        Self {
+            name: None,
+            params,
+            body,
+            captures,
+            memo: None,
+        }
+    }
+
+    /// Function that remembers its results, `memo fib(n) = ...`. Its body
+    /// must only depend on its arguments, constants and other functions.
+    pub fn memoized(mut self) -> Self {
+        self.memo = Some(Rc::default());
+        self
+    }
+
+    pub fn is_memoized(&self) -> bool {
+        self.memo.is_some()
+    }
+
+    pub fn name(&self) -> Option<&String> {
+        self.name.as_ref()
+    }
+
+    pub fn params(&self) -> &[String] {
+        &self.params
+    }
+
+    pub fn body(&self) -> &dyn ASTSemanticExpression {
+        self.body.as_ref()
+    }
+
+    /// Variables a lambda captured at its definition.
+    pub fn captures(&self) -> &HashMap<String, Value> {
+        &self.captures
+    }
+
+    pub fn call(
+        &self,
+        args: &[Value],
+        symtab: &mut SymbolTable,
+    ) -> Result<Value, InterpreterError> {
+        if args.len() != self.params.len() {
+            return Err(InterpreterError::ArgumentCountMismatch(
+                self.name.clone().unwrap_or_else(|| self.to_string()),
+                self.params.len(),
+                args.len(),
+            ));
+        }
+
+        let key = self.memo_key(args, symtab);
+
+        if let Some(value) = key.as_ref().and_then(|key| self.recall(key)) {
+            return Ok(value);
+        }
+
+        let mut bindings = self.captures.clone();
+
+        bindings.extend(self.params.iter().cloned().zip(args.iter().cloned()));
+        symtab.push_scope(bindings)?;
+
+        let result = self.body.eval(symtab);
+
+        symtab.pop_scope();
+
+        if let (Some(memo), Some(key), Ok(value)) = (&self.memo, key, &result) {
+            memo.borrow_mut().results.insert(key, value.clone());
+        }
+
+        result
+    }
+
+    /// Key of the results of a memoized function for `args`, `None` when it
+    /// is not memoized or an argument is a function, whose text does not
+    /// identify it. Clears results from earlier definitions of functions.
+    fn memo_key(&self, args: &[Value], symtab: &SymbolTable) -> Option<String> {
+        let mut memo = self.memo.as_ref()?.borrow_mut();
+
+        if memo.generation != symtab.generation() {
+            memo.generation = symtab.generation();
+            memo.results.clear();
+        }
+
+        (!args.iter().any(contains_function)).then(|| format!("{:?}", args))
+    }
+
+    fn recall(&self, key: &str) -> Option<Value> {
+        self.memo.as_ref()?.borrow().results.get(key).cloned()
+    }
+}
+
+fn contains_function(value: &Value) -> bool {
+    match value {
+        Value::Function(_) => true,
+        Value::List(values) => values.iter().any(contains_function),
+        _ => false,
+    }
+}
+
+impl PartialEq for Function {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.params == other.params
+            && Rc::ptr_eq(&self.body, &other.body)
+            && self.captures == other.captures
+            && self.is_memoized() == other.is_memoized()
+    }
+}
+
+impl fmt::Debug for Function {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Function({})", self)
+    }
+}
+
+impl fmt::Display for Function {
+    /// Named functions show their signature, anonymous ones their body, as
+    /// in `x -> 2 * x`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (&self.name, self.params.as_slice()) {
+            (Some(name), params) => write!(f, "{}({})", name, params.join(", ")),
+            (None, params) => write!(f, "{}", format::lambda(params, self.body())),
+        }
+    }
+}
@@ -0,0 +1,568 @@
+use std::f64::consts::{FRAC_PI_2, PI, TAU};
+use std::fmt;
+
+/// Argument and value of the minimum of `gamma(x + 1)` for `x > -1`.
+const FACTORIAL_MIN_X: f64 = 0.461_632_144_968_362_3;
+const FACTORIAL_MIN: f64 = 0.885_603_194_410_888_7;
+
+/// Closed interval `[lo, hi]`. Arithmetic rounds the bounds outward, so a
+/// result always contains every value the exact operation could produce.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Interval {
+    lo: f64,
+    hi: f64,
+}
+
+impl Interval {
+    /// Returns `None` unless `lo <= hi`.
+    pub fn new(lo: f64, hi: f64) -> Option<Self> {
+        if lo <= hi {
+            Some(Self { lo, hi })
+        } else {
+            None
+        }
+    }
+
+    pub fn point(value: f64) -> Self {
+        Self {
+            lo: value,
+            hi: value,
+        }
+    }
+
+    pub fn entire() -> Self {
+        Self {
+            lo: f64::NEG_INFINITY,
+            hi: f64::INFINITY,
+        }
+    }
+
+    pub fn lo(&self) -> f64 {
+        self.lo
+    }
+
+    pub fn hi(&self) -> f64 {
+        self.hi
+    }
+
+    pub fn midpoint(&self) -> f64 {
+        self.lo / 2. + self.hi / 2.
+    }
+
+    pub fn width(&self) -> f64 {
+        self.hi - self.lo
+    }
+
+    pub fn contains_zero(&self) -> bool {
+        self.lo <= 0. && self.hi >= 0.
+    }
+
+    pub fn neg(&self) -> Self {
+        Self {
+            lo: -self.hi,
+            hi: -self.lo,
+        }
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        Self {
+            lo: add_down(self.lo, other.lo),
+            hi: add_up(self.hi, other.hi),
+        }
+    }
+
+    pub fn sub(&self, other: &Self) -> Self {
+        self.add(&other.neg())
+    }
+
+    pub fn mul(&self, other: &Self) -> Self {
+        let corners = [
+            (self.lo, other.lo),
+            (self.lo, other.hi),
+            (self.hi, other.lo),
+            (self.hi, other.hi),
+        ];
+
+        Self {
+            lo: corners
+                .iter()
+                .map(|(a, b)| mul_down(*a, *b))
+                .fold(f64::INFINITY, f64::min),
+            hi: corners
+                .iter()
+                .map(|(a, b)| mul_up(*a, *b))
+                .fold(f64::NEG_INFINITY, f64::max),
+        }
+    }
+
+    /// Returns `None` when dividing by exactly zero. A divisor that only
+    /// contains zero gives an unbounded result.
+    pub fn div(&self, other: &Self) -> Option<Self> {
+        if other.lo == 0. && other.hi == 0. {
+            return None;
+        }
+
+        if !other.contains_zero() {
+            let corners = [
+                (self.lo, other.lo),
+                (self.lo, other.hi),
+                (self.hi, other.lo),
+                (self.hi, other.hi),
+            ];
+
+            return Some(Self {
+                lo: corners
+                    .iter()
+                    .map(|(a, b)| div_down(*a, *b))
+                    .fold(f64::INFINITY, f64::min),
+                hi: corners
+                    .iter()
+                    .map(|(a, b)| div_up(*a, *b))
+                    .fold(f64::NEG_INFINITY, f64::max),
+            });
+        }
+
+        // `1 / other` is a half line when zero is an endpoint of `other`.
+        let reciprocal = if other.lo == 0. {
+            Self {
+                lo: div_down(1., other.hi),
+                hi: f64::INFINITY,
+            }
+        } else if other.hi == 0. {
+            Self {
+                lo: f64::NEG_INFINITY,
+                hi: div_up(1., other.lo),
+            }
+        } else {
+            return Some(Self::entire());
+        };
+
+        Some(self.mul(&reciprocal))
+    }
+
+    /// Returns `None` for a negative power of an interval that is exactly
+    /// zero.
+    pub fn powi(&self, exponent: i32) -> Option<Self> {
+        if exponent < 0 {
+            return Self::point(1.).div(&self.powi(-exponent)?);
+        }
+
+        let n = exponent as u32;
+        let (lo_down, lo_up) = pow_bounds(self.lo.abs(), n);
+        let (hi_down, hi_up) = pow_bounds(self.hi.abs(), n);
+
+        Some(if n % 2 == 1 {
+            Self {
+                lo: if self.lo < 0. { -lo_up } else { lo_down },
+                hi: if self.hi < 0. { -hi_down } else { hi_up },
+            }
+        } else if self.lo >= 0. {
+            Self {
+                lo: lo_down,
+                hi: hi_up,
+            }
+        } else if self.hi <= 0. {
+            Self {
+                lo: hi_down,
+                hi: lo_up,
+            }
+        } else {
+            Self {
+                lo: 0.,
+                hi: lo_up.max(hi_up),
+            }
+        })
+    }
+
+    /// Real power, returns `None` when the base is not non-negative.
+    pub fn powf(&self, exponent: &Self) -> Option<Self> {
+        if self.lo < 0. {
+            return None;
+        }
+
+        // `ln(x^y) = y ln(x)` is bilinear in `y` and `ln(x)`, so the extremes
+        // lie on the corners.
+        let corners = [
+            self.lo.powf(exponent.lo),
+            self.lo.powf(exponent.hi),
+            self.hi.powf(exponent.lo),
+            self.hi.powf(exponent.hi),
+        ];
+
+        Some(
+            Self {
+                lo: corners.iter().fold(f64::INFINITY, |a, b| a.min(*b)),
+                hi: corners.iter().fold(f64::NEG_INFINITY, |a, b| a.max(*b)),
+            }
+            .widen(),
+        )
+    }
+
+    /// Square root of a non-negative interval. `sqrt` is correctly rounded,
+    /// so exact roots are not widened.
+    pub fn sqrt(&self) -> Self {
+        Self {
+            lo: sqrt_down(self.lo),
+            hi: sqrt_up(self.hi),
+        }
+    }
+
+    /// Applies a non-decreasing function that is exact, such as `floor`.
+    pub fn map_exact(&self, f: fn(f64) -> f64) -> Self {
+        Self {
+            lo: f(self.lo),
+            hi: f(self.hi),
+        }
+    }
+
+    /// Applies a non-decreasing function whose results may be off by one
+    /// unit in the last place.
+    pub fn map(&self, f: fn(f64) -> f64) -> Self {
+        self.map_exact(f).widen()
+    }
+
+    pub fn abs(&self) -> Self {
+        if self.lo >= 0. {
+            *self
+        } else if self.hi <= 0. {
+            self.neg()
+        } else {
+            Self {
+                lo: 0.,
+                hi: self.hi.max(-self.lo),
+            }
+        }
+    }
+
+    /// `gamma(x + 1)` for `x > -1`, decreasing up to its minimum and
+    /// increasing after it. Returns `None` outside the domain.
+    pub fn factorial(&self, f: fn(f64) -> Option<f64>) -> Option<Self> {
+        if self.lo <= -1. {
+            return None;
+        }
+
+        let (lo, hi) = (f(self.lo)?, f(self.hi)?);
+
+        Some(
+            if self.lo >= FACTORIAL_MIN_X {
+                Self { lo, hi }
+            } else if self.hi <= FACTORIAL_MIN_X {
+                Self { lo: hi, hi: lo }
+            } else {
+                Self {
+                    lo: FACTORIAL_MIN,
+                    hi: lo.max(hi),
+                }
+            }
+            .widen(),
+        )
+    }
+
+    pub fn sin(&self) -> Self {
+        self.periodic(f64::sin, FRAC_PI_2)
+    }
+
+    pub fn cos(&self) -> Self {
+        self.periodic(f64::cos, 0.)
+    }
+
+    /// Tangent, increasing between its poles at `pi/2 + k pi`. Returns `None`
+    /// when the interval contains a pole.
+    pub fn tan(&self) -> Option<Self> {
+        if self.width() >= PI || self.contains_period(FRAC_PI_2, PI) {
+            return None;
+        }
+
+        Some(self.map(f64::tan))
+    }
+
+    /// A function of period `2 pi` with its maximum 1 at `peak` and its
+    /// minimum -1 half a period away.
+    fn periodic(&self, f: fn(f64) -> f64, peak: f64) -> Self {
+        if self.width() >= TAU {
+            return Self { lo: -1., hi: 1. };
+        }
+
+        let (lo, hi) = (f(self.lo), f(self.hi));
+        let range = Self {
+            lo: if self.contains_period(peak + PI, TAU) {
+                -1.
+            } else {
+                lo.min(hi)
+            },
+            hi: if self.contains_period(peak, TAU) {
+                1.
+            } else {
+                lo.max(hi)
+            },
+        }
+        .widen();
+
+        Self {
+            lo: range.lo.max(-1.),
+            hi: range.hi.min(1.),
+        }
+    }
+
+    /// Whether `at + k period` lies in the interval for some integer `k`.
+    fn contains_period(&self, at: f64, period: f64) -> bool {
+        at + ((self.lo - at) / period).ceil() * period <= self.hi
+    }
+
+    /// Smallest interval containing both the smaller lower and upper bounds.
+    pub fn min(&self, other: &Self) -> Self {
+        Self {
+            lo: self.lo.min(other.lo),
+            hi: self.hi.min(other.hi),
+        }
+    }
+
+    pub fn max(&self, other: &Self) -> Self {
+        Self {
+            lo: self.lo.max(other.lo),
+            hi: self.hi.max(other.hi),
+        }
+    }
+
+    fn widen(&self) -> Self {
+        Self {
+            lo: self.lo.next_down(),
+            hi: self.hi.next_up(),
+        }
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{}, {}]", self.lo, self.hi)
+    }
+}
+
+/// Rounds `value` down when the exact result, `value + error`, is below it.
+fn round_down(value: f64, error: f64) -> f64 {
+    if error < 0. {
+        value.next_down()
+    } else {
+        value
+    }
+}
+
+fn round_up(value: f64, error: f64) -> f64 {
+    if error > 0. {
+        value.next_up()
+    } else {
+        value
+    }
+}
+
+/// Sum and its rounding error, using the error-free two-sum transformation.
+fn add_error(a: f64, b: f64) -> (f64, f64) {
+    let sum = a + b;
+
+    if !sum.is_finite() {
+        return (sum, 0.);
+    }
+
+    let b_virtual = sum - a;
+
+    (sum, (a - (sum - b_virtual)) + (b - b_virtual))
+}
+
+fn mul_error(a: f64, b: f64) -> (f64, f64) {
+    // `0 * inf` only arises from unbounded intervals, where it is zero.
+    if a == 0. || b == 0. {
+        return (0., 0.);
+    }
+
+    let product = a * b;
+
+    if !product.is_finite() {
+        return (product, 0.);
+    }
+
+    (product, a.mul_add(b, -product))
+}
+
+fn div_error(a: f64, b: f64) -> (f64, f64) {
+    let quotient = a / b;
+
+    if !quotient.is_finite() || !b.is_finite() {
+        return (quotient, 0.);
+    }
+
+    let remainder = (-quotient).mul_add(b, a);
+
+    (quotient, remainder * b.signum())
+}
+
+fn add_down(a: f64, b: f64) -> f64 {
+    let (sum, error) = add_error(a, b);
+
+    round_down(sum, error)
+}
+
+fn add_up(a: f64, b: f64) -> f64 {
+    let (sum, error) = add_error(a, b);
+
+    round_up(sum, error)
+}
+
+fn mul_down(a: f64, b: f64) -> f64 {
+    let (product, error) = mul_error(a, b);
+
+    round_down(product, error)
+}
+
+fn mul_up(a: f64, b: f64) -> f64 {
+    let (product, error) = mul_error(a, b);
+
+    round_up(product, error)
+}
+
+fn div_down(a: f64, b: f64) -> f64 {
+    let (quotient, error) = div_error(a, b);
+
+    round_down(quotient, error)
+}
+
+fn div_up(a: f64, b: f64) -> f64 {
+    let (quotient, error) = div_error(a, b);
+
+    round_up(quotient, error)
+}
+
+fn sqrt_down(value: f64) -> f64 {
+    let root = value.sqrt();
+
+    round_down(root, -root.mul_add(root, -value))
+}
+
+fn sqrt_up(value: f64) -> f64 {
+    let root = value.sqrt();
+
+    round_up(root, -root.mul_add(root, -value))
+}
+
+/// Lower and upper bounds of `base^n` for a non-negative `base`.
+fn pow_bounds(base: f64, n: u32) -> (f64, f64) {
+    let mut lo = 1.;
+    let mut hi = 1.;
+
+    for _ in 0..n {
+        lo = mul_down(lo, base);
+        hi = mul_up(hi, base);
+    }
+
+    (lo, hi)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Interval;
+
+    fn interval(lo: f64, hi: f64) -> Interval {
+        Interval::new(lo, hi).unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(Interval::new(2., 1.).is_none());
+        assert!(Interval::new(f64::NAN, 1.).is_none());
+        assert_eq!(interval(9.5, 10.5).midpoint(), 10.);
+        assert_eq!(interval(9.5, 10.5).width(), 1.);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let a = interval(1., 2.);
+        let b = interval(-3., 4.);
+
+        assert_eq!(a.add(&b), interval(-2., 6.));
+        assert_eq!(a.sub(&b), interval(-3., 5.));
+        assert_eq!(a.mul(&b), interval(-6., 8.));
+        assert_eq!(a.div(&interval(2., 4.)).unwrap(), interval(0.25, 1.));
+    }
+
+    #[test]
+    fn test_outward_rounding() {
+        let sum = Interval::point(0.1).add(&Interval::point(0.2));
+
+        assert!(sum.lo() < sum.hi());
+        assert!(sum.lo() <= 0.3 && sum.hi() >= 0.3);
+
+        let third = Interval::point(1.).div(&Interval::point(3.)).unwrap();
+
+        assert_eq!(third.hi(), third.lo().next_up());
+    }
+
+    #[test]
+    fn test_div_by_zero() {
+        let a = interval(1., 2.);
+
+        assert!(a.div(&Interval::point(0.)).is_none());
+        assert_eq!(a.div(&interval(-1., 1.)).unwrap(), Interval::entire());
+        assert_eq!(
+            a.div(&interval(0., 2.)).unwrap(),
+            interval(0.5, f64::INFINITY)
+        );
+        assert_eq!(
+            a.div(&interval(-2., 0.)).unwrap(),
+            interval(f64::NEG_INFINITY, -0.5)
+        );
+    }
+
+    #[test]
+    fn test_pow() {
+        let a = interval(-2., 3.);
+
+        assert_eq!(a.powi(2).unwrap(), interval(0., 9.));
+        assert_eq!(a.powi(3).unwrap(), interval(-8., 27.));
+        assert_eq!(interval(-3., -2.).powi(2).unwrap(), interval(4., 9.));
+        assert_eq!(interval(2., 4.).powi(-1).unwrap(), interval(0.25, 0.5));
+        assert!(a.powf(&Interval::point(0.5)).is_none());
+
+        let root = interval(4., 9.).powf(&Interval::point(0.5)).unwrap();
+
+        assert!(root.lo() <= 2. && root.hi() >= 3.);
+    }
+
+    #[test]
+    fn test_sqrt() {
+        assert_eq!(interval(4., 9.).sqrt(), interval(2., 3.));
+
+        let root = interval(2., 2.).sqrt();
+
+        assert!(root.lo() < root.hi());
+        assert!(root.lo() * root.lo() <= 2. && root.hi() * root.hi() >= 2.);
+    }
+
+    #[test]
+    fn test_trigonometric() {
+        assert_eq!(interval(0., 2.).sin().hi(), 1.);
+        assert_eq!(interval(-4., 4.).cos(), interval(-1., 1.));
+        assert_eq!(interval(0., 10.).sin(), interval(-1., 1.));
+
+        let sin = interval(0.1, 0.2).sin();
+
+        assert!(sin.lo() <= 0.1f64.sin() && sin.hi() >= 0.2f64.sin() && sin.hi() < 0.2);
+
+        let cos = interval(1., 2.).cos();
+
+        assert!(cos.lo() <= 2f64.cos() && cos.hi() >= 1f64.cos() && cos.lo() > -1.);
+
+        let tan = interval(-1., 1.).tan().unwrap();
+
+        assert!(tan.lo() <= -(1f64.tan()) && tan.hi() >= 1f64.tan());
+        assert!(interval(1., 2.).tan().is_none());
+        assert!(interval(-2., -1.).tan().is_none());
+    }
+
+    #[test]
+    fn test_abs_and_factorial() {
+        assert_eq!(interval(-3., 2.).abs(), interval(0., 3.));
+
+        let factorial = interval(0., 3.).factorial(crate::math::factorial).unwrap();
+
+        assert!(factorial.lo() < 0.886 && factorial.hi() >= 6.);
+    }
+}
@@ -0,0 +1,331 @@
+mod bigint;
+mod complex;
+mod decimal;
+mod dual;
+mod function;
+mod interval;
+mod polynomial;
+mod quantity;
+mod rational;
+mod uncertain;
+
+use std::fmt;
+
+pub use bigint::BigInt;
+pub use complex::Complex;
+pub use decimal::{Decimal, DecimalContext, RoundingMode};
+pub use dual::Dual;
+pub use function::Function;
+pub use interval::Interval;
+pub use polynomial::Polynomial;
+pub use quantity::{Dimension, Quantity, Unit};
+pub use rational::Rational;
+pub use uncertain::Uncertain;
+
+/// Largest magnitude below which every integer is exactly representable as
+/// an `f64`.
+pub const MAX_EXACT_INTEGER: f64 = 9007199254740992.;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Number(f64),
+    Decimal(Decimal),
+    Rational(Rational),
+    Integer(BigInt),
+    Complex(Complex),
+    Quantity(Quantity),
+    Interval(Interval),
+    Uncertain(Uncertain),
+    Dual(Dual),
+    List(Vec<Value>),
+    Function(Function),
+    Polynomial(Polynomial),
+}
+
+impl Value {
+    /// Wraps an integer, using `Number` while it is still exact as an `f64`.
+    pub fn from_bigint(value: BigInt) -> Self {
+        if value.abs() <= BigInt::from_f64(MAX_EXACT_INTEGER).unwrap() {
+            Value::Number(value.to_f64())
+        } else {
+            Value::Integer(value)
+        }
+    }
+
+    /// Lengths along each nesting level of a list, empty for scalars.
+    pub fn shape(&self) -> Vec<usize> {
+        match self {
+            Value::List(values) => {
+                let mut shape = vec![values.len()];
+
+                if let Some(first) = values.first() {
+                    shape.extend(first.shape());
+                }
+
+                shape
+            }
+            _ => vec![],
+        }
+    }
+
+    /// Wraps a complex number, using `Number` when it has no imaginary part.
+    pub fn from_complex(value: Complex) -> Self {
+        if value.im == 0. {
+            Value::Number(value.re)
+        } else {
+            Value::Complex(value)
+        }
+    }
+
+    /// Wraps a quantity, using `Number` when its units cancel out.
+    pub fn from_quantity(value: Quantity) -> Self {
+        if value.dimension().is_dimensionless() {
+            Value::Number(value.value())
+        } else {
+            Value::Quantity(value)
+        }
+    }
+
+    pub fn to_complex(&self) -> Complex {
+        match self {
+            Value::Complex(value) => *value,
+            _ => Complex::new(self.to_f64(), 0.),
+        }
+    }
+
+    /// Returns the value as an interval, a real number is a point interval.
+    pub fn to_interval(&self) -> Option<Interval> {
+        match self {
+            Value::Interval(value) => Some(*value),
+            Value::Complex(_)
+            | Value::Quantity(_)
+            | Value::Uncertain(_)
+            | Value::Dual(_)
+            | Value::List(_)
+            | Value::Function(_)
+            | Value::Polynomial(_) => None,
+            _ => Some(Interval::point(self.to_f64())),
+        }
+    }
+
+    /// Returns the value as a measurement, a real number is exact.
+    pub fn to_uncertain(&self) -> Option<Uncertain> {
+        match self {
+            Value::Uncertain(value) => Some(value.clone()),
+            Value::Complex(_)
+            | Value::Quantity(_)
+            | Value::Interval(_)
+            | Value::Dual(_)
+            | Value::List(_)
+            | Value::Function(_)
+            | Value::Polynomial(_) => None,
+            _ => Some(Uncertain::exact(self.to_f64())),
+        }
+    }
+
+    /// Returns the value as a dual number, a real number is a constant.
+    pub fn to_dual(&self) -> Option<Dual> {
+        match self {
+            Value::Dual(value) => Some(value.clone()),
+            Value::Number(_) | Value::Decimal(_) | Value::Rational(_) | Value::Integer(_) => {
+                Some(Dual::constant(self.to_f64()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the value as an integer if it is an `Integer`, an integral
+    /// `Number` that is still exact, or an integral decimal or rational.
+    pub fn to_bigint(&self) -> Option<BigInt> {
+        match self {
+            Value::Number(value) if value.abs() <= MAX_EXACT_INTEGER => BigInt::from_f64(*value),
+            Value::Integer(value) => Some(value.clone()),
+            Value::Decimal(value) => value.to_bigint(),
+            Value::Rational(value) if value.is_integer() => Some(value.numerator().clone()),
+            _ => None,
+        }
+    }
+
+    /// Complex values always have an imaginary part, quantities always have a
+    /// unit, intervals and measurements have no single value and dual numbers
+    /// would lose their derivatives, so they, lists, functions and
+    /// polynomials are `NaN` as plain reals.
+    pub fn to_f64(&self) -> f64 {
+        match self {
+            Value::Number(value) => *value,
+            Value::Decimal(value) => value.to_f64(),
+            Value::Rational(value) => value.to_f64(),
+            Value::Integer(value) => value.to_f64(),
+            Value::Complex(_)
+            | Value::Quantity(_)
+            | Value::Interval(_)
+            | Value::Uncertain(_)
+            | Value::Dual(_)
+            | Value::List(_)
+            | Value::Function(_)
+            | Value::Polynomial(_) => f64::NAN,
+        }
+    }
+}
+
+impl From<f64> for Value {
+    fn from(value: f64) -> Self {
+        Value::Number(value)
+    }
+}
+
+impl PartialEq<f64> for Value {
+    fn eq(&self, other: &f64) -> bool {
+        match self {
+            Value::Number(value) => value == other,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Number(value) => write!(f, "{}", value),
+            Value::Decimal(value) => write!(f, "{}", value),
+            Value::Rational(value) if value.is_integer() => write!(f, "{}", value),
+            Value::Rational(value) => write!(f, "{} ({})", value, value.to_f64()),
+            Value::Integer(value) => write!(f, "{}", value),
+            Value::Complex(value) => write!(f, "{}", value),
+            Value::Quantity(value) => write!(f, "{}", value),
+            Value::Interval(value) if value.width().is_finite() => write!(
+                f,
+                "{} (mid {}, width {})",
+                value,
+                value.midpoint(),
+                value.width()
+            ),
+            Value::Interval(value) => write!(f, "{}", value),
+            Value::Uncertain(value) => write!(f, "{}", value),
+            Value::Dual(value) => write!(f, "{}", value),
+            Value::Function(value) => write!(f, "{}", value),
+            Value::Polynomial(value) => write!(f, "{}", value),
+            Value::List(values) => {
+                write!(f, "[")?;
+
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+
+                    write!(f, "{}", value)?;
+                }
+
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+/// Number backend used for literals.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum NumberMode {
+    #[default]
+    FLOAT,
+    DECIMAL(DecimalContext),
+    RATIONAL,
+}
+
+impl NumberMode {
+    pub fn literal(&self, value: f64) -> Value {
+        match self {
+            NumberMode::FLOAT => Value::Number(value),
+            NumberMode::DECIMAL(context) => Decimal::from_f64(value, *context)
+                .map(Value::Decimal)
+                .unwrap_or(Value::Number(value)),
+            NumberMode::RATIONAL => Rational::from_f64(value)
+                .map(Value::Rational)
+                .unwrap_or(Value::Number(value)),
+        }
+    }
+
+    /// Decimal literals whose digits an `f64` would not keep.
+    pub fn decimal(&self, text: &str) -> Value {
+        let value = text.parse::<f64>().unwrap_or(f64::NAN);
+
+        match self {
+            NumberMode::FLOAT => Value::Number(value),
+            NumberMode::DECIMAL(context) => Decimal::parse(text, *context)
+                .map(Value::Decimal)
+                .unwrap_or(Value::Number(value)),
+            NumberMode::RATIONAL => Rational::parse(text)
+                .map(Value::Rational)
+                .unwrap_or(Value::Number(value)),
+        }
+    }
+
+    /// Integer literals too long to be exact as an `f64`.
+    pub fn integer(&self, value: BigInt) -> Value {
+        match self {
+            NumberMode::FLOAT => Value::from_bigint(value),
+            NumberMode::DECIMAL(context) => Value::Decimal(Decimal::from_bigint(value, *context)),
+            NumberMode::RATIONAL => Value::Rational(Rational::from_bigint(value)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BigInt, DecimalContext, NumberMode, Value};
+
+    #[test]
+    fn test_literal() {
+        let decimal = NumberMode::DECIMAL(DecimalContext::default());
+
+        assert_eq!(NumberMode::FLOAT.literal(0.1), Value::Number(0.1));
+        assert_eq!(decimal.literal(0.1).to_string(), "0.1");
+        assert!(matches!(decimal.literal(0.1), Value::Decimal(_)));
+        assert_eq!(
+            decimal.decimal("1.00000000000000000001").to_string(),
+            "1.00000000000000000001"
+        );
+        assert_eq!(
+            NumberMode::RATIONAL
+                .decimal("0.30000000000000000001")
+                .to_string(),
+            "30000000000000000001/100000000000000000000 (0.3)"
+        );
+    }
+
+    #[test]
+    fn test_display_rational() {
+        assert_eq!(NumberMode::RATIONAL.literal(3.).to_string(), "3");
+        assert_eq!(NumberMode::RATIONAL.literal(0.5).to_string(), "1/2 (0.5)");
+    }
+
+    #[test]
+    fn test_from_bigint() {
+        let exact = BigInt::from_i64(9007199254740992);
+        let inexact = BigInt::from_i64(9007199254740993);
+
+        assert_eq!(Value::from_bigint(exact), 9007199254740992.);
+        assert_eq!(Value::from_bigint(inexact.clone()), Value::Integer(inexact));
+        assert_eq!(Value::Number(-12.).to_bigint(), Some(BigInt::from_i64(-12)));
+        assert!(Value::Number(0.5).to_bigint().is_none());
+        assert!(Value::Number(1e20).to_bigint().is_none());
+        assert_eq!(
+            NumberMode::RATIONAL.literal(3.).to_bigint(),
+            Some(BigInt::from_i64(3))
+        );
+        assert!(NumberMode::RATIONAL.literal(0.5).to_bigint().is_none());
+        assert_eq!(
+            NumberMode::DECIMAL(DecimalContext::default())
+                .literal(3.)
+                .to_bigint(),
+            Some(BigInt::from_i64(3))
+        );
+    }
+
+    #[test]
+    fn test_to_f64() {
+        let decimal = NumberMode::DECIMAL(DecimalContext::default());
+
+        assert_eq!(Value::Number(1.5).to_f64(), 1.5);
+        assert_eq!(decimal.literal(1.5).to_f64(), 1.5);
+    }
+}
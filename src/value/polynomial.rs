@@ -0,0 +1,427 @@
+use std::fmt;
+
+use crate::ast::{
+    ASTSemanticExpression, BinaryOpNode, BinaryOpType, NumberNode, UnaryOpNode, UnaryOpType,
+    VarNode,
+};
+use crate::error::InterpreterError;
+use crate::format;
+
+use super::{Complex, Value};
+
+/// Durand–Kerner iterations before the roots are taken as they are.
+const MAX_ROOT_ITERATIONS: usize = 1000;
+
+/// Polynomial in one variable with real coefficients, e.g. `x^2 - 3 * x + 2`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polynomial {
+    /// Coefficients from the constant term up, without zeros at the end.
+    coefficients: Vec<f64>,
+    var: String,
+}
+
+impl Polynomial {
+    /// Polynomial with `coefficients` from the constant term up.
+    pub fn new(mut coefficients: Vec<f64>, var: String) -> Self {
+        while coefficients.last() == Some(&0.) {
+            coefficients.pop();
+        }
+
+        Self { coefficients, var }
+    }
+
+    /// The polynomial `var` itself.
+    pub fn variable(var: String) -> Self {
+        Self::new(vec![0., 1.], var)
+    }
+
+    pub fn constant(value: f64, var: String) -> Self {
+        Self::new(vec![value], var)
+    }
+
+    /// Coefficients from the constant term up, empty for zero.
+    pub fn coefficients(&self) -> &[f64] {
+        &self.coefficients
+    }
+
+    pub fn var(&self) -> &String {
+        &self.var
+    }
+
+    /// Degree of the polynomial, 0 for constants including zero.
+    pub fn degree(&self) -> usize {
+        self.coefficients.len().saturating_sub(1)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coefficients.is_empty()
+    }
+
+    /// Variable of a result, constants take the variable of the other operand.
+    fn common_var(&self, other: &Self) -> Result<String, InterpreterError> {
+        match (self.degree(), other.degree()) {
+            (_, 0) => Ok(self.var.clone()),
+            (0, _) => Ok(other.var.clone()),
+            _ if self.var == other.var => Ok(self.var.clone()),
+            _ => Err(InterpreterError::TypeError(format!(
+                "polynomials in {} and {} cannot be combined",
+                self.var, other.var
+            ))),
+        }
+    }
+
+    pub fn add(&self, other: &Self) -> Result<Self, InterpreterError> {
+        let len = self.coefficients.len().max(other.coefficients.len());
+        let coefficients = (0..len)
+            .map(|i| self.coefficient(i) + other.coefficient(i))
+            .collect();
+
+        Ok(Self::new(coefficients, self.common_var(other)?))
+    }
+
+    pub fn sub(&self, other: &Self) -> Result<Self, InterpreterError> {
+        self.add(&other.scale(-1.))
+    }
+
+    pub fn mul(&self, other: &Self) -> Result<Self, InterpreterError> {
+        let var = self.common_var(other)?;
+
+        if self.is_zero() || other.is_zero() {
+            return Ok(Self::new(vec![], var));
+        }
+
+        let mut coefficients = vec![0.; self.coefficients.len() + other.coefficients.len() - 1];
+
+        for (i, a) in self.coefficients.iter().enumerate() {
+            for (j, b) in other.coefficients.iter().enumerate() {
+                coefficients[i + j] += a * b;
+            }
+        }
+
+        Ok(Self::new(coefficients, var))
+    }
+
+    pub fn scale(&self, factor: f64) -> Self {
+        Self::new(
+            self.coefficients.iter().map(|c| c * factor).collect(),
+            self.var.clone(),
+        )
+    }
+
+    pub fn powi(&self, exponent: u32) -> Self {
+        let mut result = Self::constant(1., self.var.clone());
+
+        for _ in 0..exponent {
+            result = result.mul(self).unwrap();
+        }
+
+        result
+    }
+
+    /// Quotient and remainder of long division, `None` when dividing by zero.
+    pub fn div_rem(&self, other: &Self) -> Result<Option<(Self, Self)>, InterpreterError> {
+        let var = self.common_var(other)?;
+        let divisor = match other.coefficients.last() {
+            Some(leading) => *leading,
+            None => return Ok(None),
+        };
+
+        if self.coefficients.len() < other.coefficients.len() {
+            return Ok(Some((Self::new(vec![], var.clone()), self.with_var(var))));
+        }
+
+        let mut remainder = self.coefficients.clone();
+        let mut quotient = vec![0.; self.coefficients.len() - other.coefficients.len() + 1];
+
+        for i in (0..quotient.len()).rev() {
+            let factor = remainder[i + other.degree()] / divisor;
+
+            quotient[i] = factor;
+
+            for (j, c) in other.coefficients.iter().enumerate() {
+                remainder[i + j] -= factor * c;
+            }
+
+            // The leading term cancels exactly, whatever the rounding.
+            remainder[i + other.degree()] = 0.;
+        }
+
+        Ok(Some((
+            Self::new(quotient, var.clone()),
+            Self::new(remainder, var),
+        )))
+    }
+
+    /// Monic greatest common divisor by the Euclidean algorithm, where a
+    /// remainder that is small relative to the dividend counts as zero.
+    pub fn gcd(&self, other: &Self) -> Result<Self, InterpreterError> {
+        let var = self.common_var(other)?;
+        let (mut a, mut b) = (self.with_var(var.clone()), other.with_var(var));
+
+        while !b.is_negligible(&a) {
+            let (_, remainder) = a.div_rem(&b)?.unwrap();
+
+            (a, b) = (b, remainder);
+        }
+
+        Ok(match a.coefficients.last() {
+            Some(leading) => a.scale(1. / leading),
+            None => a,
+        })
+    }
+
+    fn is_negligible(&self, reference: &Self) -> bool {
+        let scale = reference
+            .coefficients
+            .iter()
+            .fold(0f64, |scale, c| scale.max(c.abs()));
+
+        self.coefficients
+            .iter()
+            .all(|c| c.abs() <= 1e-10 * scale.max(f64::MIN_POSITIVE))
+    }
+
+    pub fn derivative(&self) -> Self {
+        Self::new(
+            self.coefficients
+                .iter()
+                .enumerate()
+                .skip(1)
+                .map(|(i, c)| i as f64 * c)
+                .collect(),
+            self.var.clone(),
+        )
+    }
+
+    /// Value at `x` by Horner's scheme, with the operations of the value so
+    /// that complex numbers, intervals and dual numbers work as well.
+    pub fn eval(&self, x: &Value) -> Result<Value, InterpreterError> {
+        let mut result = Value::Number(0.);
+
+        for c in self.coefficients.iter().rev() {
+            result = BinaryOpType::ADD
+                .apply(&BinaryOpType::MUL.apply(&result, x)?, &Value::Number(*c))?;
+        }
+
+        Ok(result)
+    }
+
+    /// All complex roots by the Durand–Kerner method, which refines
+    /// estimates of every root at once. Roots whose imaginary part is
+    /// negligible are real, the roots are ordered by real part.
+    pub fn roots(&self) -> Vec<Complex> {
+        let degree = self.degree();
+
+        if degree == 0 {
+            return vec![];
+        }
+
+        let leading = self.coefficients[degree];
+        let monic: Vec<f64> = self.coefficients.iter().map(|c| c / leading).collect();
+        // Every root lies within the Cauchy bound.
+        let radius = 1.
+            + monic[..degree]
+                .iter()
+                .fold(0f64, |radius, c| radius.max(c.abs()));
+        let mut roots: Vec<Complex> = (0..degree)
+            .map(|k| {
+                Complex::from_polar(
+                    radius,
+                    0.4 + 2. * std::f64::consts::PI * k as f64 / degree as f64,
+                )
+            })
+            .collect();
+
+        for _ in 0..MAX_ROOT_ITERATIONS {
+            let mut change: f64 = 0.;
+
+            for k in 0..degree {
+                let z = roots[k];
+                let value = monic.iter().rev().fold(Complex::new(0., 0.), |value, c| {
+                    value.mul(&z).add(&Complex::new(*c, 0.))
+                });
+                let denominator = (0..degree)
+                    .filter(|j| *j != k)
+                    .fold(Complex::new(1., 0.), |product, j| {
+                        product.mul(&z.sub(&roots[j]))
+                    });
+
+                if let Some(step) = value.div(&denominator) {
+                    roots[k] = z.sub(&step);
+                    change = change.max(step.norm() / z.norm().max(1.));
+                }
+            }
+
+            if change <= 4. * f64::EPSILON {
+                break;
+            }
+        }
+
+        for root in roots.iter_mut() {
+            if root.im.abs() <= 1e-8 * root.norm().max(1.) {
+                *root = Complex::new(root.re, 0.);
+            }
+        }
+
+        roots.sort_by(|a, b| a.re.total_cmp(&b.re).then(a.im.total_cmp(&b.im)));
+        roots
+    }
+
+    fn coefficient(&self, power: usize) -> f64 {
+        self.coefficients.get(power).copied().unwrap_or(0.)
+    }
+
+    fn with_var(&self, var: String) -> Self {
+        Self::new(self.coefficients.clone(), var)
+    }
+
+    /// The polynomial as an expression with its terms in decreasing powers.
+    fn to_node(&self) -> Box<dyn ASTSemanticExpression> {
+        let mut node: Option<Box<dyn ASTSemanticExpression>> = None;
+
+        for (power, c) in self.coefficients.iter().enumerate().rev() {
+            if *c == 0. {
+                continue;
+            }
+
+            let var = Box::new(VarNode::new(self.var.clone()));
+            let monomial: Option<Box<dyn ASTSemanticExpression>> = match power {
+                0 => None,
+                1 => Some(var),
+                power => Some(Box::new(BinaryOpNode::new(
+                    var,
+                    Box::new(NumberNode::new(power as f64)),
+                    BinaryOpType::POW,
+                ))),
+            };
+            // The leading coefficient keeps its sign, `-3 * x + 3`, the
+            // others are added or subtracted.
+            let coefficient = if node.is_none() { *c } else { c.abs() };
+            let term: Box<dyn ASTSemanticExpression> = match (monomial, coefficient) {
+                (None, c) => Box::new(NumberNode::new(c)),
+                (Some(monomial), 1.) => monomial,
+                (Some(monomial), -1.) => Box::new(UnaryOpNode::new(monomial, UnaryOpType::MINUS)),
+                (Some(monomial), c) => Box::new(BinaryOpNode::new(
+                    Box::new(NumberNode::new(c)),
+                    monomial,
+                    BinaryOpType::MUL,
+                )),
+            };
+
+            node = Some(match node {
+                None => term,
+                Some(node) if *c < 0. => Box::new(BinaryOpNode::new(node, term, BinaryOpType::SUB)),
+                Some(node) => Box::new(BinaryOpNode::new(node, term, BinaryOpType::ADD)),
+            });
+        }
+
+        node.unwrap_or_else(|| Box::new(NumberNode::new(0.)))
+    }
+}
+
+impl fmt::Display for Polynomial {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", format::expression(self.to_node().as_ref()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::value::{Complex, Value};
+
+    use super::Polynomial;
+
+    fn poly(coefficients: &[f64]) -> Polynomial {
+        Polynomial::new(coefficients.to_vec(), "x".to_string())
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(poly(&[2., -3., 1.]).to_string(), "x^2 - 3 * x + 2");
+        assert_eq!(poly(&[0., 0.5, 0., -1.]).to_string(), "-x^3 + 0.5 * x");
+        assert_eq!(poly(&[3., -3.]).to_string(), "-3 * x + 3");
+        assert_eq!(poly(&[-1.]).to_string(), "-1");
+        assert_eq!(poly(&[0., 0.]).to_string(), "0");
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let p = poly(&[1., 1.]);
+        let q = poly(&[-1., 1.]);
+
+        assert_eq!(p.mul(&q).unwrap(), poly(&[-1., 0., 1.]));
+        assert_eq!(p.sub(&p).unwrap(), poly(&[]));
+        assert_eq!(p.powi(3), poly(&[1., 3., 3., 1.]));
+        assert_eq!(poly(&[1., 2., 3.]).derivative(), poly(&[2., 6.]));
+
+        let y = Polynomial::variable("y".to_string());
+
+        assert!(p.add(&y).is_err());
+        assert_eq!(y.add(&poly(&[2.])).unwrap().to_string(), "y + 2");
+    }
+
+    #[test]
+    fn test_div_rem() {
+        // x^3 - 2x + 1 = (x - 1)(x^2 + x - 1)
+        let (quotient, remainder) = poly(&[1., -2., 0., 1.])
+            .div_rem(&poly(&[-1., 1.]))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(quotient, poly(&[-1., 1., 1.]));
+        assert!(remainder.is_zero());
+
+        let (quotient, remainder) = poly(&[1., 0., 1.])
+            .div_rem(&poly(&[0., 2.]))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(quotient, poly(&[0., 0.5]));
+        assert_eq!(remainder, poly(&[1.]));
+        assert_eq!(poly(&[1.]).div_rem(&poly(&[])).unwrap(), None);
+    }
+
+    #[test]
+    fn test_gcd() {
+        // (x - 1)(x + 2) and (x - 1)(x - 3)
+        let p = poly(&[-2., 1., 1.]);
+        let q = poly(&[3., -4., 1.]);
+
+        assert_eq!(p.scale(2.).gcd(&q).unwrap(), poly(&[-1., 1.]));
+        assert_eq!(p.gcd(&poly(&[1., 1.])).unwrap(), poly(&[1.]));
+    }
+
+    #[test]
+    fn test_eval() {
+        let p = poly(&[1., 0., 1.]);
+
+        assert_eq!(p.eval(&Value::Number(2.)).unwrap(), Value::Number(5.));
+        assert_eq!(
+            p.eval(&Value::Complex(Complex::new(0., 1.))).unwrap(),
+            Value::Number(0.)
+        );
+    }
+
+    #[test]
+    fn test_roots() {
+        let roots = poly(&[6., -5., -2., 1.]).roots();
+        let expected = [-2., 1., 3.];
+
+        assert_eq!(roots.len(), 3);
+
+        for (root, expected) in roots.iter().zip(expected) {
+            assert!((root.re - expected).abs() < 1e-12);
+            assert_eq!(root.im, 0.);
+        }
+
+        let roots = poly(&[1., 0., 1.]).roots();
+
+        assert!(roots[0].sub(&Complex::new(0., -1.)).norm() < 1e-12);
+        assert!(roots[1].sub(&Complex::new(0., 1.)).norm() < 1e-12);
+
+        let roots = poly(&[1., -2., 1.]).roots();
+
+        assert!(roots.iter().all(|root| (root.re - 1.).abs() < 1e-6));
+        assert!(poly(&[5.]).roots().is_empty());
+    }
+}
@@ -0,0 +1,378 @@
+use std::fmt;
+
+const BASE_UNITS: [&str; 7] = ["m", "kg", "s", "A", "K", "mol", "cd"];
+
+/// Exponents of the SI base units `m, kg, s, A, K, mol, cd`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Dimension(pub [i32; 7]);
+
+impl Dimension {
+    pub fn is_dimensionless(&self) -> bool {
+        self.0.iter().all(|exponent| *exponent == 0)
+    }
+
+    pub fn mul(&self, other: &Self) -> Self {
+        Self(std::array::from_fn(|i| self.0[i] + other.0[i]))
+    }
+
+    pub fn div(&self, other: &Self) -> Self {
+        Self(std::array::from_fn(|i| self.0[i] - other.0[i]))
+    }
+
+    pub fn powi(&self, exponent: i32) -> Self {
+        Self(self.0.map(|value| value * exponent))
+    }
+
+    /// Raises to a real power, returns `None` when an exponent would not be
+    /// an integer, as in `m^0.5`.
+    pub fn powf(&self, exponent: f64) -> Option<Self> {
+        let mut result = [0; 7];
+
+        for (i, value) in self.0.iter().enumerate() {
+            let scaled = *value as f64 * exponent;
+
+            if scaled.fract() != 0. {
+                return None;
+            }
+
+            result[i] = scaled as i32;
+        }
+
+        Some(Self(result))
+    }
+}
+
+impl fmt::Display for Dimension {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let unit = Unit::base(*self);
+
+        if unit.name.is_empty() {
+            write!(f, "1")
+        } else {
+            write!(f, "{}", unit.name)
+        }
+    }
+}
+
+/// Named unit, `factor` converts a value in this unit to SI base units.
+/// Products and powers collect the exponent of each named unit, so that
+/// `m^2/m` is `m` and `m*m` is `m^2`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Unit {
+    name: String,
+    powers: Vec<(String, i32)>,
+    factor: f64,
+    dimension: Dimension,
+}
+
+impl Unit {
+    pub fn new(name: &str, factor: f64, dimension: Dimension) -> Self {
+        Self::from_powers(vec![(name.to_string(), 1)], factor, dimension)
+    }
+
+    pub fn dimensionless() -> Self {
+        Self::from_powers(vec![], 1., Dimension::default())
+    }
+
+    /// The SI base units of a dimension, `m/s^2`.
+    pub fn base(dimension: Dimension) -> Self {
+        let powers = BASE_UNITS
+            .iter()
+            .zip(dimension.0)
+            .map(|(name, exponent)| (name.to_string(), exponent))
+            .collect();
+
+        Self::from_powers(powers, 1., dimension)
+    }
+
+    fn from_powers(mut powers: Vec<(String, i32)>, factor: f64, dimension: Dimension) -> Self {
+        powers.retain(|(name, exponent)| !name.is_empty() && *exponent != 0);
+
+        Self {
+            name: name(&powers),
+            powers,
+            factor,
+            dimension,
+        }
+    }
+
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn factor(&self) -> f64 {
+        self.factor
+    }
+
+    pub fn dimension(&self) -> Dimension {
+        self.dimension
+    }
+
+    pub fn mul(&self, other: &Self) -> Self {
+        Self::from_powers(
+            self.collect(other, 1),
+            self.factor * other.factor,
+            self.dimension.mul(&other.dimension),
+        )
+    }
+
+    pub fn div(&self, other: &Self) -> Self {
+        Self::from_powers(
+            self.collect(other, -1),
+            self.factor / other.factor,
+            self.dimension.div(&other.dimension),
+        )
+    }
+
+    /// Exponents of `self` times `other^sign`, in order of appearance.
+    fn collect(&self, other: &Self, sign: i32) -> Vec<(String, i32)> {
+        let mut powers = self.powers.clone();
+
+        for (name, exponent) in other.powers.iter() {
+            match powers.iter_mut().find(|(other, _)| other == name) {
+                Some((_, power)) => *power += sign * exponent,
+                None => powers.push((name.clone(), sign * exponent)),
+            }
+        }
+
+        powers
+    }
+
+    pub fn powi(&self, exponent: i32) -> Self {
+        Self::from_powers(
+            self.powers
+                .iter()
+                .map(|(name, power)| (name.clone(), power * exponent))
+                .collect(),
+            self.factor.powi(exponent),
+            self.dimension.powi(exponent),
+        )
+    }
+
+    /// Raises to a real power, returns `None` when the exponent of a named
+    /// unit would not be an integer, as in `ha^0.5`.
+    pub fn powf(&self, exponent: f64) -> Option<Self> {
+        let powers = self
+            .powers
+            .iter()
+            .map(|(name, power)| {
+                let scaled = *power as f64 * exponent;
+
+                (scaled.fract() == 0.).then(|| (name.clone(), scaled as i32))
+            })
+            .collect::<Option<Vec<(String, i32)>>>()?;
+
+        Some(Self::from_powers(
+            powers,
+            self.factor.powf(exponent),
+            self.dimension.powf(exponent)?,
+        ))
+    }
+}
+
+/// Name of a product of unit powers, the negative ones after a `/`, e.g.
+/// `kg*m/s^2`, or `s^-1` without a numerator.
+fn name(powers: &[(String, i32)]) -> String {
+    let power = |name: &String, exponent: i32| match exponent {
+        1 => name.clone(),
+        _ => format!("{}^{}", name, exponent),
+    };
+    let numerator: Vec<String> = powers
+        .iter()
+        .filter(|(_, exponent)| *exponent > 0)
+        .map(|(name, exponent)| power(name, *exponent))
+        .collect();
+    let denominator: Vec<String> = powers
+        .iter()
+        .filter(|(_, exponent)| *exponent < 0)
+        .map(|(name, exponent)| power(name, -exponent))
+        .collect();
+    let numerator = numerator.join("*");
+
+    match denominator.as_slice() {
+        [] => numerator,
+        [_] if numerator.is_empty() => powers
+            .iter()
+            .find(|(_, exponent)| *exponent < 0)
+            .map(|(name, exponent)| power(name, *exponent))
+            .unwrap_or_default(),
+        _ if numerator.is_empty() => format!("({})^-1", denominator.join("*")),
+        [denominator] => format!("{}/{}", numerator, denominator),
+        _ => format!("{}/({})", numerator, denominator.join("*")),
+    }
+}
+
+/// Physical quantity stored in SI base units, displayed in `unit`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Quantity {
+    value: f64,
+    unit: Unit,
+}
+
+impl Quantity {
+    /// Creates a quantity of `value` measured in `unit`.
+    pub fn new(value: f64, unit: Unit) -> Self {
+        Self {
+            value: value * unit.factor,
+            unit,
+        }
+    }
+
+    pub fn dimensionless(value: f64) -> Self {
+        Self::new(value, Unit::dimensionless())
+    }
+
+    /// Value in SI base units.
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    pub fn dimension(&self) -> Dimension {
+        self.unit.dimension
+    }
+
+    pub fn neg(&self) -> Self {
+        self.scale(-1.)
+    }
+
+    pub fn scale(&self, factor: f64) -> Self {
+        Self {
+            value: self.value * factor,
+            unit: self.unit.clone(),
+        }
+    }
+
+    /// Returns `None` when the dimensions differ. The result keeps the unit
+    /// of `self`, unless `self` is a plain number.
+    pub fn add(&self, other: &Self) -> Option<Self> {
+        if self.dimension() != other.dimension() {
+            return None;
+        }
+
+        let unit = if self.unit.name.is_empty() {
+            other.unit.clone()
+        } else {
+            self.unit.clone()
+        };
+
+        Some(Self {
+            value: self.value + other.value,
+            unit,
+        })
+    }
+
+    pub fn mul(&self, other: &Self) -> Self {
+        Self {
+            value: self.value * other.value,
+            unit: self.unit.mul(&other.unit),
+        }
+    }
+
+    pub fn div(&self, other: &Self) -> Self {
+        Self {
+            value: self.value / other.value,
+            unit: self.unit.div(&other.unit),
+        }
+    }
+
+    /// Returns `None` when the resulting dimension is not integral.
+    pub fn powf(&self, exponent: f64) -> Option<Self> {
+        let dimension = self.dimension().powf(exponent)?;
+        // Units such as `ha^0.5` have no name, display in base units.
+        let unit = self
+            .unit
+            .powf(exponent)
+            .unwrap_or_else(|| Unit::base(dimension));
+
+        Some(Self {
+            value: self.value.powf(exponent),
+            unit,
+        })
+    }
+
+    /// Expresses the quantity in `unit`, returns `None` when the dimensions
+    /// differ.
+    pub fn convert(&self, unit: &Unit) -> Option<Self> {
+        if self.dimension() != unit.dimension {
+            return None;
+        }
+
+        Some(Self {
+            value: self.value,
+            unit: unit.clone(),
+        })
+    }
+}
+
+impl fmt::Display for Quantity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Unit factors introduce rounding noise, so show 15 significant digits.
+        let value: f64 = format!("{:.14e}", self.value / self.unit.factor)
+            .parse()
+            .unwrap();
+
+        write!(f, "{} {}", value, self.unit.name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Dimension, Quantity, Unit};
+
+    const LENGTH: Dimension = Dimension([1, 0, 0, 0, 0, 0, 0]);
+    const TIME: Dimension = Dimension([0, 0, 1, 0, 0, 0, 0]);
+
+    #[test]
+    fn test_unit_names() {
+        let km = Unit::new("km", 1000., LENGTH);
+        let h = Unit::new("h", 3600., TIME);
+        let s = Unit::new("s", 1., TIME);
+
+        assert_eq!(km.div(&h).name(), "km/h");
+        assert_eq!(km.div(&s.powi(2)).name(), "km/s^2");
+        assert_eq!(km.mul(&h).powi(2).name(), "km^2*h^2");
+        assert_eq!(Unit::dimensionless().div(&s).name(), "s^-1");
+        assert_eq!(Unit::dimensionless().div(&s.mul(&h)).name(), "(s*h)^-1");
+        assert_eq!(km.div(&s.mul(&h)).name(), "km/(s*h)");
+        assert_eq!(LENGTH.div(&TIME.powi(2)).to_string(), "m/s^2");
+
+        // Powers of the same unit are collected
+        assert_eq!(km.mul(&km).name(), "km^2");
+        assert_eq!(km.powi(2).div(&km).name(), "km");
+        assert_eq!(km.div(&km).name(), "");
+        assert_eq!(km.div(&h).powi(2).powf(0.5).unwrap().name(), "km/h");
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let km = Quantity::new(5., Unit::new("km", 1000., LENGTH));
+        let m = Quantity::new(300., Unit::new("m", 1., LENGTH));
+        let s = Quantity::new(2., Unit::new("s", 1., TIME));
+
+        assert_eq!(km.add(&m).unwrap().to_string(), "5.3 km");
+        assert!(km.add(&s).is_none());
+        assert_eq!(m.div(&s).to_string(), "150 m/s");
+        assert_eq!(m.mul(&m).powf(0.5).unwrap().to_string(), "300 m");
+        assert!(m.powf(0.5).is_none());
+        assert_eq!(m.mul(&m).div(&m).to_string(), "300 m");
+        assert_eq!(
+            m.mul(&m).div(&s.mul(&s)).powf(0.5).unwrap().to_string(),
+            "150 m/s"
+        );
+
+        let hectare = Quantity::new(1., Unit::new("ha", 1e4, LENGTH.powi(2)));
+
+        assert_eq!(hectare.powf(0.5).unwrap().to_string(), "100 m");
+    }
+
+    #[test]
+    fn test_convert() {
+        let mph = Unit::new("mph", 0.44704, LENGTH.div(&TIME));
+        let kmh = Unit::new("km", 1000., LENGTH).div(&Unit::new("h", 3600., TIME));
+        let speed = Quantity::new(60., mph).convert(&kmh).unwrap();
+
+        assert!((speed.value() - 26.8224).abs() < 1e-12);
+        assert!(speed.to_string().ends_with(" km/h"));
+        assert!(speed.convert(&Unit::new("s", 1., TIME)).is_none());
+    }
+}
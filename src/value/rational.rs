@@ -0,0 +1,242 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use super::BigInt;
+
+/// Exact fraction kept in lowest terms with a positive denominator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rational {
+    numerator: BigInt,
+    denominator: BigInt,
+}
+
+impl Rational {
+    /// Returns `None` when `denominator` is zero.
+    pub fn new(numerator: BigInt, denominator: BigInt) -> Option<Self> {
+        if denominator.is_zero() {
+            return None;
+        }
+
+        let gcd = numerator.gcd(&denominator);
+        let (mut numerator, _) = numerator.div_rem(&gcd)?;
+        let (mut denominator, _) = denominator.div_rem(&gcd)?;
+
+        if denominator.is_negative() {
+            numerator = numerator.neg();
+            denominator = denominator.neg();
+        }
+
+        Some(Self {
+            numerator,
+            denominator,
+        })
+    }
+
+    pub fn from_bigint(value: BigInt) -> Self {
+        Self {
+            numerator: value,
+            denominator: BigInt::from_i64(1),
+        }
+    }
+
+    /// Converts a finite `f64` using its shortest round-trip representation,
+    /// so `0.1` becomes exactly `1/10`.
+    pub fn from_f64(value: f64) -> Option<Self> {
+        if !value.is_finite() {
+            return None;
+        }
+
+        Self::parse(&value.to_string())
+    }
+
+    /// Parses a plain decimal literal such as `-12.034` exactly.
+    pub fn parse(text: &str) -> Option<Self> {
+        let (integer, fraction) = text.split_once('.').unwrap_or((text, ""));
+
+        Self::new(
+            BigInt::parse(&format!("{}{}", integer, fraction))?,
+            BigInt::pow10(fraction.len()),
+        )
+    }
+
+    pub fn numerator(&self) -> &BigInt {
+        &self.numerator
+    }
+
+    pub fn denominator(&self) -> &BigInt {
+        &self.denominator
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denominator == BigInt::from_i64(1)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numerator.is_zero()
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        // Scale the quotient to keep at least 20 significant digits, since the
+        // numerator and denominator may not fit in an `f64` on their own.
+        let scale =
+            (20 + self.denominator.digit_count()).saturating_sub(self.numerator.digit_count());
+        let (quotient, _) = self
+            .numerator
+            .mul(&BigInt::pow10(scale))
+            .div_rem(&self.denominator)
+            .unwrap();
+
+        format!("{}e-{}", quotient, scale)
+            .parse::<f64>()
+            .unwrap_or(f64::NAN)
+    }
+
+    pub fn neg(&self) -> Self {
+        Self {
+            numerator: self.numerator.neg(),
+            denominator: self.denominator.clone(),
+        }
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        Self::new(
+            self.numerator
+                .mul(&other.denominator)
+                .add(&other.numerator.mul(&self.denominator)),
+            self.denominator.mul(&other.denominator),
+        )
+        .unwrap()
+    }
+
+    pub fn sub(&self, other: &Self) -> Self {
+        self.add(&other.neg())
+    }
+
+    pub fn mul(&self, other: &Self) -> Self {
+        Self::new(
+            self.numerator.mul(&other.numerator),
+            self.denominator.mul(&other.denominator),
+        )
+        .unwrap()
+    }
+
+    /// Returns `None` when dividing by zero.
+    pub fn div(&self, other: &Self) -> Option<Self> {
+        Self::new(
+            self.numerator.mul(&other.denominator),
+            self.denominator.mul(&other.numerator),
+        )
+    }
+
+    /// Raises to an integer power, returns `None` for `0` to a negative power.
+    pub fn powi(&self, exponent: i64) -> Option<Self> {
+        let n = u32::try_from(exponent.unsigned_abs()).ok()?;
+        let value = Self::new(self.numerator.pow(n), self.denominator.pow(n))?;
+
+        if exponent < 0 {
+            Self::from_bigint(BigInt::from_i64(1)).div(&value)
+        } else {
+            Some(value)
+        }
+    }
+
+    pub fn floor(&self) -> BigInt {
+        self.numerator.div_floor(&self.denominator).unwrap().0
+    }
+
+    pub fn ceil(&self) -> BigInt {
+        self.neg().floor().neg()
+    }
+
+    /// Rounds half away from zero.
+    pub fn round(&self) -> BigInt {
+        let half = Self::new(BigInt::from_i64(1), BigInt::from_i64(2)).unwrap();
+
+        if self.numerator.is_negative() {
+            self.sub(&half).ceil()
+        } else {
+            self.add(&half).floor()
+        }
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.numerator
+            .mul(&other.denominator)
+            .cmp(&other.numerator.mul(&self.denominator))
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BigInt, Rational};
+
+    fn ratio(numerator: i64, denominator: i64) -> Rational {
+        Rational::new(BigInt::from_i64(numerator), BigInt::from_i64(denominator)).unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        assert_eq!(ratio(2, 4), ratio(1, 2));
+        assert_eq!(ratio(3, -6), ratio(-1, 2));
+        assert_eq!(ratio(0, -6).to_string(), "0");
+        assert!(Rational::new(BigInt::from_i64(1), BigInt::zero()).is_none());
+    }
+
+    #[test]
+    fn test_from_f64() {
+        assert_eq!(Rational::from_f64(0.1).unwrap(), ratio(1, 10));
+        assert_eq!(Rational::from_f64(-2.5).unwrap(), ratio(-5, 2));
+        assert_eq!(Rational::from_f64(3.).unwrap().to_string(), "3");
+        assert!(Rational::from_f64(f64::INFINITY).is_none());
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(ratio(1, 3).add(&ratio(1, 6)), ratio(1, 2));
+        assert_eq!(ratio(1, 3).sub(&ratio(1, 2)), ratio(-1, 6));
+        assert_eq!(ratio(2, 3).mul(&ratio(9, 4)), ratio(3, 2));
+        assert_eq!(ratio(2, 3).div(&ratio(4, 3)).unwrap(), ratio(1, 2));
+        assert!(ratio(2, 3).div(&ratio(0, 1)).is_none());
+        assert_eq!(ratio(2, 3).powi(-2).unwrap(), ratio(9, 4));
+    }
+
+    #[test]
+    fn test_rounding() {
+        assert_eq!(ratio(-7, 2).floor(), BigInt::from_i64(-4));
+        assert_eq!(ratio(-7, 2).ceil(), BigInt::from_i64(-3));
+        assert_eq!(ratio(-7, 2).round(), BigInt::from_i64(-4));
+        assert_eq!(ratio(7, 3).round(), BigInt::from_i64(2));
+    }
+
+    #[test]
+    fn test_to_f64() {
+        assert_eq!(ratio(1, 4).to_f64(), 0.25);
+        assert_eq!(ratio(-1, 3).to_f64(), -1. / 3.);
+
+        let huge = Rational::new(
+            BigInt::pow10(400),
+            BigInt::pow10(399).mul(&BigInt::from_i64(4)),
+        )
+        .unwrap();
+
+        assert_eq!(huge.to_f64(), 2.5);
+    }
+}
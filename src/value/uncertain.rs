@@ -0,0 +1,227 @@
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Each `±` literal is an independent source of error.
+static NEXT_SOURCE: AtomicUsize = AtomicUsize::new(0);
+
+/// Value with a standard uncertainty, propagated to first order. The error
+/// is kept per source, as the change in value for a one standard deviation
+/// change in that source, so repeated uses of a measurement stay correlated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Uncertain {
+    value: f64,
+    terms: Vec<(usize, f64)>,
+}
+
+impl Uncertain {
+    /// Creates a measurement with a new, independent error source.
+    pub fn new(value: f64, uncertainty: f64) -> Self {
+        let terms = if uncertainty == 0. {
+            vec![]
+        } else {
+            vec![(NEXT_SOURCE.fetch_add(1, Ordering::Relaxed), uncertainty)]
+        };
+
+        Self { value, terms }
+    }
+
+    pub fn exact(value: f64) -> Self {
+        Self {
+            value,
+            terms: vec![],
+        }
+    }
+
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// Standard uncertainty, combining all error sources in quadrature.
+    pub fn uncertainty(&self) -> f64 {
+        self.terms
+            .iter()
+            .fold(0., |sum, (_, term)| sum + term * term)
+            .sqrt()
+    }
+
+    /// Result `value` of a function of `self` and `other`, given its partial
+    /// derivatives with respect to each.
+    fn combine(&self, other: &Self, value: f64, dself: f64, dother: f64) -> Self {
+        let mut terms: Vec<(usize, f64)> = vec![];
+
+        for (source, term) in self.terms.iter() {
+            terms.push((*source, dself * term));
+        }
+
+        for (source, term) in other.terms.iter() {
+            match terms.iter_mut().find(|(s, _)| s == source) {
+                Some((_, sum)) => *sum += dother * term,
+                None => terms.push((*source, dother * term)),
+            }
+        }
+
+        terms.retain(|(_, term)| *term != 0.);
+
+        Self { value, terms }
+    }
+
+    pub fn scale(&self, factor: f64) -> Self {
+        self.combine(&Self::exact(0.), self.value * factor, factor, 0.)
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        self.combine(other, self.value + other.value, 1., 1.)
+    }
+
+    pub fn sub(&self, other: &Self) -> Self {
+        self.combine(other, self.value - other.value, 1., -1.)
+    }
+
+    pub fn mul(&self, other: &Self) -> Self {
+        self.combine(other, self.value * other.value, other.value, self.value)
+    }
+
+    /// Returns `None` when dividing by zero.
+    pub fn div(&self, other: &Self) -> Option<Self> {
+        if other.value == 0. {
+            return None;
+        }
+
+        Some(self.combine(
+            other,
+            self.value / other.value,
+            1. / other.value,
+            -self.value / (other.value * other.value),
+        ))
+    }
+
+    /// Returns `None` when the result is not real, or its error is not
+    /// finite as for `(0 ± 1)^0.5`.
+    pub fn powf(&self, exponent: &Self) -> Option<Self> {
+        let value = self.value.powf(exponent.value);
+
+        if value.is_nan() {
+            return None;
+        }
+
+        let dself = exponent.value * self.value.powf(exponent.value - 1.);
+        let dexponent = if exponent.terms.is_empty() {
+            0.
+        } else {
+            value * self.value.ln()
+        };
+
+        self.combine(exponent, value, dself, dexponent).finite()
+    }
+
+    /// Applies `f`, propagating the error with its numerical derivative.
+    /// Returns `None` when the derivative is not finite, as for `sqrt` at 0.
+    pub fn map(&self, f: impl Fn(f64) -> f64) -> Option<Self> {
+        let value = f(self.value);
+        let h = f64::EPSILON.cbrt() * self.value.abs().max(1.);
+        let derivative = (f(self.value + h) - f(self.value - h)) / (2. * h);
+
+        self.combine(&Self::exact(0.), value, derivative, 0.)
+            .finite()
+    }
+
+    /// `None` when an error is not finite, where the function is not
+    /// differentiable.
+    fn finite(self) -> Option<Self> {
+        self.terms
+            .iter()
+            .all(|(_, term)| term.is_finite())
+            .then_some(self)
+    }
+}
+
+impl fmt::Display for Uncertain {
+    /// Shows the uncertainty with two significant digits when it starts with
+    /// a 1 and one otherwise, and the value to the same decimal place.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let uncertainty = self.uncertainty();
+
+        if uncertainty == 0. || !uncertainty.is_finite() || !self.value.is_finite() {
+            return write!(f, "{} ± {}", self.value, uncertainty);
+        }
+
+        let exponent = uncertainty.log10().floor() as i32;
+        let digits = if uncertainty / 10f64.powi(exponent) < 2. {
+            2
+        } else {
+            1
+        };
+        let decimals = digits - 1 - exponent;
+
+        if decimals >= 0 {
+            let decimals = decimals as usize;
+
+            write!(
+                f,
+                "{:.*} ± {:.*}",
+                decimals, self.value, decimals, uncertainty
+            )
+        } else {
+            let unit = 10f64.powi(-decimals);
+
+            write!(
+                f,
+                "{} ± {}",
+                (self.value / unit).round() * unit,
+                (uncertainty / unit).round() * unit
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Uncertain;
+
+    #[test]
+    fn test_propagation() {
+        let x = Uncertain::new(3., 0.1);
+        let y = Uncertain::new(4., 0.2);
+
+        assert!((x.add(&y).uncertainty() - 0.05f64.sqrt()).abs() < 1e-12);
+        assert!((x.mul(&Uncertain::exact(2.)).uncertainty() - 0.2).abs() < 1e-12);
+        assert!((x.mul(&y).uncertainty() - (0.16f64 + 0.36).sqrt()).abs() < 1e-12);
+        assert!(x.div(&Uncertain::exact(0.)).is_none());
+    }
+
+    #[test]
+    fn test_correlation() {
+        let x = Uncertain::new(12.3, 0.2);
+
+        assert_eq!(x.sub(&x).value(), 0.);
+        assert_eq!(x.sub(&x).to_string(), "0 ± 0");
+        assert!((x.add(&x).uncertainty() - 0.4).abs() < 1e-12);
+        assert!((x.div(&x).unwrap().uncertainty()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_map() {
+        let x = Uncertain::new(4., 0.2);
+
+        assert!((x.map(f64::sqrt).unwrap().uncertainty() - 0.05).abs() < 1e-9);
+        assert!((x.powf(&Uncertain::exact(2.)).unwrap().uncertainty() - 1.6).abs() < 1e-12);
+        assert!(Uncertain::new(-1., 0.1)
+            .powf(&Uncertain::exact(0.5))
+            .is_none());
+
+        // Not differentiable at 0, unless the value is exact
+        let zero = Uncertain::new(0., 0.1);
+
+        assert!(zero.map(f64::sqrt).is_none());
+        assert!(zero.powf(&Uncertain::exact(0.5)).is_none());
+        assert_eq!(Uncertain::exact(0.).map(f64::sqrt).unwrap().value(), 0.);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Uncertain::new(12.3, 0.2).to_string(), "12.3 ± 0.2");
+        assert_eq!(Uncertain::new(12.345, 0.0123).to_string(), "12.345 ± 0.012");
+        assert_eq!(Uncertain::new(1234., 56.).to_string(), "1230 ± 60");
+        assert_eq!(Uncertain::exact(2.).to_string(), "2 ± 0");
+    }
+}